@@ -0,0 +1,37 @@
+//! Records the git commit and build time reported by `/api/v1/version`.
+
+#![allow(non_snake_case)]
+
+use std::process::Command;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+fn main() {
+    // Builds from a source tarball or a Docker context without .git can pass the SHA in
+    println!("cargo:rerun-if-env-changed=SPARK_GIT_SHA");
+    let sha = std::env::var("SPARK_GIT_SHA").ok().or_else(git_sha).unwrap_or_else(|| "unknown".into());
+
+    // Rebuild when HEAD moves, so the SHA and time follow new commits
+    if let Some(gitDir) = git(&["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={gitDir}/HEAD");
+        if let Some(headRef) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={gitDir}/{headRef}");
+        }
+    }
+
+    let builtAt = OffsetDateTime::now_utc().format(&Rfc3339).unwrap_or_else(|_| "unknown".into());
+    println!("cargo:rustc-env=SPARK_GIT_SHA={sha}");
+    println!("cargo:rustc-env=SPARK_BUILT_AT={builtAt}");
+}
+
+fn git_sha() -> Option<String> {
+    git(&["rev-parse", "--short=12", "HEAD"])
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
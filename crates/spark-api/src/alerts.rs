@@ -0,0 +1,187 @@
+//! Threshold alerts, evaluated against every metrics sample the history sampler takes.
+
+use serde::Deserialize;
+use spark_types::{Alert, AlertKind, SystemMetrics};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+pub type SharedAlerts = Arc<AlertMonitor>;
+
+/// The `[alerts]` section of the config. A threshold of 0 turns that alert off.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct AlertOptions {
+    /// GPU temperature in °C, checked per device.
+    pub gpu_temp_c: f32,
+    /// System memory used, in percent.
+    pub mem_pct: f32,
+    /// Disk space used, in percent, checked per configured mount.
+    pub disk_pct: f32,
+}
+
+impl AlertOptions {
+    /// Whether any threshold is set, i.e. alerts can be raised at all.
+    pub fn any_enabled(&self) -> bool {
+        self.gpu_temp_c > 0.0 || self.mem_pct > 0.0 || self.disk_pct > 0.0
+    }
+}
+
+impl Default for AlertOptions {
+    fn default() -> Self {
+        Self {
+            gpu_temp_c: 85.0,
+            mem_pct: 90.0,
+            disk_pct: 90.0,
+        }
+    }
+}
+
+/// Holds the alerts active as of the latest sample.
+pub struct AlertMonitor {
+    options: AlertOptions,
+    active: RwLock<Vec<Alert>>,
+}
+
+impl AlertMonitor {
+    pub fn new(options: AlertOptions) -> Self {
+        Self {
+            options,
+            active: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn options(&self) -> &AlertOptions {
+        &self.options
+    }
+
+    /// Re-evaluate against `metrics`, logging alerts as they are raised and cleared.
+    pub async fn update(&self, metrics: &SystemMetrics) {
+        let alerts = evaluate(&self.options, metrics);
+        let mut active = self.active.write().await;
+        let before: HashSet<String> = active.iter().map(Alert::key).collect();
+        let after: HashSet<String> = alerts.iter().map(Alert::key).collect();
+        for alert in alerts.iter().filter(|a| !before.contains(&a.key())) {
+            warn!("alert raised: {}", alert.message);
+        }
+        for key in before.difference(&after) {
+            info!("alert cleared: {key}");
+        }
+        *active = alerts;
+    }
+
+    pub async fn active(&self) -> Vec<Alert> {
+        self.active.read().await.clone()
+    }
+}
+
+/// Every threshold in `options` that `metrics` exceeds.
+pub fn evaluate(options: &AlertOptions, metrics: &SystemMetrics) -> Vec<Alert> {
+    let mut alerts = Vec::new();
+
+    if options.gpu_temp_c > 0.0 {
+        for gpu in &metrics.gpus {
+            let temp = gpu.temperature_c as f32;
+            if temp >= options.gpu_temp_c {
+                alerts.push(Alert {
+                    kind: AlertKind::GpuTemperature,
+                    target: gpu.index.to_string(),
+                    value: temp,
+                    threshold: options.gpu_temp_c,
+                    message: format!(
+                        "GPU {} at {temp:.0}\u{00B0}C (limit {:.0}\u{00B0}C)",
+                        gpu.index, options.gpu_temp_c
+                    ),
+                });
+            }
+        }
+    }
+
+    if options.mem_pct > 0.0 {
+        if let Some(pct) = percent(metrics.memory.used_bytes, metrics.memory.total_bytes) {
+            if pct >= options.mem_pct {
+                alerts.push(Alert {
+                    kind: AlertKind::Memory,
+                    target: String::new(),
+                    value: pct,
+                    threshold: options.mem_pct,
+                    message: format!("Memory {pct:.0}% used (limit {:.0}%)", options.mem_pct),
+                });
+            }
+        }
+    }
+
+    if options.disk_pct > 0.0 {
+        for disk in &metrics.disks {
+            let Some(pct) = percent(disk.used_bytes, disk.total_bytes) else {
+                continue;
+            };
+            if pct >= options.disk_pct {
+                alerts.push(Alert {
+                    kind: AlertKind::Disk,
+                    target: disk.mount_point.clone(),
+                    value: pct,
+                    threshold: options.disk_pct,
+                    message: format!(
+                        "Disk {} {pct:.0}% used (limit {:.0}%)",
+                        disk.mount_point, options.disk_pct
+                    ),
+                });
+            }
+        }
+    }
+
+    alerts
+}
+
+fn percent(used: u64, total: u64) -> Option<f32> {
+    (total > 0).then(|| (used as f64 / total as f64 * 100.0) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spark_types::{DiskMetrics, GpuMetrics, MemoryMetrics};
+
+    #[test]
+    fn reports_only_exceeded_thresholds() {
+        let metrics = SystemMetrics {
+            gpus: vec![
+                GpuMetrics {
+                    index: 0,
+                    temperature_c: 87,
+                    ..Default::default()
+                },
+                GpuMetrics {
+                    index: 1,
+                    temperature_c: 60,
+                    ..Default::default()
+                },
+            ],
+            memory: MemoryMetrics {
+                total_bytes: 100,
+                used_bytes: 50,
+                ..Default::default()
+            },
+            disks: vec![DiskMetrics {
+                mount_point: "/data".into(),
+                total_bytes: 100,
+                used_bytes: 95,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let alerts = evaluate(&AlertOptions::default(), &metrics);
+        let keys: Vec<String> = alerts.iter().map(Alert::key).collect();
+        assert_eq!(keys, ["GpuTemperature:0", "Disk:/data"]);
+
+        let disabled = AlertOptions {
+            gpu_temp_c: 0.0,
+            disk_pct: 0.0,
+            ..Default::default()
+        };
+        assert!(evaluate(&disabled, &metrics).is_empty());
+    }
+}
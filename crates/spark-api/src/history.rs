@@ -0,0 +1,357 @@
+use serde::Deserialize;
+use spark_providers::ProviderOptions;
+use spark_types::{HistoryResponse, HistorySample, SystemMetrics};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+#[cfg(feature = "history-sqlite")]
+use tracing::warn;
+
+use crate::alerts::SharedAlerts;
+#[cfg(feature = "history-sqlite")]
+use crate::history_db::HistoryDb;
+
+pub type SharedHistory = Arc<RwLock<HistoryStore>>;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct HistoryOptions {
+    pub sample_interval_secs: u64,
+    pub tiers: Vec<TierOptions>,
+    /// SQLite file every sample is also written to; needs the `history-sqlite` feature.
+    pub db_path: Option<String>,
+    /// How long rows are kept in `db_path`; 0 keeps them forever.
+    pub db_retention_secs: u64,
+}
+
+/// Samples averaged into `resolution_secs` buckets, kept for `retention_secs`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct TierOptions {
+    pub resolution_secs: u64,
+    pub retention_secs: u64,
+}
+
+impl Default for HistoryOptions {
+    fn default() -> Self {
+        Self {
+            sample_interval_secs: 5,
+            tiers: vec![
+                // Full resolution for an hour
+                TierOptions {
+                    resolution_secs: 5,
+                    retention_secs: 3600,
+                },
+                // 1-minute averages for a day
+                TierOptions {
+                    resolution_secs: 60,
+                    retention_secs: 86_400,
+                },
+                // 5-minute averages for a week
+                TierOptions {
+                    resolution_secs: 300,
+                    retention_secs: 604_800,
+                },
+            ],
+            db_path: None,
+            // 30 days
+            db_retention_secs: 2_592_000,
+        }
+    }
+}
+
+struct Tier {
+    resolution_secs: u64,
+    retention_secs: u64,
+    samples: VecDeque<HistorySample>,
+    /// Samples in the bucket currently being filled.
+    pending: Vec<HistorySample>,
+}
+
+/// Tiered, bounded in-memory metrics history.
+///
+/// Every sample goes into each tier. Tiers coarser than the sample interval
+/// average their samples per bucket, and every tier drops entries older than
+/// its retention, so memory use is fixed by the configuration.
+///
+/// With the `history-sqlite` feature and a `db_path`, [`record`] also writes samples to
+/// SQLite and [`query`] answers from there, so history survives restarts and can reach
+/// back further than the tiers. The database is only touched by those two, on a blocking
+/// thread and without the store's lock held.
+pub struct HistoryStore {
+    sample_interval_secs: u64,
+    tiers: Vec<Tier>,
+    recorded: u64,
+    #[cfg(feature = "history-sqlite")]
+    db: Option<Arc<HistoryDb>>,
+}
+
+impl HistoryStore {
+    pub fn new(options: &HistoryOptions) -> Self {
+        let mut tiers: Vec<Tier> = options
+            .tiers
+            .iter()
+            .map(|t| Tier {
+                resolution_secs: t.resolution_secs.max(1),
+                retention_secs: t.retention_secs,
+                samples: VecDeque::new(),
+                pending: Vec::new(),
+            })
+            .collect();
+        tiers.sort_by_key(|t| t.resolution_secs);
+
+        Self {
+            sample_interval_secs: options.sample_interval_secs.max(1),
+            tiers,
+            recorded: 0,
+            #[cfg(feature = "history-sqlite")]
+            db: None,
+        }
+    }
+
+    /// Like [`HistoryStore::new`], but also opens `db_path` when one is configured.
+    pub fn open(options: &HistoryOptions) -> Result<Self, String> {
+        #[allow(unused_mut)]
+        let mut store = Self::new(options);
+        if let Some(path) = &options.db_path {
+            #[cfg(feature = "history-sqlite")]
+            {
+                store.db = Some(Arc::new(HistoryDb::open(path, options.db_retention_secs)?));
+            }
+            #[cfg(not(feature = "history-sqlite"))]
+            return Err(format!(
+                "history.db_path is set to {path}, but this build lacks the history-sqlite feature"
+            ));
+        }
+        Ok(store)
+    }
+
+    /// Whether at least one sample has been recorded since startup.
+    pub fn has_samples(&self) -> bool {
+        self.recorded > 0
+    }
+
+    /// Add `sample` to the in-memory tiers.
+    pub fn record(&mut self, sample: HistorySample) {
+        self.recorded += 1;
+        let sampleInterval = self.sample_interval_secs;
+        for tier in &mut self.tiers {
+            if tier.resolution_secs <= sampleInterval {
+                tier.samples.push_back(sample.clone());
+            } else {
+                let bucket = sample.timestamp - sample.timestamp % tier.resolution_secs;
+                let pendingBucket = tier
+                    .pending
+                    .first()
+                    .map(|p| p.timestamp - p.timestamp % tier.resolution_secs);
+                if pendingBucket.is_some_and(|b| b != bucket) {
+                    let mut averaged = average(&tier.pending);
+                    averaged.timestamp = pendingBucket.unwrap_or(bucket);
+                    tier.samples.push_back(averaged);
+                    tier.pending.clear();
+                }
+                tier.pending.push(sample.clone());
+            }
+
+            let cutoff = sample.timestamp.saturating_sub(tier.retention_secs);
+            while tier.samples.front().is_some_and(|s| s.timestamp < cutoff) {
+                tier.samples.pop_front();
+            }
+        }
+    }
+
+    /// In-memory samples from the Unix timestamp `since` onwards, so a client can fetch
+    /// only what it hasn't seen yet.
+    pub fn query_since(&self, since: u64) -> HistoryResponse {
+        self.query(unix_now().saturating_sub(since))
+    }
+
+    /// In-memory samples covering the last `range_secs`, from the finest tier that retains
+    /// that far back.
+    pub fn query(&self, range_secs: u64) -> HistoryResponse {
+        let cutoff = unix_now().saturating_sub(range_secs);
+        let Some(tier) = self.tier_for(range_secs) else {
+            return HistoryResponse::default();
+        };
+
+        HistoryResponse {
+            resolution_secs: tier.resolution_secs.max(self.sample_interval_secs),
+            samples: tier
+                .samples
+                .iter()
+                .filter(|s| s.timestamp >= cutoff)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn tier_for(&self, range_secs: u64) -> Option<&Tier> {
+        self.tiers
+            .iter()
+            .find(|t| t.retention_secs >= range_secs)
+            .or_else(|| self.tiers.last())
+    }
+
+    /// Resolution a query over `range_secs` is answered at.
+    #[cfg(feature = "history-sqlite")]
+    fn resolution_for(&self, range_secs: u64) -> u64 {
+        self.tier_for(range_secs)
+            .map_or(0, |t| t.resolution_secs)
+            .max(self.sample_interval_secs)
+    }
+}
+
+/// Record `sample` in `history`, and in its database when there is one. The insert runs
+/// on a blocking thread after the lock is released, so a slow disk holds up neither the
+/// runtime nor readers.
+pub async fn record(history: &SharedHistory, sample: HistorySample) {
+    let mut store = history.write().await;
+    store.record(sample.clone());
+    #[cfg(feature = "history-sqlite")]
+    if let Some(db) = store.db.clone() {
+        drop(store);
+        match tokio::task::spawn_blocking(move || db.insert(&sample)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => warn!("{e}"),
+            Err(e) => warn!("history insert task failed: {e}"),
+        }
+    }
+}
+
+/// Samples covering the last `range_secs`. With a database they come from it, averaged to
+/// the resolution of the tier that would otherwise answer, queried on a blocking thread.
+pub async fn query(history: &SharedHistory, range_secs: u64) -> HistoryResponse {
+    #[cfg(feature = "history-sqlite")]
+    {
+        let (db, resolution) = {
+            let store = history.read().await;
+            (store.db.clone(), store.resolution_for(range_secs))
+        };
+        if let Some(db) = db {
+            let cutoff = unix_now().saturating_sub(range_secs);
+            match tokio::task::spawn_blocking(move || db.query(cutoff, resolution)).await {
+                Ok(Ok(samples)) => {
+                    return HistoryResponse {
+                        resolution_secs: resolution,
+                        samples,
+                    }
+                }
+                // Fall back to the in-memory tiers
+                Ok(Err(e)) => warn!("{e}"),
+                Err(e) => warn!("history query task failed: {e}"),
+            }
+        }
+    }
+    history.read().await.query(range_secs)
+}
+
+/// Samples from the Unix timestamp `since` onwards; see [`query`].
+pub async fn query_since(history: &SharedHistory, since: u64) -> HistoryResponse {
+    query(history, unix_now().saturating_sub(since)).await
+}
+
+fn average(samples: &[HistorySample]) -> HistorySample {
+    let n = samples.len().max(1) as f64;
+    let mean = |f: fn(&HistorySample) -> f64| samples.iter().map(f).sum::<f64>() / n;
+    HistorySample {
+        timestamp: samples.first().map(|s| s.timestamp).unwrap_or(0),
+        gpu_utilization_pct: mean(|s| s.gpu_utilization_pct as f64) as f32,
+        gpu_temperature_c: mean(|s| s.gpu_temperature_c as f64) as f32,
+        gpu_power_draw_w: mean(|s| s.gpu_power_draw_w as f64) as f32,
+        gpu_memory_used_mib: mean(|s| s.gpu_memory_used_mib as f64) as f32,
+        memory_used_bytes: mean(|s| s.memory_used_bytes),
+        cpu_load_1m: mean(|s| s.cpu_load_1m as f64) as f32,
+        disk_used_bytes: mean(|s| s.disk_used_bytes),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn to_sample(metrics: &SystemMetrics, timestamp: u64) -> HistorySample {
+    // History tracks the first GPU only
+    let gpu = metrics.gpus.first().cloned().unwrap_or_default();
+    HistorySample {
+        timestamp,
+        gpu_utilization_pct: gpu.utilization_pct,
+        gpu_temperature_c: gpu.temperature_c as f32,
+        gpu_power_draw_w: gpu.power_draw_w,
+        gpu_memory_used_mib: gpu.memory_used_mib as f32,
+        memory_used_bytes: metrics.memory.used_bytes as f64,
+        cpu_load_1m: metrics.cpu.load_1m,
+        disk_used_bytes: metrics.disk.used_bytes as f64,
+    }
+}
+
+/// Spawn the background task that samples system metrics into `history` and checks
+/// each sample against the alert thresholds.
+pub fn spawn_sampler(
+    history: SharedHistory,
+    alerts: SharedAlerts,
+    options: &HistoryOptions,
+    providers: ProviderOptions,
+) {
+    let interval = Duration::from_secs(options.sample_interval_secs.max(1));
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let metrics = spark_providers::collect_system_metrics(&providers).await;
+            alerts.update(&metrics).await;
+            let sample = to_sample(&metrics, unix_now());
+            record(&history, sample).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_since_skips_older_samples() {
+        let mut store = HistoryStore::new(&HistoryOptions::default());
+        let now = unix_now();
+        for age in [120, 60, 10] {
+            store.record(HistorySample {
+                timestamp: now - age,
+                ..Default::default()
+            });
+        }
+
+        let timestamps: Vec<u64> = store.query_since(now - 90).samples.iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, [now - 60, now - 10]);
+        assert_eq!(store.query(3600).samples.len(), 3);
+    }
+
+    #[cfg(feature = "history-sqlite")]
+    #[tokio::test]
+    async fn database_is_written_and_read_off_the_lock() {
+        let options = HistoryOptions {
+            db_path: Some(":memory:".into()),
+            ..Default::default()
+        };
+        let history: SharedHistory = Arc::new(RwLock::new(HistoryStore::open(&options).unwrap()));
+        let now = unix_now();
+        for age in [60, 10] {
+            record(
+                &history,
+                HistorySample {
+                    timestamp: now - age,
+                    cpu_load_1m: 1.0,
+                    ..Default::default()
+                },
+            )
+            .await;
+        }
+
+        let response = query_since(&history, now - 30).await;
+        assert_eq!(response.resolution_secs, 5);
+        assert_eq!(response.samples.len(), 1);
+        assert!(history.read().await.has_samples());
+    }
+}
@@ -0,0 +1,124 @@
+//! SQLite-backed metrics history, built with the `history-sqlite` feature.
+
+use rusqlite::{params, Connection};
+use spark_types::HistorySample;
+use std::sync::Mutex;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS samples (
+        timestamp INTEGER PRIMARY KEY,
+        gpu_utilization_pct REAL NOT NULL,
+        gpu_temperature_c REAL NOT NULL,
+        gpu_power_draw_w REAL NOT NULL,
+        gpu_memory_used_mib REAL NOT NULL,
+        memory_used_bytes REAL NOT NULL,
+        cpu_load_1m REAL NOT NULL,
+        disk_used_bytes REAL NOT NULL
+    );
+";
+
+/// Every history sample, one row each, kept for `retention_secs` (0 keeps them forever).
+/// Calls block on disk I/O, so async code runs them with `spawn_blocking`.
+pub struct HistoryDb {
+    // A connection isn't Sync, and the sampler and history queries share this one
+    conn: Mutex<Connection>,
+    retention_secs: u64,
+}
+
+impl HistoryDb {
+    /// Open or create the database at `path`.
+    pub fn open(path: &str, retention_secs: u64) -> Result<Self, String> {
+        let conn = Connection::open(path)
+            .map_err(|e| format!("failed to open history database {path}: {e}"))?;
+        conn.execute_batch(SCHEMA)
+            .map_err(|e| format!("failed to create history table in {path}: {e}"))?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            retention_secs,
+        })
+    }
+
+    /// Store `sample`, then drop rows that have aged past the retention.
+    pub fn insert(&self, sample: &HistorySample) -> Result<(), String> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        conn.execute(
+            "INSERT OR REPLACE INTO samples VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                sample.timestamp as i64,
+                sample.gpu_utilization_pct,
+                sample.gpu_temperature_c,
+                sample.gpu_power_draw_w,
+                sample.gpu_memory_used_mib,
+                sample.memory_used_bytes,
+                sample.cpu_load_1m,
+                sample.disk_used_bytes,
+            ],
+        )
+        .map_err(|e| format!("failed to insert history sample: {e}"))?;
+
+        if self.retention_secs > 0 {
+            let cutoff = sample.timestamp.saturating_sub(self.retention_secs);
+            conn.execute("DELETE FROM samples WHERE timestamp < ?1", params![cutoff as i64])
+                .map_err(|e| format!("failed to prune history: {e}"))?;
+        }
+        Ok(())
+    }
+
+    /// Samples from the Unix timestamp `since` onwards, averaged into `resolution_secs` buckets.
+    pub fn query(&self, since: u64, resolution_secs: u64) -> Result<Vec<HistorySample>, String> {
+        let conn = self.conn.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stmt = conn
+            .prepare_cached(
+                "SELECT timestamp / ?2 * ?2 AS bucket,
+                    AVG(gpu_utilization_pct), AVG(gpu_temperature_c), AVG(gpu_power_draw_w),
+                    AVG(gpu_memory_used_mib), AVG(memory_used_bytes), AVG(cpu_load_1m),
+                    AVG(disk_used_bytes)
+                 FROM samples WHERE timestamp >= ?1
+                 GROUP BY bucket ORDER BY bucket",
+            )
+            .map_err(|e| format!("failed to query history: {e}"))?;
+        let rows = stmt
+            .query_map(params![since as i64, resolution_secs.max(1) as i64], |row| {
+                Ok(HistorySample {
+                    timestamp: row.get::<_, i64>(0)? as u64,
+                    gpu_utilization_pct: row.get::<_, f64>(1)? as f32,
+                    gpu_temperature_c: row.get::<_, f64>(2)? as f32,
+                    gpu_power_draw_w: row.get::<_, f64>(3)? as f32,
+                    gpu_memory_used_mib: row.get::<_, f64>(4)? as f32,
+                    memory_used_bytes: row.get(5)?,
+                    cpu_load_1m: row.get::<_, f64>(6)? as f32,
+                    disk_used_bytes: row.get(7)?,
+                })
+            })
+            .map_err(|e| format!("failed to query history: {e}"))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("failed to read history: {e}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_and_prunes_samples() {
+        let db = HistoryDb::open(":memory:", 650).unwrap();
+        let sample = |timestamp, cpu_load_1m| HistorySample {
+            timestamp,
+            cpu_load_1m,
+            ..Default::default()
+        };
+        let points = |samples: Vec<HistorySample>| -> Vec<(u64, f32)> {
+            samples.iter().map(|s| (s.timestamp, s.cpu_load_1m)).collect()
+        };
+        for (timestamp, load) in [(1000, 1.0), (1010, 3.0), (1070, 5.0)] {
+            db.insert(&sample(timestamp, load)).unwrap();
+        }
+        assert_eq!(points(db.query(0, 60).unwrap()), [(960, 2.0), (1020, 5.0)]);
+        assert_eq!(points(db.query(1005, 1).unwrap()), [(1010, 3.0), (1070, 5.0)]);
+
+        // Pushes the first two samples past the 650s retention
+        db.insert(&sample(1700, 7.0)).unwrap();
+        assert_eq!(points(db.query(0, 60).unwrap()), [(1020, 5.0), (1680, 7.0)]);
+    }
+}
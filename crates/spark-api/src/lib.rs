@@ -1,16 +1,28 @@
 #![allow(non_snake_case)]
 
+pub mod alerts;
+pub mod error;
+pub mod history;
+#[cfg(feature = "history-sqlite")]
+pub mod history_db;
 pub mod middleware;
 pub mod routes;
 
 use axum::Router;
 
 use crate::middleware::auth::AppState;
+use crate::middleware::cors::cors_layer;
 
 pub fn api_router(state: AppState) -> Router {
     let apiRoutes = routes::api_routes(state.clone());
+    let cors = cors_layer(&state.cors_allowed_origins);
 
-    Router::new()
+    let router = Router::new()
         .merge(apiRoutes)
-        .with_state(state)
+        .with_state(state);
+    // Outermost, so preflight requests are answered before the auth check
+    match cors {
+        Some(cors) => router.layer(cors),
+        None => router,
+    }
 }
@@ -1,4 +1,686 @@
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+};
+use serde::Deserialize;
+use spark_providers::ProviderOptions;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::alerts::SharedAlerts;
+use crate::error::ApiError;
+use crate::history::SharedHistory;
+
+/// Cookie holding the token after a successful login.
+pub const SESSION_COOKIE: &str = "session_token";
+/// Sessions last a month; the token itself doesn't expire.
+const SESSION_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+/// The token early example configs shipped with, so it is effectively public.
+pub const PLACEHOLDER_TOKEN: &str = "change-me-on-first-run";
+/// Shorter tokens are refused at startup unless `--allow-default-token` is passed.
+pub const MIN_TOKEN_LEN: usize = 16;
+/// Label the single `auth.token` / `auth.token_hash` is logged under.
+pub const DEFAULT_TOKEN_LABEL: &str = "default";
+
 #[derive(Clone)]
 pub struct AppState {
     pub config_path: String,
+    pub providers: ProviderOptions,
+    pub history: SharedHistory,
+    /// Alerts raised by the latest history sample.
+    pub alerts: SharedAlerts,
+    /// How often `/api/v1/system/stream` pushes a frame.
+    pub stream_interval: Duration,
+    /// Serve `/metrics` without authentication, for Prometheus scrapers.
+    pub public_metrics: bool,
+    /// Holds `None` when no token is configured, which leaves the console open (LAN-only use).
+    pub auth_token: SharedAuthToken,
+    /// Re-reads `config_path` and applies its `[auth]` section; `None` when reloading
+    /// isn't supported.
+    pub reload_config: Option<ConfigReload>,
+    pub cookies: CookieOptions,
+    /// Origins allowed to call the API from a browser; empty means same-origin only.
+    pub cors_allowed_origins: Vec<String>,
+}
+
+/// Called by `POST /api/v1/admin/reload` (and by `main` on SIGHUP). It lives in `main`,
+/// which owns the config format, and swaps the new token into [`AppState::auth_token`].
+pub type ConfigReload = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthOptions {
+    /// Shared secret for the console and API, in plaintext. Prefer `token_hash`.
+    pub token: Option<String>,
+    /// Argon2 PHC hash of the token (`spark-console --hash-token <token>`). Takes
+    /// precedence over `token`. Leave both unset to disable authentication.
+    pub token_hash: Option<String>,
+    /// Let `/metrics` through without a token so Prometheus can scrape it.
+    pub public_metrics: bool,
+    /// Further tokens (`[[auth.tokens]]`), e.g. one per person or script. Any of them is
+    /// accepted alongside `token`, and the access log records which label was used.
+    pub tokens: Vec<LabeledToken>,
+}
+
+/// One `[[auth.tokens]]` entry. As with the single token, `token_hash` takes precedence.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct LabeledToken {
+    pub label: String,
+    pub token: Option<String>,
+    pub token_hash: Option<String>,
+}
+
+impl AuthOptions {
+    pub fn auth_token(&self) -> Option<AuthToken> {
+        let credentials: Vec<Credential> = self
+            .labeled()
+            .filter_map(|(label, token, hash)| {
+                Some(Credential {
+                    label: label.to_string(),
+                    secret: Secret::from_config(token, hash)?,
+                    verified: Mutex::new(None),
+                })
+            })
+            .collect();
+        (!credentials.is_empty()).then(|| AuthToken {
+            credentials: Arc::new(credentials),
+        })
+    }
+
+    /// Every configured token as `(label, token, token_hash)`, the single one first.
+    fn labeled(&self) -> impl Iterator<Item = (&str, Option<&str>, Option<&str>)> {
+        std::iter::once((DEFAULT_TOKEN_LABEL, self.token.as_deref(), self.token_hash.as_deref())).chain(
+            self.tokens
+                .iter()
+                .map(|entry| (entry.label.as_str(), entry.token.as_deref(), entry.token_hash.as_deref())),
+        )
+    }
+
+    /// Plaintext tokens in use (those without a hash), with their labels.
+    fn plaintext_tokens(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.labeled().filter_map(|(label, token, hash)| match Secret::from_config(token, hash)? {
+            Secret::Plain(_) => Some((label, token?)),
+            Secret::Hash(_) => None,
+        })
+    }
+
+    /// Whether any token is stored in plaintext rather than as a hash.
+    pub fn has_plaintext_token(&self) -> bool {
+        self.plaintext_tokens().next().is_some()
+    }
+
+    /// Why a plaintext token is unsafe to run with, when one in use is. Hashed tokens
+    /// can't be checked here; `--hash-token` checks them before hashing.
+    pub fn weak_token_reason(&self) -> Option<String> {
+        self.plaintext_tokens().find_map(|(label, token)| {
+            let reason = weak_token_reason(token)?;
+            Some(if label == DEFAULT_TOKEN_LABEL {
+                reason
+            } else {
+                format!("{reason} (auth.tokens \"{label}\")")
+            })
+        })
+    }
+
+    /// Problems with `[[auth.tokens]]` that would make the access log ambiguous or leave
+    /// an entry silently unusable: missing or repeated labels, and entries with no token.
+    pub fn check_tokens(&self) -> Result<(), String> {
+        let mut seen = vec![DEFAULT_TOKEN_LABEL];
+        for entry in &self.tokens {
+            let label = entry.label.trim();
+            if label.is_empty() {
+                return Err("every [[auth.tokens]] entry needs a label".into());
+            }
+            if seen.contains(&label) {
+                return Err(format!("auth token label \"{label}\" is used more than once"));
+            }
+            if Secret::from_config(entry.token.as_deref(), entry.token_hash.as_deref()).is_none() {
+                return Err(format!("[[auth.tokens]] entry \"{label}\" has no token or token_hash"));
+            }
+            seen.push(label);
+        }
+        Ok(())
+    }
+}
+
+/// Why `token` is too easy to guess to protect the console, if it is.
+pub fn weak_token_reason(token: &str) -> Option<String> {
+    if token == PLACEHOLDER_TOKEN {
+        Some(format!("the auth token is the placeholder \"{PLACEHOLDER_TOKEN}\""))
+    } else if token.chars().count() < MIN_TOKEN_LEN {
+        Some(format!("the auth token is shorter than {MIN_TOKEN_LEN} characters"))
+    } else {
+        None
+    }
+}
+
+/// The configured tokens. `main` puts them in a [`SharedAuthToken`] in both `AppState`
+/// and Leptos context, and both login paths and the middleware check against that.
+#[derive(Clone)]
+pub struct AuthToken {
+    credentials: Arc<Vec<Credential>>,
+}
+
+struct Credential {
+    label: String,
+    secret: Secret,
+    /// Last token that matched the hash, so each request doesn't pay for an argon2 verify.
+    verified: Mutex<Option<String>>,
+}
+
+enum Secret {
+    Plain(String),
+    Hash(String),
+}
+
+impl Secret {
+    fn from_config(token: Option<&str>, hash: Option<&str>) -> Option<Self> {
+        match (hash, token) {
+            (Some(hash), _) if !hash.is_empty() => Some(Secret::Hash(hash.to_string())),
+            (_, Some(token)) if !token.is_empty() => Some(Secret::Plain(token.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl Credential {
+    /// Constant-time check against the plaintext token or the remembered hash match.
+    fn matches_known(&self, presented: &str) -> bool {
+        match &self.secret {
+            Secret::Plain(token) => tokens_match(token.as_bytes(), presented.as_bytes()),
+            Secret::Hash(_) => self
+                .verified
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_deref()
+                .is_some_and(|known| tokens_match(known.as_bytes(), presented.as_bytes())),
+        }
+    }
+
+    fn matches_hash(&self, presented: &str) -> bool {
+        let Secret::Hash(hash) = &self.secret else {
+            return false;
+        };
+        // argon2 compares the derived hashes in constant time
+        let matches = PasswordHash::new(hash).is_ok_and(|parsed| {
+            Argon2::default()
+                .verify_password(presented.as_bytes(), &parsed)
+                .is_ok()
+        });
+        if matches {
+            *self.verified.lock().unwrap_or_else(|e| e.into_inner()) = Some(presented.to_string());
+        }
+        matches
+    }
+}
+
+impl AuthToken {
+    pub fn verify(&self, presented: &str) -> bool {
+        self.authenticate(presented).is_some()
+    }
+
+    /// The label of the token `presented` matches. Every plaintext token (and remembered
+    /// hash match) is compared, so timing doesn't reveal which one was close; only then
+    /// are the hashes tried, since each argon2 verify is deliberately slow.
+    pub fn authenticate(&self, presented: &str) -> Option<&str> {
+        let mut found = None;
+        for credential in self.credentials.iter() {
+            if credential.matches_known(presented) && found.is_none() {
+                found = Some(credential.label.as_str());
+            }
+        }
+        found.or_else(|| {
+            self.credentials
+                .iter()
+                .find(|credential| credential.matches_hash(presented))
+                .map(|credential| credential.label.as_str())
+        })
+    }
+
+    /// Label of a configured hash that can't be parsed, which locks that token out.
+    pub fn invalid_hash_label(&self) -> Option<&str> {
+        self.credentials
+            .iter()
+            .find(|credential| matches!(&credential.secret, Secret::Hash(hash) if PasswordHash::new(hash).is_err()))
+            .map(|credential| credential.label.as_str())
+    }
+}
+
+/// Argon2 PHC string for `auth.token_hash`.
+pub fn hash_token(token: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(token.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("failed to hash token: {e}"))
+}
+
+/// Constant-time token comparison. The loop always runs over the expected token's
+/// length, so timing doesn't reveal how long the presented token is or where it differs.
+fn tokens_match(expected: &[u8], presented: &[u8]) -> bool {
+    let mut equal: Choice = (expected.len() as u64).ct_eq(&(presented.len() as u64));
+    for (i, byte) in expected.iter().enumerate() {
+        let other = presented.get(i).copied().unwrap_or(0);
+        equal &= byte.ct_eq(&other);
+    }
+    equal.into()
+}
+
+/// The token in use, swapped on config reload. Sessions hold the token itself, so once it
+/// changes every cookie issued for the old one stops verifying.
+#[derive(Clone, Default)]
+pub struct SharedAuthToken(Arc<RwLock<Option<AuthToken>>>);
+
+impl SharedAuthToken {
+    pub fn new(token: Option<AuthToken>) -> Self {
+        Self(Arc::new(RwLock::new(token)))
+    }
+
+    /// A copy of the current token, so a slow argon2 verify doesn't hold the lock.
+    pub fn get(&self) -> Option<AuthToken> {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    pub fn replace(&self, token: Option<AuthToken>) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = token;
+    }
+}
+
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AuthToken(..)")
+    }
+}
+
+/// How the session cookie is issued. Both login paths (the API route and the login
+/// page's server function) build it from the same settings.
+#[derive(Clone, Copy, Debug)]
+pub struct CookieOptions {
+    /// Mark the cookie `Secure`. Browsers drop such cookies over plain HTTP, so this must
+    /// be off when the console isn't served over HTTPS.
+    pub secure: bool,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self { secure: true }
+    }
+}
+
+impl CookieOptions {
+    /// `Set-Cookie` value that logs the browser in with `token`, or `None` when `token`
+    /// isn't one of `expected`, so a submitted value is never echoed back unchecked. A
+    /// match is recorded on the request's span like any authenticated request.
+    pub fn session_cookie(&self, expected: &AuthToken, token: &str) -> Option<String> {
+        record_auth_label(expected.authenticate(token)?);
+        let secure = if self.secure { "; Secure" } else { "" };
+        Some(format!(
+            "{SESSION_COOKIE}={token}; Path=/; HttpOnly; SameSite=Strict; Max-Age={SESSION_MAX_AGE_SECS}{secure}"
+        ))
+    }
+}
+
+/// Token from an `Authorization: Bearer` header, falling back to the session cookie.
+fn presented_token(headers: &HeaderMap) -> Option<String> {
+    if let Some(bearer) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    {
+        return Some(bearer.trim().to_string());
+    }
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(|cookies| extract_cookie_value(cookies, SESSION_COOKIE))
+}
+
+/// Whether `headers` carry a configured token (always true with auth off). The matching
+/// token's label is recorded on the request's span, which the access log prints.
+pub fn is_authenticated(authToken: Option<&AuthToken>, headers: &HeaderMap) -> bool {
+    let Some(expected) = authToken else {
+        return true;
+    };
+    let Some(presented) = presented_token(headers) else {
+        return false;
+    };
+    match expected.authenticate(&presented) {
+        Some(label) => {
+            record_auth_label(label);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Note which token authenticated the current request, as the `auth` field of its span.
+pub fn record_auth_label(label: &str) {
+    tracing::Span::current().record("auth", tracing::field::display(label));
+}
+
+/// The token `headers` present, if it is `expected`. Lets a logged-in browser show the
+/// token it holds without the server ever storing it in plaintext.
+pub fn verified_token(expected: &AuthToken, headers: &HeaderMap) -> Option<String> {
+    presented_token(headers).filter(|token| expected.verify(token))
+}
+
+/// Reject unauthenticated API requests with 401.
+pub async fn require_api_auth(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    if is_authenticated(state.auth_token.get().as_ref(), request.headers()) {
+        next.run(request).await
+    } else {
+        ApiError::unauthorized("authentication required").into_response()
+    }
+}
+
+/// Send unauthenticated browsers to the login page. Server function calls (under `/api/`)
+/// get a 401 instead, since a redirect would be followed as a successful response.
+pub async fn require_page_auth(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    let public = path == "/login"
+        || path == "/api/login"
+        || path == "/healthz"
+        || path == "/readyz"
+        || path == "/api/v1/version"
+        || path == "/favicon.svg"
+        || path.starts_with("/pkg/");
+    if public || is_authenticated(state.auth_token.get().as_ref(), request.headers()) {
+        return next.run(request).await;
+    }
+    if path.starts_with("/api/") {
+        ApiError::unauthorized("authentication required").into_response()
+    } else {
+        Redirect::to("/login").into_response()
+    }
+}
+
+/// Find the value of cookie `name` in a `Cookie` request header.
+///
+/// Cookies are `;`-separated `name=value` pairs with optional whitespace around
+/// each pair. Names must match exactly, values may themselves contain `=`, and a
+/// value wrapped in double quotes is returned without them. Segments with no `=`
+/// (e.g. a stray `Secure` or `HttpOnly`) are skipped. When the name appears more
+/// than once the first match wins: browsers list cookies with longer paths first, so
+/// that is the most specific one.
+pub fn extract_cookie_value(header: &str, name: &str) -> Option<String> {
+    header.split(';').find_map(|segment| {
+        let (cookieName, value) = segment.split_once('=')?;
+        if cookieName.trim() != name {
+            return None;
+        }
+        let value = value.trim();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+        Some(value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        extract_cookie_value, hash_token, tokens_match, AuthOptions, CookieOptions, LabeledToken,
+        DEFAULT_TOKEN_LABEL, PLACEHOLDER_TOKEN,
+    };
+
+    #[test]
+    fn single_cookie() {
+        assert_eq!(
+            extract_cookie_value("session_token=abc123", "session_token").as_deref(),
+            Some("abc123")
+        );
+    }
+
+    #[test]
+    fn multiple_cookies_with_ows() {
+        let header = "theme=dark;  session_token=abc123 ;lang=en";
+        assert_eq!(
+            extract_cookie_value(header, "session_token").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(extract_cookie_value(header, "lang").as_deref(), Some("en"));
+    }
+
+    #[test]
+    fn does_not_match_name_suffix_or_prefix() {
+        let header = "foo_session_token=wrong; session_token_old=wrong2";
+        assert_eq!(extract_cookie_value(header, "session_token"), None);
+
+        let header = "foo_session_token=wrong; session_token=right";
+        assert_eq!(
+            extract_cookie_value(header, "session_token").as_deref(),
+            Some("right")
+        );
+    }
+
+    #[test]
+    fn value_containing_equals() {
+        assert_eq!(
+            extract_cookie_value("session_token=YWJj==; a=b", "session_token").as_deref(),
+            Some("YWJj==")
+        );
+    }
+
+    #[test]
+    fn quoted_value() {
+        assert_eq!(
+            extract_cookie_value("session_token=\"abc 123\"", "session_token").as_deref(),
+            Some("abc 123")
+        );
+        assert_eq!(
+            extract_cookie_value("a=1; session_token=\"abc\"; b=2", "session_token").as_deref(),
+            Some("abc")
+        );
+        // An unbalanced quote is kept as-is
+        assert_eq!(
+            extract_cookie_value("session_token=\"abc", "session_token").as_deref(),
+            Some("\"abc")
+        );
+    }
+
+    #[test]
+    fn attribute_like_segments_are_skipped() {
+        let header = "Secure; HttpOnly; session_token=abc; Path=/";
+        assert_eq!(
+            extract_cookie_value(header, "session_token").as_deref(),
+            Some("abc")
+        );
+        assert_eq!(extract_cookie_value(header, "Secure"), None);
+    }
+
+    #[test]
+    fn empty_and_missing() {
+        assert_eq!(extract_cookie_value("", "session_token"), None);
+        assert_eq!(extract_cookie_value(";;", "session_token"), None);
+        assert_eq!(
+            extract_cookie_value("session_token=", "session_token").as_deref(),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn first_match_wins() {
+        assert_eq!(
+            extract_cookie_value("session_token=a; session_token=b", "session_token").as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            extract_cookie_value("a=1;session_token=\"x\" ; session_token=y", "session_token").as_deref(),
+            Some("x")
+        );
+    }
+
+    #[test]
+    fn session_cookie_needs_a_configured_token() {
+        let expected = AuthOptions {
+            token: Some("abc".into()),
+            ..Default::default()
+        }
+        .auth_token()
+        .unwrap();
+
+        let secure = CookieOptions { secure: true }.session_cookie(&expected, "abc").unwrap();
+        assert!(secure.starts_with("session_token=abc; "));
+        assert!(secure.ends_with("; Secure"));
+        let plain = CookieOptions { secure: false }.session_cookie(&expected, "abc").unwrap();
+        assert!(!plain.contains("Secure"));
+
+        assert_eq!(CookieOptions::default().session_cookie(&expected, "abd"), None);
+        assert_eq!(CookieOptions::default().session_cookie(&expected, "x; Domain=evil"), None);
+    }
+
+    #[test]
+    fn token_comparison() {
+        assert!(tokens_match(b"abc123", b"abc123"));
+        assert!(!tokens_match(b"abc123", b"abc124"));
+        assert!(!tokens_match(b"abc123", b"abc12"));
+        assert!(!tokens_match(b"abc123", b"abc1234"));
+        assert!(!tokens_match(b"abc123", b""));
+        // A zero-padded prefix must not match
+        assert!(!tokens_match(b"abc\0", b"abc"));
+    }
+
+    #[test]
+    fn cookie_token_verifies() {
+        let token = AuthOptions {
+            token: Some("s3cret".into()),
+            token_hash: None,
+            public_metrics: false,
+            tokens: Vec::new(),
+        }
+        .auth_token()
+        .unwrap();
+
+        let presented = extract_cookie_value("theme=dark; session_token=s3cret", "session_token");
+        assert!(token.verify(&presented.unwrap()));
+
+        let presented = extract_cookie_value("session_token=s3cre", "session_token");
+        assert!(!token.verify(&presented.unwrap()));
+
+        let presented = extract_cookie_value("session_token=s3cret2", "session_token");
+        assert!(!token.verify(&presented.unwrap()));
+    }
+
+    #[test]
+    fn hashed_token_verifies() {
+        let token = AuthOptions {
+            token: Some("ignored".into()),
+            token_hash: Some(hash_token("s3cret").unwrap()),
+            public_metrics: false,
+            tokens: Vec::new(),
+        }
+        .auth_token()
+        .unwrap();
+
+        assert!(token.invalid_hash_label().is_none());
+        assert!(!token.verify("ignored"));
+        assert!(token.verify("s3cret"));
+        // Second check is served from the cache and must still reject others
+        assert!(token.verify("s3cret"));
+        assert!(!token.verify("s3cre"));
+    }
+
+    #[test]
+    fn malformed_hash_rejects_everything() {
+        let token = AuthOptions {
+            token: None,
+            token_hash: Some("not-a-phc-string".into()),
+            public_metrics: false,
+            tokens: Vec::new(),
+        }
+        .auth_token()
+        .unwrap();
+
+        assert_eq!(token.invalid_hash_label(), Some(DEFAULT_TOKEN_LABEL));
+        assert!(!token.verify("not-a-phc-string"));
+    }
+
+    #[test]
+    fn weak_plaintext_tokens_are_reported() {
+        let options = |token: &str, hash: Option<&str>| AuthOptions {
+            token: Some(token.into()),
+            token_hash: hash.map(Into::into),
+            public_metrics: false,
+            tokens: Vec::new(),
+        };
+
+        assert!(options(PLACEHOLDER_TOKEN, None).weak_token_reason().is_some());
+        assert!(options("s3cret", None).weak_token_reason().is_some());
+        assert!(options("a-long-enough-random-token", None).weak_token_reason().is_none());
+        // The plaintext token is ignored once a hash is set
+        assert!(options("s3cret", Some("$argon2id$...")).weak_token_reason().is_none());
+        // No token at all means no auth, which is reported separately
+        assert!(options("", None).weak_token_reason().is_none());
+    }
+
+    #[test]
+    fn labeled_tokens_authenticate_by_label() {
+        let labeled = |label: &str, token: Option<&str>, hash: Option<String>| LabeledToken {
+            label: label.into(),
+            token: token.map(Into::into),
+            token_hash: hash,
+        };
+        let options = AuthOptions {
+            token: Some("single-token".into()),
+            token_hash: None,
+            public_metrics: false,
+            tokens: vec![
+                labeled("alice", Some("alice-token"), None),
+                labeled("ci", None, Some(hash_token("ci-token").unwrap())),
+            ],
+        };
+        assert!(options.check_tokens().is_ok());
+        let token = options.auth_token().unwrap();
+
+        assert_eq!(token.authenticate("single-token"), Some(DEFAULT_TOKEN_LABEL));
+        assert_eq!(token.authenticate("alice-token"), Some("alice"));
+        assert_eq!(token.authenticate("ci-token"), Some("ci"));
+        // Cached hash match
+        assert_eq!(token.authenticate("ci-token"), Some("ci"));
+        assert_eq!(token.authenticate("bob-token"), None);
+
+        // Only labeled tokens is fine too
+        let options = AuthOptions {
+            token: None,
+            ..options
+        };
+        let token = options.auth_token().unwrap();
+        assert_eq!(token.authenticate("single-token"), None);
+        assert_eq!(token.authenticate("alice-token"), Some("alice"));
+    }
+
+    #[test]
+    fn labeled_token_mistakes_are_reported() {
+        let options = |tokens: Vec<(&str, &str)>| AuthOptions {
+            tokens: tokens
+                .into_iter()
+                .map(|(label, token)| LabeledToken {
+                    label: label.into(),
+                    token: Some(token.into()),
+                    token_hash: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        assert!(options(vec![("", "a-long-enough-random-token")]).check_tokens().is_err());
+        assert!(options(vec![("a", "token-one-long-enough"), ("a", "token-two-long-enough")])
+            .check_tokens()
+            .is_err());
+        assert!(options(vec![(DEFAULT_TOKEN_LABEL, "a-long-enough-random-token")])
+            .check_tokens()
+            .is_err());
+        assert!(options(vec![("a", "")]).check_tokens().is_err());
+
+        let weak = options(vec![("a", "a-long-enough-random-token"), ("b", "short")]).weak_token_reason();
+        assert!(weak.is_some_and(|reason| reason.contains("\"b\"")));
+    }
 }
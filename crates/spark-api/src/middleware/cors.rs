@@ -0,0 +1,34 @@
+use axum::http::{header, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::warn;
+
+/// CORS for the API, allowing exactly `origins` (e.g. `https://dash.example.com`).
+/// Credentials are allowed so the session cookie works cross-origin, which rules out
+/// a `*` wildcard. Returns `None`, leaving the API same-origin only, when no valid
+/// origin is configured.
+pub fn cors_layer(origins: &[String]) -> Option<CorsLayer> {
+    let allowed: Vec<HeaderValue> = origins
+        .iter()
+        .filter_map(|origin| {
+            let origin = origin.trim().trim_end_matches('/');
+            if origin == "*" {
+                warn!("ignoring \"*\" in server.cors_allowed_origins: credentialed requests need explicit origins");
+                return None;
+            }
+            HeaderValue::from_str(origin)
+                .map_err(|e| warn!("ignoring CORS origin {origin:?}: {e}"))
+                .ok()
+        })
+        .collect();
+    if allowed.is_empty() {
+        return None;
+    }
+
+    Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(allowed))
+            .allow_credentials(true)
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]),
+    )
+}
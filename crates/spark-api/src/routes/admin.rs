@@ -0,0 +1,22 @@
+use axum::{extract::State, http::StatusCode, routing::post, Router};
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+/// Admin actions, behind the same token as the rest of the API.
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/admin/reload", post(reload_config))
+}
+
+/// Re-read the config file and apply its `[auth]` section. A file that fails to load
+/// leaves the current token in place.
+async fn reload_config(State(state): State<AppState>) -> Result<StatusCode, ApiError> {
+    let Some(reload) = state.reload_config else {
+        return Err(ApiError::unavailable("config reload is not available"));
+    };
+    reload().map_err(|e| {
+        tracing::warn!("config reload failed: {e}");
+        ApiError::internal(e)
+    })?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,12 @@
+use axum::{extract::State, routing::get, Json, Router};
+
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/alerts", get(get_alerts))
+}
+
+/// Alerts active as of the latest history sample; empty until the first one is taken.
+async fn get_alerts(State(state): State<AppState>) -> Json<Vec<spark_types::Alert>> {
+    Json(state.alerts.active().await)
+}
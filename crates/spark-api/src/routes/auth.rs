@@ -0,0 +1,31 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde::Deserialize;
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+/// Login is the one API route reachable without a token.
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/auth/login", post(handle_login))
+}
+
+#[derive(Deserialize)]
+struct LoginRequest {
+    token: String,
+}
+
+async fn handle_login(State(state): State<AppState>, Json(login): Json<LoginRequest>) -> Response {
+    let Some(expected) = state.auth_token.get() else {
+        return StatusCode::NO_CONTENT.into_response();
+    };
+    let Some(cookie) = state.cookies.session_cookie(&expected, &login.token) else {
+        return ApiError::unauthorized("invalid token").into_response();
+    };
+    (StatusCode::NO_CONTENT, [(header::SET_COOKIE, cookie)]).into_response()
+}
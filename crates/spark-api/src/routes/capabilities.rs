@@ -0,0 +1,33 @@
+use axum::{extract::State, routing::get, Json, Router};
+use spark_types::{Capabilities, TokenScope};
+
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/capabilities", get(get_capabilities))
+}
+
+/// Build the capability set for the current configuration.
+pub fn server_capabilities(state: &AppState) -> Capabilities {
+    Capabilities {
+        container_actions: spark_providers::docker::SUPPORTED_ACTIONS
+            .iter()
+            .map(|a| a.to_string())
+            .collect(),
+        container_size: state.providers.docker.include_size,
+        container_runtime: state.providers.docker.program().to_string(),
+        container_kill: true,
+        services: true,
+        service_actions: state.providers.services.allow_actions,
+        gpu_kill: state.providers.gpu.allow_kill,
+        updates: true,
+        alerting: state.alerts.options().any_enabled(),
+        // Tokens aren't scoped: every authenticated token gets full read-write access.
+        token_scope: TokenScope::ReadWrite,
+        ..Capabilities::default()
+    }
+}
+
+async fn get_capabilities(State(state): State<AppState>) -> Json<Capabilities> {
+    Json(server_capabilities(&state))
+}
@@ -1,32 +1,104 @@
 use axum::{
-    extract::State,
-    http::StatusCode,
+    extract::{Path, Query, State},
+    response::sse::{Event, KeepAlive, Sse},
     routing::{get, post},
     Json, Router,
 };
+use futures_util::{stream, Stream, StreamExt};
+use serde::Deserialize;
+use spark_providers::logs;
 
+use crate::error::ApiError;
 use crate::middleware::auth::AppState;
 
+#[derive(Deserialize)]
+struct ContainersQuery {
+    /// Include CPU/memory/network usage. Defaults to true; pass false to get the
+    /// listing without waiting on a stats sample, then fetch `/containers/stats`.
+    with_stats: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct LogsQuery {
+    /// Number of lines from the end of the log.
+    tail: Option<usize>,
+}
+
 pub fn routes(_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/api/v1/containers", get(get_containers))
+        .route("/api/v1/containers/stats", get(get_container_stats))
         .route("/api/v1/containers/action", post(post_container_action))
+        .route("/api/v1/containers/:id/logs", get(get_container_logs))
+        .route("/api/v1/containers/:id/logs/stream", get(container_log_events))
 }
 
 async fn get_containers(
-    State(_state): State<AppState>,
-) -> Result<Json<Vec<spark_types::ContainerSummary>>, (StatusCode, String)> {
-    match spark_providers::docker::collect().await {
+    State(state): State<AppState>,
+    Query(query): Query<ContainersQuery>,
+) -> Result<Json<Vec<spark_types::ContainerSummary>>, ApiError> {
+    let withStats = query.with_stats.unwrap_or(true);
+    match spark_providers::docker::collect(&state.providers.docker, withStats).await {
         Ok(containers) => Ok(Json(containers)),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+        Err(e) => Err(ApiError::unavailable(e)),
+    }
+}
+
+async fn get_container_stats(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<spark_types::ContainerStats>>, ApiError> {
+    match spark_providers::docker::collect_stats(&state.providers.docker).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => Err(ApiError::unavailable(e)),
     }
 }
 
 async fn post_container_action(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     Json(action): Json<spark_types::ContainerAction>,
 ) -> Json<spark_types::ContainerActionResult> {
-    let result =
-        spark_providers::docker::execute_action(&action.container_id, &action.action).await;
+    let result = spark_providers::docker::execute_action(
+        &state.providers.docker,
+        &action.container_id,
+        &action.action,
+    )
+    .await;
     Json(result)
 }
+
+async fn get_container_logs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogsQuery>,
+) -> Result<String, ApiError> {
+    if !logs::is_valid_container_ref(&id) {
+        return Err(ApiError::bad_request(format!("invalid container: {id}")));
+    }
+    let tail = query.tail.unwrap_or(logs::DEFAULT_TAIL);
+    logs::tail(&state.providers.docker, &id, tail)
+        .await
+        .map_err(ApiError::unavailable)
+}
+
+/// `log` events, one per line: the last `tail` lines, then new output as it's written.
+/// A final `end` event marks the container exiting, so clients know not to reconnect.
+async fn container_log_events(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    if !logs::is_valid_container_ref(&id) {
+        return Err(ApiError::bad_request(format!("invalid container: {id}")));
+    }
+    let tail = query.tail.unwrap_or(logs::DEFAULT_TAIL);
+    let lines = logs::follow(&state.providers.docker, &id, tail)
+        .await
+        .map_err(ApiError::unavailable)?;
+
+    let events = stream::unfold(lines, |mut lines| async move {
+        let line = lines.recv().await?;
+        Some((Ok(Event::default().event("log").data(line)), lines))
+    })
+    .chain(stream::once(async { Ok(Event::default().event("end").data("")) }));
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
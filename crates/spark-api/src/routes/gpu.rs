@@ -0,0 +1,18 @@
+use axum::{
+    extract::{Path, State},
+    routing::post,
+    Json, Router,
+};
+
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/gpu/processes/:pid/kill", post(kill_gpu_process))
+}
+
+async fn kill_gpu_process(
+    State(state): State<AppState>,
+    Path(pid): Path<u32>,
+) -> Json<spark_types::ContainerActionResult> {
+    Json(spark_providers::gpu::kill_process(&state.providers.gpu, pid).await)
+}
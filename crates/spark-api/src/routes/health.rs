@@ -0,0 +1,27 @@
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde_json::{json, Value};
+
+use crate::middleware::auth::AppState;
+
+/// Probes for load balancers and orchestrators. These skip auth and never touch a provider.
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+}
+
+async fn healthz() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+/// Ready once the history sampler has completed its first metrics collection.
+async fn readyz(State(state): State<AppState>) -> (StatusCode, Json<Value>) {
+    if state.history.read().await.has_samples() {
+        (StatusCode::OK, Json(json!({ "status": "ready" })))
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "starting" })),
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use axum::{
+    extract::{Path, State},
+    routing::{delete, get, post},
+    Json, Router,
+};
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/images", get(get_images))
+        .route("/api/v1/images/prune", post(post_prune_images))
+        .route("/api/v1/images/:id", delete(delete_image))
+}
+
+async fn get_images(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<spark_types::ImageSummary>>, ApiError> {
+    match spark_providers::docker::list_images(&state.providers.docker).await {
+        Ok(images) => Ok(Json(images)),
+        Err(e) => Err(ApiError::unavailable(e)),
+    }
+}
+
+async fn delete_image(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Json<spark_types::ContainerActionResult> {
+    Json(spark_providers::docker::remove_image(&state.providers.docker, &id).await)
+}
+
+async fn post_prune_images(
+    State(state): State<AppState>,
+) -> Json<spark_types::ContainerActionResult> {
+    Json(spark_providers::docker::prune_images(&state.providers.docker).await)
+}
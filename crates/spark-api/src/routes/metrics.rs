@@ -0,0 +1,221 @@
+use std::fmt::Write;
+
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use spark_types::{InventorySummary, SystemMetrics};
+
+use crate::middleware::auth::AppState;
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/metrics", get(get_metrics))
+}
+
+async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let (metrics, summary) = tokio::join!(
+        spark_providers::collect_system_metrics(&state.providers),
+        spark_providers::collect_inventory_summary(&state.providers),
+    );
+    (
+        [(header::CONTENT_TYPE, CONTENT_TYPE)],
+        render(&metrics, &summary),
+    )
+}
+
+/// Prometheus text exposition of the current metrics.
+fn render(metrics: &SystemMetrics, summary: &InventorySummary) -> String {
+    let mut out = String::new();
+
+    let gpuSamples = |value: &dyn Fn(&spark_types::GpuMetrics) -> f64| -> Vec<(String, f64)> {
+        metrics
+            .gpus
+            .iter()
+            .map(|g| {
+                (
+                    format!("gpu=\"{}\",name=\"{}\"", g.index, escape(&g.name)),
+                    value(g),
+                )
+            })
+            .collect()
+    };
+    family(
+        &mut out,
+        "spark_gpu_utilization_percent",
+        "GPU utilization.",
+        &gpuSamples(&|g| g.utilization_pct as f64),
+    );
+    family(
+        &mut out,
+        "spark_gpu_temperature_celsius",
+        "GPU temperature.",
+        &gpuSamples(&|g| g.temperature_c as f64),
+    );
+    family(
+        &mut out,
+        "spark_gpu_power_draw_watts",
+        "GPU power draw.",
+        &gpuSamples(&|g| g.power_draw_w as f64),
+    );
+    family(
+        &mut out,
+        "spark_gpu_memory_used_bytes",
+        "GPU memory in use.",
+        &gpuSamples(&|g| (g.memory_used_mib * 1024 * 1024) as f64),
+    );
+    family(
+        &mut out,
+        "spark_gpu_memory_total_bytes",
+        "GPU memory size (system memory on unified-memory GPUs).",
+        &gpuSamples(&|g| (g.memory_total_mib * 1024 * 1024) as f64),
+    );
+
+    for (name, help, value) in [
+        (
+            "spark_cpu_load1",
+            "1-minute load average.",
+            metrics.cpu.load_1m,
+        ),
+        (
+            "spark_cpu_load5",
+            "5-minute load average.",
+            metrics.cpu.load_5m,
+        ),
+        (
+            "spark_cpu_load15",
+            "15-minute load average.",
+            metrics.cpu.load_15m,
+        ),
+    ] {
+        family(&mut out, name, help, &[(String::new(), value as f64)]);
+    }
+
+    family(
+        &mut out,
+        "spark_memory_total_bytes",
+        "System memory size.",
+        &[(String::new(), metrics.memory.total_bytes as f64)],
+    );
+    family(
+        &mut out,
+        "spark_memory_used_bytes",
+        "System memory counted as used, per memory.count_cache_as_used.",
+        &[(String::new(), metrics.memory.used_bytes as f64)],
+    );
+    family(
+        &mut out,
+        "spark_memory_available_bytes",
+        "System memory available for new allocations.",
+        &[(String::new(), metrics.memory.available_bytes as f64)],
+    );
+
+    let diskSamples = |value: &dyn Fn(&spark_types::DiskMetrics) -> f64| -> Vec<(String, f64)> {
+        metrics
+            .disks
+            .iter()
+            .map(|d| (format!("mount=\"{}\"", escape(&d.mount_point)), value(d)))
+            .collect()
+    };
+    family(
+        &mut out,
+        "spark_disk_total_bytes",
+        "Filesystem size.",
+        &diskSamples(&|d| d.total_bytes as f64),
+    );
+    family(
+        &mut out,
+        "spark_disk_used_bytes",
+        "Filesystem space in use.",
+        &diskSamples(&|d| d.used_bytes as f64),
+    );
+
+    family(
+        &mut out,
+        "spark_uptime_seconds",
+        "System uptime.",
+        &[(String::new(), metrics.uptime.seconds as f64)],
+    );
+
+    family(
+        &mut out,
+        "spark_containers_total",
+        "Containers known to the container engine.",
+        &[(String::new(), summary.containers_total as f64)],
+    );
+    family(
+        &mut out,
+        "spark_containers",
+        "Containers running, and containers unhealthy, restarting or dead.",
+        &[
+            (
+                "state=\"running\"".to_string(),
+                summary.containers_running as f64,
+            ),
+            (
+                "state=\"unhealthy\"".to_string(),
+                summary.containers_unhealthy as f64,
+            ),
+        ],
+    );
+
+    out
+}
+
+/// Write one gauge family: HELP/TYPE lines, then a sample per label set.
+fn family(out: &mut String, name: &str, help: &str, samples: &[(String, f64)]) {
+    if samples.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} gauge");
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
+
+/// Escape a label value per the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn container_total_is_its_own_family() {
+        let summary = InventorySummary {
+            containers_running: 3,
+            containers_total: 5,
+            containers_unhealthy: 1,
+            models_total: 0,
+        };
+        let out = render(&SystemMetrics::default(), &summary);
+
+        assert!(out.contains("# TYPE spark_containers_total gauge\nspark_containers_total 5\n"));
+        assert!(out.contains("spark_containers{state=\"running\"} 3\n"));
+        assert!(out.contains("spark_containers{state=\"unhealthy\"} 1\n"));
+        assert!(!out.contains("state=\"all\""));
+        assert!(out.contains("spark_memory_total_bytes "));
+        // Every sample line belongs to a family declared above it
+        for line in out.lines().filter(|l| !l.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(out.contains(&format!("# TYPE {name} gauge")), "{line}");
+        }
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape(r#"say "hi""#), r#"say \"hi\""#);
+        assert_eq!(escape(r"C:\data"), r"C:\\data");
+        assert_eq!(escape("two\nlines"), r"two\nlines");
+        assert_eq!(escape("\\\""), r#"\\\""#);
+    }
+}
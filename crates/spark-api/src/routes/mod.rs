@@ -1,14 +1,52 @@
+pub mod admin;
+pub mod alerts;
+pub mod auth;
+pub mod capabilities;
 pub mod containers;
+pub mod gpu;
+pub mod health;
+pub mod images;
+pub mod metrics;
 pub mod models;
+pub mod services;
+pub mod storage;
+pub mod summary;
+pub mod updates;
 pub mod system;
+pub mod version;
+pub mod volumes;
 
-use axum::Router;
+use axum::{middleware::from_fn_with_state, Router};
 
-use crate::middleware::auth::AppState;
+use crate::middleware::auth::{require_api_auth, AppState};
 
 pub fn api_routes(state: AppState) -> Router<AppState> {
+    // Scrapers often can't log in, so /metrics can be configured to skip auth
+    let (protectedMetrics, publicMetrics) = if state.public_metrics {
+        (Router::new(), metrics::routes(state.clone()))
+    } else {
+        (metrics::routes(state.clone()), Router::new())
+    };
+
     Router::new()
         .merge(system::routes(state.clone()))
+        .merge(alerts::routes(state.clone()))
+        .merge(capabilities::routes(state.clone()))
         .merge(containers::routes(state.clone()))
-        .merge(models::routes(state))
+        .merge(gpu::routes(state.clone()))
+        .merge(images::routes(state.clone()))
+        .merge(volumes::routes(state.clone()))
+        .merge(models::routes(state.clone()))
+        .merge(services::routes(state.clone()))
+        .merge(storage::routes(state.clone()))
+        .merge(summary::routes(state.clone()))
+        .merge(updates::routes(state.clone()))
+        .merge(admin::routes(state.clone()))
+        .merge(protectedMetrics)
+        // route_layer only covers the routes above, so login stays reachable
+        .route_layer(from_fn_with_state(state.clone(), require_api_auth))
+        .merge(auth::routes(state.clone()))
+        .merge(health::routes(state.clone()))
+        .merge(version::routes(state))
+        .merge(publicMetrics)
 }
@@ -1,14 +1,49 @@
-use axum::{extract::State, routing::get, Json, Router};
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
 
+use crate::error::ApiError;
 use crate::middleware::auth::AppState;
 
+#[derive(Deserialize)]
+struct DeleteModelQuery {
+    /// Path of the model file, as listed by `GET /api/v1/models`.
+    path: String,
+}
+
 pub fn routes(_state: AppState) -> Router<AppState> {
-    Router::new().route("/api/v1/models", get(get_models))
+    Router::new().route("/api/v1/models", get(get_models).delete(delete_model))
+}
+
+/// The model list, tagged with an `ETag` from its fingerprint. A request whose
+/// `If-None-Match` already holds that tag gets `304 Not Modified` and no body.
+async fn get_models(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let models = spark_providers::models::collect(&state.providers.models).await;
+    let etag = format!("\"{}\"", spark_providers::models::fingerprint(&models));
+    let unchanged = headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag);
+    if unchanged {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
+    }
+    ([(header::ETAG, etag)], Json(models)).into_response()
 }
 
-async fn get_models(
-    State(_state): State<AppState>,
-) -> Json<Vec<spark_types::ModelEntry>> {
-    let models = spark_providers::models::collect().await;
-    Json(models)
+async fn delete_model(
+    State(state): State<AppState>,
+    Query(query): Query<DeleteModelQuery>,
+) -> Result<StatusCode, ApiError> {
+    spark_providers::models::delete(&state.providers.models, &query.path)
+        .await
+        .map(|()| StatusCode::NO_CONTENT)
+        .map_err(ApiError::bad_request)
 }
@@ -0,0 +1,33 @@
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/services", get(get_services))
+        .route("/api/v1/services/action", post(post_service_action))
+}
+
+async fn get_services(
+    State(_state): State<AppState>,
+) -> Result<Json<Vec<spark_types::ServiceSummary>>, ApiError> {
+    match spark_providers::services::list().await {
+        Ok(services) => Ok(Json(services)),
+        Err(e) => Err(ApiError::unavailable(e)),
+    }
+}
+
+async fn post_service_action(
+    State(state): State<AppState>,
+    Json(action): Json<spark_types::ServiceAction>,
+) -> Json<spark_types::ContainerActionResult> {
+    Json(
+        spark_providers::services::run_action(&state.providers.services, &action.unit, &action.action)
+            .await,
+    )
+}
@@ -0,0 +1,38 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use serde::Deserialize;
+use spark_providers::disk;
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+#[derive(Deserialize)]
+struct UsageQuery {
+    /// A mount from `disk.mounts` or a directory from `disk.usage_paths`.
+    path: String,
+    /// How many of the largest directories to return.
+    top: Option<usize>,
+}
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/storage/usage", get(get_usage))
+}
+
+async fn get_usage(
+    State(state): State<AppState>,
+    Query(query): Query<UsageQuery>,
+) -> Result<Json<Vec<spark_types::DirUsage>>, ApiError> {
+    let options = &state.providers.disk;
+    let top = query.top.unwrap_or(disk::DEFAULT_USAGE_TOP);
+    let root = disk::usage_root(options, &query.path)
+        .await
+        .map_err(ApiError::bad_request)?;
+    // Past validation, a failure is du timing out or not running at all
+    disk::dir_usage(options, &root, top)
+        .await
+        .map(Json)
+        .map_err(ApiError::unavailable)
+}
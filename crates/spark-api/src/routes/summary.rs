@@ -0,0 +1,14 @@
+use axum::{extract::State, routing::get, Json, Router};
+
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/summary", get(get_summary))
+}
+
+async fn get_summary(
+    State(state): State<AppState>,
+) -> Json<spark_types::InventorySummary> {
+    let summary = spark_providers::collect_inventory_summary(&state.providers).await;
+    Json(summary)
+}
@@ -1,35 +1,169 @@
 use axum::{
-    extract::State,
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
     routing::get,
     Json, Router,
 };
+use futures_util::{stream, Stream};
+use serde::Deserialize;
+use tracing::warn;
 
+use crate::error::ApiError;
+use crate::history;
 use crate::middleware::auth::AppState;
 
+const DEFAULT_HISTORY_RANGE_SECS: u64 = 3600;
+
+#[derive(Deserialize)]
+struct HistoryQuery {
+    /// How far back to return samples, in seconds.
+    range: Option<u64>,
+    /// Unix timestamp (seconds) of the oldest sample wanted. Takes precedence over `range`.
+    since: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct FilesystemsQuery {
+    /// Include pseudo filesystems such as proc, tmpfs and overlay. Defaults to false.
+    all: Option<bool>,
+}
+
 pub fn routes(_state: AppState) -> Router<AppState> {
     Router::new()
         .route("/api/v1/system", get(get_system_metrics))
         .route("/api/v1/system/gpu", get(get_gpu_metrics))
         .route("/api/v1/system/memory", get(get_memory_metrics))
+        .route("/api/v1/system/cpu", get(get_cpu_metrics))
+        .route("/api/v1/system/disk", get(get_disk_metrics))
+        .route("/api/v1/system/filesystems", get(get_filesystems))
+        .route("/api/v1/system/net", get(get_net_metrics))
+        .route("/api/v1/system/uptime", get(get_uptime_metrics))
+        .route("/api/v1/system/history", get(get_history))
+        .route("/api/v1/system/stream", get(stream_system_metrics))
+        .route("/api/v1/system/events", get(system_metrics_events))
 }
 
 async fn get_system_metrics(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Json<spark_types::SystemMetrics> {
-    let metrics = spark_providers::collect_system_metrics().await;
+    let metrics = spark_providers::collect_system_metrics(&state.providers).await;
     Json(metrics)
 }
 
 async fn get_gpu_metrics(
-    State(_state): State<AppState>,
-) -> Json<spark_types::GpuMetrics> {
-    let metrics = spark_providers::gpu::collect().await;
+    State(state): State<AppState>,
+) -> Json<Vec<spark_types::GpuMetrics>> {
+    let metrics = spark_providers::gpu::collect(&state.providers.gpu).await;
     Json(metrics)
 }
 
 async fn get_memory_metrics(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
 ) -> Json<spark_types::MemoryMetrics> {
-    let metrics = spark_providers::memory::collect().await;
+    let metrics = spark_providers::memory::collect(&state.providers.memory).await;
     Json(metrics)
 }
+
+async fn get_cpu_metrics(
+    State(_state): State<AppState>,
+) -> Json<spark_types::CpuMetrics> {
+    let metrics = spark_providers::cpu::collect().await;
+    Json(metrics)
+}
+
+/// One entry per configured mount, like `SystemMetrics.disks`.
+async fn get_disk_metrics(
+    State(state): State<AppState>,
+) -> Json<Vec<spark_types::DiskMetrics>> {
+    let metrics = spark_providers::disk::collect(&state.providers.disk).await;
+    Json(metrics)
+}
+
+/// Every mounted filesystem, not just the configured mounts.
+async fn get_filesystems(
+    State(_state): State<AppState>,
+    Query(query): Query<FilesystemsQuery>,
+) -> Result<Json<Vec<spark_types::DiskMetrics>>, ApiError> {
+    spark_providers::disk::list_filesystems(query.all.unwrap_or(false))
+        .await
+        .map(Json)
+        .map_err(ApiError::unavailable)
+}
+
+async fn get_net_metrics(
+    State(state): State<AppState>,
+) -> Json<Vec<spark_types::NetInterfaceMetrics>> {
+    let metrics = spark_providers::net::collect(&state.providers.net).await;
+    Json(metrics)
+}
+
+async fn get_uptime_metrics(
+    State(_state): State<AppState>,
+) -> Json<spark_types::UptimeMetrics> {
+    let metrics = spark_providers::uptime::collect().await;
+    Json(metrics)
+}
+
+async fn get_history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> Json<spark_types::HistoryResponse> {
+    if let Some(since) = query.since {
+        return Json(history::query_since(&state.history, since).await);
+    }
+    Json(history::query(&state.history, query.range.unwrap_or(DEFAULT_HISTORY_RANGE_SECS)).await)
+}
+
+async fn stream_system_metrics(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(move |socket| push_system_metrics(socket, state))
+}
+
+/// Send a `SystemMetrics` frame every `stream_interval` until the client goes away.
+async fn push_system_metrics(mut socket: WebSocket, state: AppState) {
+    let mut ticker = tokio::time::interval(state.stream_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let metrics = spark_providers::collect_system_metrics(&state.providers).await;
+                let frame = match serde_json::to_string(&metrics) {
+                    Ok(frame) => frame,
+                    Err(e) => {
+                        warn!("failed to serialize metrics frame: {e}");
+                        continue;
+                    }
+                };
+                if socket.send(Message::Text(frame)).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                // Clients don't send anything meaningful; only watch for the close
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// `metrics` events carrying `SystemMetrics` every `stream_interval`. Axum drops the
+/// stream when the client disconnects, which stops collection.
+async fn system_metrics_events(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let mut ticker = tokio::time::interval(state.stream_interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let events = stream::unfold((ticker, state), |(mut ticker, state)| async move {
+        ticker.tick().await;
+        let metrics = spark_providers::collect_system_metrics(&state.providers).await;
+        let event = Event::default().event("metrics").json_data(&metrics);
+        Some((event, (ticker, state)))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
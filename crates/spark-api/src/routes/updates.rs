@@ -0,0 +1,17 @@
+use axum::{extract::State, routing::get, Json, Router};
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/updates", get(get_updates))
+}
+
+async fn get_updates(
+    State(_state): State<AppState>,
+) -> Result<Json<spark_types::UpdateStatus>, ApiError> {
+    match spark_providers::updates::collect().await {
+        Ok(status) => Ok(Json(status)),
+        Err(e) => Err(ApiError::unavailable(e)),
+    }
+}
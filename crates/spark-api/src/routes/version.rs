@@ -0,0 +1,18 @@
+use axum::{routing::get, Json, Router};
+use spark_types::VersionInfo;
+
+use crate::middleware::auth::AppState;
+
+/// Which build is running. Public like the health probes, so a deploy can be checked
+/// without a token.
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new().route("/api/v1/version", get(get_version))
+}
+
+async fn get_version() -> Json<VersionInfo> {
+    Json(VersionInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: env!("SPARK_GIT_SHA").to_string(),
+        built_at: env!("SPARK_BUILT_AT").to_string(),
+    })
+}
@@ -0,0 +1,29 @@
+use axum::{
+    extract::State,
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::error::ApiError;
+use crate::middleware::auth::AppState;
+
+pub fn routes(_state: AppState) -> Router<AppState> {
+    Router::new()
+        .route("/api/v1/volumes", get(get_volumes))
+        .route("/api/v1/volumes/prune", post(post_prune_volumes))
+}
+
+async fn get_volumes(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<spark_types::VolumeSummary>>, ApiError> {
+    match spark_providers::docker::list_volumes(&state.providers.docker).await {
+        Ok(volumes) => Ok(Json(volumes)),
+        Err(e) => Err(ApiError::unavailable(e)),
+    }
+}
+
+async fn post_prune_volumes(
+    State(state): State<AppState>,
+) -> Json<spark_types::ContainerActionResult> {
+    Json(spark_providers::docker::prune_volumes(&state.providers.docker).await)
+}
@@ -0,0 +1,213 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use spark_api::alerts::{AlertMonitor, AlertOptions};
+use spark_api::history::{HistoryOptions, HistoryStore};
+use spark_api::middleware::auth::{AppState, AuthOptions, ConfigReload, CookieOptions, SharedAuthToken};
+use spark_providers::ProviderOptions;
+use tokio::sync::RwLock;
+use tower::ServiceExt;
+
+const TOKEN: &str = "integration-test-token";
+
+fn router() -> Router {
+    router_with_origins(Vec::new())
+}
+
+fn router_with_origins(cors_allowed_origins: Vec<String>) -> Router {
+    spark_api::api_router(state(cors_allowed_origins))
+}
+
+fn state(cors_allowed_origins: Vec<String>) -> AppState {
+    let auth = AuthOptions {
+        token: Some(TOKEN.into()),
+        ..AuthOptions::default()
+    };
+    AppState {
+        config_path: String::new(),
+        providers: ProviderOptions::default(),
+        history: Arc::new(RwLock::new(HistoryStore::new(&HistoryOptions::default()))),
+        alerts: Arc::new(AlertMonitor::new(AlertOptions::default())),
+        stream_interval: Duration::from_secs(2),
+        public_metrics: false,
+        auth_token: SharedAuthToken::new(auth.auth_token()),
+        reload_config: None,
+        cookies: CookieOptions::default(),
+        cors_allowed_origins,
+    }
+}
+
+async fn get(path: &str, token: Option<&str>) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::get(path);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = router()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap_or_default())
+}
+
+#[tokio::test]
+async fn cpu_endpoint() {
+    let (status, body) = get("/api/v1/system/cpu", Some(TOKEN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["load_1m"].is_number());
+}
+
+#[tokio::test]
+async fn disk_endpoint() {
+    let (status, body) = get("/api/v1/system/disk", Some(TOKEN)).await;
+    assert_eq!(status, StatusCode::OK);
+    let disks = body.as_array().expect("disk endpoint returns a list");
+    assert_eq!(disks[0]["mount_point"], "/");
+}
+
+#[tokio::test]
+async fn uptime_endpoint() {
+    let (status, body) = get("/api/v1/system/uptime", Some(TOKEN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["seconds"].is_u64());
+}
+
+#[tokio::test]
+async fn history_since_and_range() {
+    for path in ["/api/v1/system/history", "/api/v1/system/history?range=60", "/api/v1/system/history?since=0"] {
+        let (status, body) = get(path, Some(TOKEN)).await;
+        assert_eq!(status, StatusCode::OK, "{path}");
+        assert_eq!(body["samples"], serde_json::json!([]), "{path}");
+    }
+}
+
+#[tokio::test]
+async fn alerts_empty_before_first_sample() {
+    let (status, body) = get("/api/v1/alerts", Some(TOKEN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!([]));
+}
+
+#[tokio::test]
+async fn endpoints_require_auth() {
+    for path in ["/api/v1/system/cpu", "/api/v1/system/disk", "/api/v1/system/uptime"] {
+        let (status, body) = get(path, None).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{path}");
+        assert_eq!(body["error"], "authentication required", "{path}");
+        let (status, _) = get(path, Some("wrong-token")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "{path}");
+    }
+}
+
+#[tokio::test]
+async fn health_probes_skip_auth() {
+    let (status, body) = get("/healthz", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "ok");
+
+    // No sampler runs in tests, so the store never fills
+    let (status, _) = get("/readyz", None).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn version_skips_auth() {
+    let (status, body) = get("/api/v1/version", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!body["git_sha"].as_str().unwrap_or_default().is_empty());
+}
+
+#[tokio::test]
+async fn cors_allows_only_configured_origins() {
+    let preflight = |origin: &'static str| {
+        Request::options("/api/v1/system/cpu")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "authorization")
+            .body(Body::empty())
+            .unwrap()
+    };
+    let router = router_with_origins(vec!["https://dash.example.com".into()]);
+
+    let response = router.clone().oneshot(preflight("https://dash.example.com")).await.unwrap();
+    assert_eq!(
+        response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://dash.example.com"
+    );
+    assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+
+    let response = router.oneshot(preflight("https://evil.example.com")).await.unwrap();
+    assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+    // Without configured origins no CORS headers are sent at all
+    let response = router_with_origins(Vec::new())
+        .oneshot(preflight("https://dash.example.com"))
+        .await
+        .unwrap();
+    assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+}
+
+#[tokio::test]
+async fn reload_replaces_token_and_drops_old_sessions() {
+    const NEW_TOKEN: &str = "rotated-integration-token";
+    let mut state = state(Vec::new());
+    let shared = state.auth_token.clone();
+    let reload: ConfigReload = std::sync::Arc::new(move || {
+        let auth = AuthOptions {
+            token: Some(NEW_TOKEN.into()),
+            ..AuthOptions::default()
+        };
+        shared.replace(auth.auth_token());
+        Ok(())
+    });
+    state.reload_config = Some(reload);
+    let router = spark_api::api_router(state);
+    let request = |method: &str, path: &str, token: &str| {
+        Request::builder()
+            .method(method)
+            .uri(path)
+            .header(header::COOKIE, format!("session_token={token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let response = router.clone().oneshot(request("POST", "/api/v1/admin/reload", TOKEN)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = router.clone().oneshot(request("GET", "/api/v1/system/uptime", TOKEN)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = router.oneshot(request("GET", "/api/v1/system/uptime", NEW_TOKEN)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn unchanged_models_are_not_modified() {
+    let mut state = state(Vec::new());
+    state.providers.models.dirs = Vec::new();
+    let router = spark_api::api_router(state);
+    let request = |if_none_match: Option<&str>| {
+        let mut request = Request::get("/api/v1/models")
+            .header(header::AUTHORIZATION, format!("Bearer {TOKEN}"));
+        if let Some(tag) = if_none_match {
+            request = request.header(header::IF_NONE_MATCH, tag);
+        }
+        request.body(Body::empty()).unwrap()
+    };
+
+    let response = router.clone().oneshot(request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let etag = response.headers()[header::ETAG].to_str().unwrap().to_string();
+
+    let response = router.clone().oneshot(request(Some(&etag))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], etag.as_str());
+    let response = router.oneshot(request(Some("\"stale\""))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
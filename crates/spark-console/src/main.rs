@@ -3,16 +3,97 @@
 #[cfg(feature = "ssr")]
 mod config {
     use serde::Deserialize;
+    use spark_api::alerts::AlertOptions;
+    use spark_api::history::HistoryOptions;
+    use spark_api::middleware::auth::AuthOptions;
+    use spark_providers::disk::DiskOptions;
+    use spark_providers::docker::DockerOptions;
+    use spark_providers::gpu::GpuOptions;
+    use spark_providers::models::ModelOptions;
+    use spark_providers::memory::MemoryOptions;
+    use spark_providers::net::NetOptions;
+    use spark_providers::services::ServiceOptions;
+    use spark_providers::{MetricsCache, ProviderOptions, DEFAULT_METRICS_CACHE_TTL};
+    use spark_ui::ui_config::UiOptions;
 
     #[derive(Deserialize, Clone, Debug)]
     pub struct Config {
         pub server: ServerConfig,
+        #[serde(default)]
+        pub docker: DockerOptions,
+        #[serde(default)]
+        pub gpu: GpuOptions,
+        #[serde(default)]
+        pub disk: DiskOptions,
+        #[serde(default)]
+        pub models: ModelOptions,
+        #[serde(default)]
+        pub services: ServiceOptions,
+        #[serde(default)]
+        pub net: NetOptions,
+        #[serde(default)]
+        pub memory: MemoryOptions,
+        #[serde(default)]
+        pub history: HistoryOptions,
+        #[serde(default)]
+        pub alerts: AlertOptions,
+        #[serde(default)]
+        pub auth: AuthOptions,
+        #[serde(default)]
+        pub ui: UiOptions,
+    }
+
+    impl Config {
+        pub fn provider_options(&self) -> ProviderOptions {
+            ProviderOptions {
+                docker: self.docker.clone(),
+                gpu: self.gpu.clone(),
+                disk: self.disk.clone(),
+                models: self.models.clone(),
+                services: self.services.clone(),
+                net: self.net.clone(),
+                memory: self.memory.clone(),
+                metrics_cache: MetricsCache::new(std::time::Duration::from_millis(
+                    self.server.metrics_cache_ms,
+                )),
+            }
+        }
     }
 
     #[derive(Deserialize, Clone, Debug)]
     pub struct ServerConfig {
         pub bind: String,
         pub port: u16,
+        /// Seconds between frames on the `/api/v1/system/stream` WebSocket.
+        #[serde(default = "default_stream_interval_secs")]
+        pub stream_interval_secs: u64,
+        /// Milliseconds a metrics collection is reused for other requests; 0 disables it.
+        #[serde(default = "default_metrics_cache_ms")]
+        pub metrics_cache_ms: u64,
+        /// Mark the session cookie `Secure`; turn off when serving over plain HTTP.
+        #[serde(default = "default_secure_cookies")]
+        pub secure_cookies: bool,
+        /// Origins other frontends may call the API from, e.g. "https://dash.example.com".
+        #[serde(default)]
+        pub cors_allowed_origins: Vec<String>,
+        /// PEM certificate chain; with `tls_key`, the console serves HTTPS instead of HTTP.
+        #[serde(default)]
+        pub tls_cert: Option<String>,
+        /// PEM private key for `tls_cert`.
+        #[serde(default)]
+        pub tls_key: Option<String>,
+    }
+
+    fn default_stream_interval_secs() -> u64 {
+        2
+    }
+
+    fn default_metrics_cache_ms() -> u64 {
+        DEFAULT_METRICS_CACHE_TTL.as_millis() as u64
+    }
+
+    fn default_secure_cookies() -> bool {
+        true
     }
 
     impl Default for Config {
@@ -21,25 +102,35 @@ mod config {
                 server: ServerConfig {
                     bind: "0.0.0.0".into(),
                     port: 3000,
+                    stream_interval_secs: default_stream_interval_secs(),
+                    metrics_cache_ms: default_metrics_cache_ms(),
+                    secure_cookies: default_secure_cookies(),
+                    cors_allowed_origins: Vec::new(),
+                    tls_cert: None,
+                    tls_key: None,
                 },
+                docker: DockerOptions::default(),
+                gpu: GpuOptions::default(),
+                disk: DiskOptions::default(),
+                models: ModelOptions::default(),
+                services: ServiceOptions::default(),
+                net: NetOptions::default(),
+                memory: MemoryOptions::default(),
+                history: HistoryOptions::default(),
+                alerts: AlertOptions::default(),
+                auth: AuthOptions::default(),
+                ui: UiOptions::default(),
             }
         }
     }
 
-    pub fn load(path: &str) -> Config {
-        match std::fs::read_to_string(path) {
-            Ok(contents) => match toml::from_str(&contents) {
-                Ok(config) => config,
-                Err(e) => {
-                    tracing::warn!("failed to parse config {path}: {e}, using defaults");
-                    Config::default()
-                }
-            },
-            Err(e) => {
-                tracing::warn!("failed to read config {path}: {e}, using defaults");
-                Config::default()
-            }
-        }
+    /// Read and parse the config file. A file that is missing, unreadable, malformed or
+    /// lacks a required section is an error, so a typo can't quietly drop settings such
+    /// as the auth token.
+    pub fn load(path: &str) -> Result<Config, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config {path}: {e}"))?;
+        toml::from_str(&contents).map_err(|e| format!("failed to parse config {path}: {e}"))
     }
 }
 
@@ -49,20 +140,37 @@ async fn main() {
     use axum::Router;
     use leptos::prelude::*;
     use leptos_axum::{generate_route_list, LeptosRoutes};
-    use spark_api::middleware::auth::AppState;
+    use spark_api::alerts::AlertMonitor;
+    use spark_api::history::{spawn_sampler, HistoryStore};
+    use axum::middleware::from_fn_with_state;
+    use spark_api::middleware::auth::{
+        require_page_auth, AppState, AuthToken, ConfigReload, CookieOptions, SharedAuthToken,
+    };
+    use std::sync::Arc;
+    use tokio::sync::RwLock;
     use spark_ui::{shell, App};
-    use tower_http::trace::TraceLayer;
     use tracing_subscriber::{fmt, EnvFilter};
 
-    // Initialize tracing
-    fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
-        .init();
+    let args: Vec<String> = std::env::args().collect();
+
+    // Initialize tracing, as one JSON object per line for log pipelines when asked
+    let logFormat = match args.iter().position(|a| a == "--log-format") {
+        Some(idx) => args.get(idx + 1).cloned().unwrap_or_default(),
+        None => std::env::var("SPARK_LOG_FORMAT").unwrap_or_else(|_| "pretty".into()),
+    };
+    let subscriber = fmt().with_env_filter(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    );
+    match logFormat.as_str() {
+        "pretty" => subscriber.init(),
+        "json" => subscriber.json().init(),
+        other => {
+            eprintln!("unknown log format {other:?}; use \"pretty\" or \"json\"");
+            std::process::exit(1);
+        }
+    }
 
     // Parse config path from args
-    let args: Vec<String> = std::env::args().collect();
     let configPath = if let Some(idx) = args.iter().position(|a| a == "--config") {
         args.get(idx + 1)
             .cloned()
@@ -71,17 +179,171 @@ async fn main() {
         "config.example.toml".into()
     };
 
-    let appConfig = config::load(&configPath);
+    // Lets a placeholder or short token through, with an error logged, for throwaway setups
+    let allowWeakToken = args.iter().any(|a| a == "--allow-default-token");
+
+    // `--hash-token <token>` prints a value for `auth.token_hash` and exits
+    if let Some(idx) = args.iter().position(|a| a == "--hash-token") {
+        let Some(token) = args.get(idx + 1) else {
+            eprintln!("usage: spark-console --hash-token <token>");
+            std::process::exit(2);
+        };
+        if let Some(reason) = spark_api::middleware::auth::weak_token_reason(token) {
+            if !allowWeakToken {
+                eprintln!("refusing to hash: {reason}. Pass --allow-default-token to hash it anyway.");
+                std::process::exit(1);
+            }
+        }
+        match spark_api::middleware::auth::hash_token(token) {
+            Ok(hash) => println!("{hash}"),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // `--lenient-config` boots with defaults when the config can't be loaded, for development
+    let appConfig = match config::load(&configPath) {
+        Ok(config) => config,
+        Err(e) if args.iter().any(|a| a == "--lenient-config") => {
+            tracing::warn!("{e}, using defaults (--lenient-config)");
+            config::Config::default()
+        }
+        Err(e) => {
+            eprintln!("{e}\nFix the config, or pass --lenient-config to start with defaults.");
+            std::process::exit(1);
+        }
+    };
     tracing::info!(
         "loaded config from {configPath}: bind={}:{}",
         appConfig.server.bind,
         appConfig.server.port
     );
 
+    if let Some(color) = appConfig.ui.accent_color.as_deref() {
+        if !spark_ui::components::branding::is_valid_accent_color(color) {
+            eprintln!("ui.accent_color must be a hex colour like \"#76b900\", got {color:?}");
+            std::process::exit(1);
+        }
+    }
+
+    // Refuse a bad token before anything is spawned or written to the history database
+    if let Err(e) = appConfig.auth.check_tokens() {
+        eprintln!("refusing to start: {e}");
+        std::process::exit(1);
+    }
+    if let Some(reason) = appConfig.auth.weak_token_reason() {
+        if !allowWeakToken {
+            eprintln!(
+                "refusing to start: {reason}. Set a longer token (see --hash-token), or pass \
+                 --allow-default-token to start anyway."
+            );
+            std::process::exit(1);
+        }
+        log_weak_token(&reason);
+    }
+    let authToken = SharedAuthToken::new(appConfig.auth.auth_token());
+    match authToken.get() {
+        None => warn_open_access(),
+        Some(token) => {
+            if let Some(label) = token.invalid_hash_label() {
+                tracing::error!("the \"{label}\" token_hash is not a valid argon2 hash, that token will be rejected")
+            }
+        }
+    }
+    if appConfig.auth.has_plaintext_token() {
+        tracing::warn!(
+            "an auth token is stored in plaintext; replace it with token_hash (spark-console --hash-token <token>)"
+        );
+    }
+
+    let tls = match load_tls(&appConfig.server).await {
+        Ok(tls) => tls,
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+
+    let history = match HistoryStore::open(&appConfig.history) {
+        Ok(store) => Arc::new(RwLock::new(store)),
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(1);
+        }
+    };
+    let providerOptions = appConfig.provider_options();
+    tracing::info!("container runtime: {}", providerOptions.docker.program());
+    let alerts = Arc::new(AlertMonitor::new(appConfig.alerts.clone()));
+    spawn_sampler(history.clone(), alerts.clone(), &appConfig.history, providerOptions.clone());
+
+    // Only `[auth]` is applied on reload; other sections are read once at startup
+    let reloadConfig: ConfigReload = {
+        let configPath = configPath.clone();
+        let authToken = authToken.clone();
+        Arc::new(move || {
+            let config = config::load(&configPath)?;
+            config.auth.check_tokens().map_err(|e| format!("kept the current token: {e}"))?;
+            if let Some(reason) = config.auth.weak_token_reason() {
+                if !allowWeakToken {
+                    return Err(format!("kept the current token: {reason}"));
+                }
+                log_weak_token(&reason);
+            }
+            let token = config.auth.auth_token();
+            if let Some(label) = token.as_ref().and_then(AuthToken::invalid_hash_label) {
+                return Err(format!(
+                    "kept the current token: the \"{label}\" token_hash is not a valid argon2 hash"
+                ));
+            }
+            if token.is_none() {
+                warn_open_access();
+            }
+            authToken.replace(token);
+            tracing::info!("reloaded auth settings from {configPath}");
+            Ok(())
+        })
+    };
+
     let appState = AppState {
         config_path: configPath,
+        providers: providerOptions.clone(),
+        history,
+        alerts: alerts.clone(),
+        stream_interval: std::time::Duration::from_secs(appConfig.server.stream_interval_secs.max(1)),
+        public_metrics: appConfig.auth.public_metrics,
+        auth_token: authToken.clone(),
+        reload_config: Some(reloadConfig.clone()),
+        cookies: CookieOptions {
+            secure: appConfig.server.secure_cookies,
+        },
+        cors_allowed_origins: appConfig.server.cors_allowed_origins.clone(),
     };
 
+    // Only listen for SIGHUP once the startup checks above have accepted the config
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::hangup()) {
+            Ok(mut hangups) => {
+                tokio::spawn(async move {
+                    while hangups.recv().await.is_some() {
+                        if let Err(e) = reloadConfig() {
+                            tracing::error!("config reload failed: {e}");
+                        }
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("failed to listen for SIGHUP, reload with POST /api/v1/admin/reload: {e}"),
+        }
+    }
+
+    let cookieOptions = appState.cookies;
+    let capabilities = spark_api::routes::capabilities::server_capabilities(&appState);
+    let uiOptions = appConfig.ui.clone();
+
     // Get Leptos configuration and override site_addr with config values
     let conf = get_configuration(None).expect("failed to load Leptos configuration");
     let mut leptosOptions = conf.leptos_options;
@@ -98,7 +360,7 @@ async fn main() {
     let routes = generate_route_list(App);
 
     // Build the API sub-router with its own state, then convert to a stateless Router
-    let apiRouter = spark_api::api_router(appState);
+    let apiRouter = spark_api::api_router(appState.clone());
 
     // Compose the full router:
     // - API routes are nested and carry their own AppState (via .with_state)
@@ -107,7 +369,16 @@ async fn main() {
         .leptos_routes_with_context(
             &leptosOptions,
             routes,
-            move || {},
+            {
+                move || {
+                    provide_context(providerOptions.clone());
+                    provide_context(capabilities.clone());
+                    provide_context(uiOptions.clone());
+                    provide_context(alerts.clone());
+                    provide_context(cookieOptions);
+                    provide_context(authToken.clone());
+                }
+            },
             {
                 let leptosOptions = leptosOptions.clone();
                 move || shell(leptosOptions.clone())
@@ -115,15 +386,100 @@ async fn main() {
         )
         .fallback(leptos_axum::file_and_error_handler(shell))
         .with_state(leptosOptions)
-        .merge(apiRouter)
-        .layer(TraceLayer::new_for_http());
+        .layer(from_fn_with_state(appState, require_page_auth))
+        .merge(apiRouter);
+    let app = with_request_logging(app);
 
-    tracing::info!("listening on {addr}");
     let listener = tokio::net::TcpListener::bind(&addr).await
         .unwrap_or_else(|e| panic!("failed to bind to {addr}: {e}"));
-    axum::serve(listener, app.into_make_service())
+    match tls {
+        Some(tls) => {
+            tracing::info!("listening on https://{addr}");
+            let listener = listener.into_std().expect("failed to take over the listening socket");
+            axum_server::from_tcp_rustls(listener, tls)
+                .serve(app.into_make_service())
+                .await
+                .expect("server exited with error");
+        }
+        None => {
+            tracing::info!("listening on http://{addr}");
+            axum::serve(listener, app.into_make_service())
+                .await
+                .expect("server exited with error");
+        }
+    }
+}
+
+/// Logged when `--allow-default-token` lets a weak token through, at startup or reload.
+#[cfg(feature = "ssr")]
+fn log_weak_token(reason: &str) {
+    tracing::error!("{reason}; anyone who guesses it can control this machine (--allow-default-token)");
+}
+
+#[cfg(feature = "ssr")]
+fn warn_open_access() {
+    tracing::warn!("no auth.token_hash configured, the console and API are open to anyone who can reach them");
+}
+
+/// The TLS setup for `server.tls_cert` and `server.tls_key`, or `None` to serve plain
+/// HTTP. Setting only one of them, or files that can't be loaded, is an error.
+#[cfg(feature = "ssr")]
+async fn load_tls(
+    server: &config::ServerConfig,
+) -> Result<Option<axum_server::tls_rustls::RustlsConfig>, String> {
+    let (cert, key) = match (&server.tls_cert, &server.tls_key) {
+        (Some(cert), Some(key)) => (cert, key),
+        (None, None) => return Ok(None),
+        _ => return Err("server.tls_cert and server.tls_key must be set together".into()),
+    };
+    // The build only includes ring, so there is exactly one provider to install
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    axum_server::tls_rustls::RustlsConfig::from_pem_file(cert, key)
         .await
-        .expect("server exited with error");
+        .map(Some)
+        .map_err(|e| format!("failed to load TLS certificate {cert} and key {key}: {e}"))
+}
+
+/// Tag every request with an `x-request-id` (kept if the client sent one, echoed on the
+/// response) and log method, path, status and latency against it at info level.
+#[cfg(feature = "ssr")]
+fn with_request_logging(app: axum::Router) -> axum::Router {
+    use axum::{body::Body, http::{HeaderName, Request}};
+    use tower::ServiceBuilder;
+    use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+    use tower_http::trace::{DefaultOnRequest, DefaultOnResponse, TraceLayer};
+    use tower_http::LatencyUnit;
+    use tracing::Level;
+
+    let requestId = HeaderName::from_static("x-request-id");
+    app.layer(
+        ServiceBuilder::new()
+            .layer(SetRequestIdLayer::new(requestId.clone(), MakeRequestUuid))
+            .layer(
+                TraceLayer::new_for_http()
+                    .make_span_with(|request: &Request<Body>| {
+                        let id = request
+                            .headers()
+                            .get("x-request-id")
+                            .and_then(|v| v.to_str().ok())
+                            .unwrap_or("-");
+                        tracing::info_span!(
+                            "request",
+                            id = %id,
+                            method = %request.method(),
+                            path = %request.uri().path(),
+                            auth = tracing::field::Empty,
+                        )
+                    })
+                    .on_request(DefaultOnRequest::new().level(Level::DEBUG))
+                    .on_response(
+                        DefaultOnResponse::new()
+                            .level(Level::INFO)
+                            .latency_unit(LatencyUnit::Millis),
+                    ),
+            )
+            .layer(PropagateRequestIdLayer::new(requestId)),
+    )
 }
 
 #[cfg(not(feature = "ssr"))]
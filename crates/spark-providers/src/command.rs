@@ -0,0 +1,23 @@
+use std::process::Output;
+use tokio::process::Command;
+use tokio::time::{timeout, Duration};
+
+/// Timeout for quick queries like nvidia-smi.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run `cmd` to completion, killing the child if it runs longer than `limit`.
+///
+/// A wedged driver or daemon can leave tools like nvidia-smi or docker hanging
+/// forever; every provider goes through this so a request can't block on them.
+/// The error names the program, so callers only need to add what they were doing.
+pub async fn run_with_timeout(cmd: &mut Command, limit: Duration) -> Result<Output, String> {
+    let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+    // Dropping the output future on timeout then kills the child
+    cmd.kill_on_drop(true);
+
+    match timeout(limit, cmd.output()).await {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(e)) => Err(format!("failed to run {program}: {e}")),
+        Err(_) => Err(format!("{program} timed out after {}s", limit.as_secs())),
+    }
+}
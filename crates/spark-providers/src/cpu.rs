@@ -1,21 +1,198 @@
 use spark_types::CpuMetrics;
+use std::collections::HashSet;
 use tracing::warn;
 
+use crate::pressure;
+
+/// hwmon driver names that report the CPU package temperature.
+const CPU_SENSOR_NAMES: &[&str] = &["coretemp", "k10temp", "cpu_thermal"];
+
 pub async fn collect() -> CpuMetrics {
-    match read_proc_loadavg().await {
+    let mut metrics = match read_proc_loadavg().await {
         Ok(metrics) => metrics,
         Err(e) => {
             warn!("/proc/loadavg unavailable, returning mock CPU data: {e}");
-            mock_cpu_metrics()
+            return mock_cpu_metrics();
+        }
+    };
+    metrics.temperature_c = read_hwmon_temperature().await;
+    metrics.pressure_some_avg10 = pressure::read_some("cpu").await.map(|psi| psi.avg10);
+    match read_proc_cpuinfo().await {
+        Ok(info) => {
+            metrics.model = info.model;
+            metrics.physical_cores = info.physical_cores;
+            metrics.logical_cores = info.logical_cores;
+            metrics.current_mhz = info.current_mhz;
         }
+        Err(e) => warn!("CPU info unavailable: {e}"),
     }
+    normalize_load(&mut metrics);
+    metrics
+}
+
+/// Fill in the per-core load averages, falling back to the scheduler's CPU count
+/// when /proc/cpuinfo couldn't be parsed.
+fn normalize_load(metrics: &mut CpuMetrics) {
+    let cores = if metrics.logical_cores > 0 {
+        metrics.logical_cores as f32
+    } else {
+        std::thread::available_parallelism().map_or(1, |n| n.get()) as f32
+    };
+    metrics.load_per_core_1m = metrics.load_1m / cores;
+    metrics.load_per_core_5m = metrics.load_5m / cores;
+    metrics.load_per_core_15m = metrics.load_15m / cores;
+}
+
+struct CpuInfo {
+    model: String,
+    physical_cores: u32,
+    logical_cores: u32,
+    current_mhz: f32,
+}
+
+async fn read_proc_cpuinfo() -> Result<CpuInfo, String> {
+    let contents = tokio::fs::read_to_string("/proc/cpuinfo")
+        .await
+        .map_err(|e| format!("failed to read /proc/cpuinfo: {e}"))?;
+
+    let mut modelName = None;
+    let mut implementer = None;
+    let mut part = None;
+    let mut logicalCores = 0u32;
+    let mut cores = HashSet::new();
+    let mut physicalId = String::new();
+    let mut mhzTotal = 0.0f32;
+    let mut mhzCount = 0u32;
+
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "processor" => logicalCores += 1,
+            "model name" if modelName.is_none() => modelName = Some(value.to_string()),
+            "CPU implementer" if implementer.is_none() => implementer = Some(value.to_string()),
+            "CPU part" if part.is_none() => part = Some(value.to_string()),
+            "physical id" => physicalId = value.to_string(),
+            // "core id" follows "physical id" within each processor block
+            "core id" => {
+                cores.insert((physicalId.clone(), value.to_string()));
+            }
+            "cpu MHz" => {
+                if let Ok(mhz) = value.parse::<f32>() {
+                    mhzTotal += mhz;
+                    mhzCount += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if logicalCores == 0 {
+        return Err("no processors listed in /proc/cpuinfo".into());
+    }
+
+    // ARM kernels have no "model name"; use the board model or the raw part IDs instead
+    let model = match modelName {
+        Some(name) => name,
+        None => match read_device_tree_model().await {
+            Some(board) => board,
+            None => match (implementer, part) {
+                (Some(implementer), Some(part)) => format!("ARM implementer {implementer} part {part}"),
+                _ => "Unknown CPU".to_string(),
+            },
+        },
+    };
+
+    let currentMhz = if mhzCount > 0 {
+        mhzTotal / mhzCount as f32
+    } else {
+        read_scaling_cur_mhz().await.unwrap_or(0.0)
+    };
+
+    Ok(CpuInfo {
+        model,
+        // ARM /proc/cpuinfo has no core ids; assume one thread per core there
+        physical_cores: if cores.is_empty() { logicalCores } else { cores.len() as u32 },
+        logical_cores: logicalCores,
+        current_mhz: currentMhz,
+    })
+}
+
+async fn read_device_tree_model() -> Option<String> {
+    let raw = tokio::fs::read_to_string("/proc/device-tree/model").await.ok()?;
+    let model = raw.trim_end_matches('\0').trim();
+    (!model.is_empty()).then(|| model.to_string())
+}
+
+/// cpufreq's current frequency for cpu0 (in kHz on disk), for CPUs that don't list MHz in cpuinfo.
+async fn read_scaling_cur_mhz() -> Option<f32> {
+    let raw = tokio::fs::read_to_string("/sys/devices/system/cpu/cpu0/cpufreq/scaling_cur_freq")
+        .await
+        .ok()?;
+    raw.trim().parse::<f32>().ok().map(|khz| khz / 1000.0)
+}
+
+/// Find a CPU sensor under /sys/class/hwmon and return its first temperature input.
+async fn read_hwmon_temperature() -> Option<u32> {
+    let mut entries = tokio::fs::read_dir("/sys/class/hwmon").await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let dir = entry.path();
+        let name = match tokio::fs::read_to_string(dir.join("name")).await {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        if !CPU_SENSOR_NAMES.contains(&name.trim()) {
+            continue;
+        }
+        if let Some(celsius) = read_first_temp_input(&dir).await {
+            return Some(celsius);
+        }
+    }
+    None
+}
+
+/// Lowest-numbered `temp*_input` in a hwmon directory, in degrees Celsius.
+/// For coretemp, temp1 is the package sensor; the rest are per-core.
+async fn read_first_temp_input(dir: &std::path::Path) -> Option<u32> {
+    let mut entries = tokio::fs::read_dir(dir).await.ok()?;
+    let mut inputs = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let fileName = entry.file_name().to_string_lossy().into_owned();
+        let Some(number) = fileName
+            .strip_prefix("temp")
+            .and_then(|rest| rest.strip_suffix("_input"))
+            .and_then(|n| n.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        inputs.push((number, entry.path()));
+    }
+    inputs.sort();
+
+    for (_, path) in inputs {
+        let Ok(raw) = tokio::fs::read_to_string(&path).await else {
+            continue;
+        };
+        // Values are in millidegrees Celsius
+        if let Ok(milli) = raw.trim().parse::<i64>() {
+            return Some((milli.max(0) / 1000) as u32);
+        }
+    }
+    None
 }
 
 async fn read_proc_loadavg() -> Result<CpuMetrics, String> {
     let contents = tokio::fs::read_to_string("/proc/loadavg")
         .await
         .map_err(|e| format!("failed to read /proc/loadavg: {e}"))?;
+    parse_loadavg(&contents)
+}
 
+/// Parse "0.52 0.58 0.59 3/842 12345": the load averages, then running/total tasks and
+/// the last PID handed out.
+fn parse_loadavg(contents: &str) -> Result<CpuMetrics, String> {
     let fields: Vec<&str> = contents.split_whitespace().collect();
     if fields.len() < 3 {
         return Err(format!("unexpected /proc/loadavg format: {contents}"));
@@ -24,18 +201,53 @@ async fn read_proc_loadavg() -> Result<CpuMetrics, String> {
     let load1m = fields[0].parse::<f32>().unwrap_or(0.0);
     let load5m = fields[1].parse::<f32>().unwrap_or(0.0);
     let load15m = fields[2].parse::<f32>().unwrap_or(0.0);
+    let (procsRunning, procsTotal) = fields
+        .get(3)
+        .and_then(|tasks| tasks.split_once('/'))
+        .map(|(running, total)| (running.parse().unwrap_or(0), total.parse().unwrap_or(0)))
+        .unwrap_or((0, 0));
 
     Ok(CpuMetrics {
         load_1m: load1m,
         load_5m: load5m,
         load_15m: load15m,
+        procs_running: procsRunning,
+        procs_total: procsTotal,
+        ..CpuMetrics::default()
     })
 }
 
 fn mock_cpu_metrics() -> CpuMetrics {
-    CpuMetrics {
+    let mut metrics = CpuMetrics {
         load_1m: 2.45,
         load_5m: 1.89,
         load_15m: 1.32,
+        model: "ARM Neoverse-V2 (mock)".into(),
+        physical_cores: 72,
+        logical_cores: 72,
+        current_mhz: 3100.0,
+        temperature_c: Some(48),
+        procs_running: 3,
+        procs_total: 842,
+        ..CpuMetrics::default()
+    };
+    normalize_load(&mut metrics);
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_task_counts_from_loadavg() {
+        let metrics = parse_loadavg("0.52 0.58 0.59 12/840 98765\n").unwrap();
+        assert_eq!(metrics.load_5m, 0.58);
+        assert_eq!((metrics.procs_running, metrics.procs_total), (12, 840));
+
+        // Older or trimmed formats still give the load averages
+        let metrics = parse_loadavg("1.00 2.00 3.00").unwrap();
+        assert_eq!((metrics.load_15m, metrics.procs_total), (3.0, 0));
+        assert!(parse_loadavg("1.00").is_err());
     }
 }
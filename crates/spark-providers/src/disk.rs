@@ -1,40 +1,431 @@
-use spark_types::DiskMetrics;
+use serde::Deserialize;
+use spark_types::{DirUsage, DiskMetrics};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tokio::time::Duration;
 use tracing::warn;
 
-pub async fn collect() -> DiskMetrics {
-    match read_disk_stats() {
-        Ok(metrics) => metrics,
-        Err(e) => {
-            warn!("statvfs unavailable, returning mock disk data: {e}");
-            mock_disk_metrics()
+use crate::command::run_with_timeout;
+use crate::RATE_SAMPLE_INTERVAL;
+
+/// /proc/diskstats counts in 512-byte sectors regardless of the device's block size.
+const SECTOR_BYTES: f64 = 512.0;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DiskOptions {
+    /// Mount points to report, in dashboard order.
+    pub mounts: Vec<String>,
+    /// Directories, besides `mounts`, whose usage breakdown may be requested.
+    pub usage_paths: Vec<String>,
+    /// Directory levels below the requested path that the breakdown lists.
+    pub usage_depth: usize,
+    /// How long one breakdown may walk before `du` is killed.
+    pub usage_timeout_secs: u64,
+}
+
+impl Default for DiskOptions {
+    fn default() -> Self {
+        Self {
+            mounts: vec!["/".into()],
+            usage_paths: Vec::new(),
+            usage_depth: 1,
+            usage_timeout_secs: 30,
+        }
+    }
+}
+
+impl DiskOptions {
+    /// The directories whose usage can be requested: the mounts, then `usage_paths`.
+    pub fn usage_roots(&self) -> Vec<String> {
+        let mut roots = self.mounts.clone();
+        for path in &self.usage_paths {
+            if !roots.contains(path) {
+                roots.push(path.clone());
+            }
+        }
+        roots
+    }
+}
+
+/// Directories returned by [`dir_usage`] when the caller doesn't ask for a number.
+pub const DEFAULT_USAGE_TOP: usize = 20;
+/// Upper bound on the `top` passed to [`dir_usage`].
+pub const MAX_USAGE_TOP: usize = 200;
+
+/// One entry per configured mount point that could be read, in config order.
+pub async fn collect(options: &DiskOptions) -> Vec<DiskMetrics> {
+    let mut disks = Vec::new();
+    let mut deviceIds = Vec::new();
+    for mountPoint in &options.mounts {
+        match read_disk_stats(mountPoint) {
+            Ok(metrics) => {
+                deviceIds.push(mount_device_id(mountPoint));
+                disks.push(metrics);
+            }
+            Err(e) => warn!("skipping disk {mountPoint}: {e}"),
+        }
+    }
+
+    if disks.is_empty() {
+        warn!("no configured mount point could be read, returning mock disk data");
+        return mock_disk_metrics(options);
+    }
+
+    apply_io_rates(&mut disks, deviceIds).await;
+    disks
+}
+
+/// `path` resolved, if it is one of [`DiskOptions::usage_roots`] and a directory. Errors
+/// here are the caller's fault; check with this before [`dir_usage`].
+pub async fn usage_root(options: &DiskOptions, path: &str) -> Result<PathBuf, String> {
+    let requested = tokio::fs::canonicalize(path)
+        .await
+        .map_err(|e| format!("{path}: {e}"))?;
+    let mut allowed = false;
+    for root in options.usage_roots() {
+        if let Ok(root) = tokio::fs::canonicalize(&root).await {
+            allowed |= requested == root;
         }
     }
+    if !allowed {
+        return Err(format!("{path} is not a disk mount or disk.usage_paths entry"));
+    }
+    if !requested.is_dir() {
+        return Err(format!("not a directory: {path}"));
+    }
+    Ok(requested)
+}
+
+/// The `top` largest directories below `root` (from [`usage_root`]), down to `usage_depth`
+/// levels and largest first, without crossing into other filesystems. The walk runs `du`
+/// and is killed after `usage_timeout_secs`. Directories `du` can't read are left out
+/// rather than failing the whole breakdown.
+pub async fn dir_usage(options: &DiskOptions, root: &Path, top: usize) -> Result<Vec<DirUsage>, String> {
+    let output = run_with_timeout(
+        Command::new("du")
+            .args(["-x", "-B1", &format!("--max-depth={}", options.usage_depth.max(1)), "--"])
+            .arg(root),
+        Duration::from_secs(options.usage_timeout_secs.max(1)),
+    )
+    .await?;
+    let mut entries = parse_du(&String::from_utf8_lossy(&output.stdout), root);
+    // du also exits 1 for unreadable subdirectories, after listing the rest
+    if entries.is_empty() && !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("du failed: {}", stderr.lines().next().unwrap_or("").trim()));
+    }
+
+    entries.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+    entries.truncate(top.clamp(1, MAX_USAGE_TOP));
+    Ok(entries)
 }
 
-fn read_disk_stats() -> Result<DiskMetrics, String> {
-    let stat = nix::sys::statvfs::statvfs("/")
+/// `du` lines are `<bytes>\t<path>`; the line for `root` itself is the total and is dropped.
+fn parse_du(output: &str, root: &Path) -> Vec<DirUsage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (size, path) = line.split_once('\t')?;
+            let size = size.trim().parse().ok()?;
+            (Path::new(path) != root).then(|| DirUsage {
+                path: path.to_string(),
+                size_bytes: size,
+            })
+        })
+        .collect()
+}
+
+/// Kernel and virtual filesystems left out of the filesystem listing unless asked for.
+/// Containers' overlay mounts go too, since they repeat the backing disk's numbers.
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs", "binfmt_misc", "bpf", "cgroup", "cgroup2", "configfs", "debugfs", "devpts",
+    "devtmpfs", "efivarfs", "fusectl", "hugetlbfs", "mqueue", "nsfs", "overlay", "proc",
+    "pstore", "ramfs", "rpc_pipefs", "securityfs", "selinuxfs", "squashfs", "sysfs", "tmpfs",
+    "tracefs",
+];
+
+/// A line of /proc/mounts.
+#[derive(Debug, PartialEq)]
+struct MountEntry {
+    device: String,
+    mount_point: String,
+    fs_type: String,
+}
+
+/// Every mounted filesystem with its usage, inode and throughput figures, sorted by mount
+/// point. Pseudo filesystems (proc, sysfs, tmpfs, overlay...) are skipped unless
+/// `includePseudo` is set.
+pub async fn list_filesystems(includePseudo: bool) -> Result<Vec<DiskMetrics>, String> {
+    let contents = tokio::fs::read_to_string("/proc/mounts")
+        .await
+        .map_err(|e| format!("failed to read /proc/mounts: {e}"))?;
+
+    let mut disks = Vec::new();
+    let mut deviceIds = Vec::new();
+    for entry in parse_mounts(&contents) {
+        if !includePseudo && PSEUDO_FILESYSTEMS.contains(&entry.fs_type.as_str()) {
+            continue;
+        }
+        // Over-mounted paths appear more than once; only the last mount is visible
+        if let Some(pos) = disks.iter().position(|d: &DiskMetrics| d.mount_point == entry.mount_point) {
+            disks.remove(pos);
+            deviceIds.remove(pos);
+        }
+        match read_disk_stats(&entry.mount_point) {
+            // Nothing to show for size-less mounts like autofs triggers
+            Ok(metrics) if metrics.total_bytes == 0 && !includePseudo => {}
+            Ok(mut metrics) => {
+                metrics.fs_type = entry.fs_type;
+                metrics.device = entry.device;
+                deviceIds.push(mount_device_id(&entry.mount_point));
+                disks.push(metrics);
+            }
+            Err(e) => warn!("skipping filesystem {}: {e}", entry.mount_point),
+        }
+    }
+
+    apply_io_rates(&mut disks, deviceIds).await;
+
+    disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    Ok(disks)
+}
+
+/// Fill in device names and throughput for `disks`, whose backing devices are
+/// `deviceIds` in the same order.
+async fn apply_io_rates(disks: &mut [DiskMetrics], deviceIds: Vec<Option<(u64, u64)>>) {
+    match sample_io_rates().await {
+        Ok(rates) => {
+            for (disk, deviceId) in disks.iter_mut().zip(deviceIds) {
+                let Some(rate) = deviceId.and_then(|id| rates.get(&id)) else {
+                    continue;
+                };
+                disk.device = rate.name.clone();
+                disk.read_bytes_per_sec = Some(rate.read_bytes_per_sec);
+                disk.write_bytes_per_sec = Some(rate.write_bytes_per_sec);
+            }
+        }
+        Err(e) => warn!("disk throughput unavailable: {e}"),
+    }
+}
+
+/// Parse /proc/mounts: `device mountpoint fstype options dump pass`, with spaces and other
+/// special characters in paths written as octal escapes (`\040`).
+fn parse_mounts(contents: &str) -> Vec<MountEntry> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(MountEntry {
+                device: unescape_mount_field(fields.next()?),
+                mount_point: unescape_mount_field(fields.next()?),
+                fs_type: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let octal = bytes.get(i + 1..i + 4).filter(|d| d.iter().all(|b| (b'0'..=b'7').contains(b)));
+        if let (b'\\', Some(digits)) = (bytes[i], octal) {
+            out.push(digits.iter().fold(0u8, |acc, d| acc.wrapping_mul(8).wrapping_add(d - b'0')));
+            i += 4;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// (major, minor) of the device a path lives on.
+fn mount_device_id(mountPoint: &str) -> Option<(u64, u64)> {
+    let stat = nix::sys::stat::stat(mountPoint).ok()?;
+    Some((nix::sys::stat::major(stat.st_dev), nix::sys::stat::minor(stat.st_dev)))
+}
+
+struct DiskCounters {
+    name: String,
+    sectorsRead: u64,
+    sectorsWritten: u64,
+}
+
+struct IoRate {
+    name: String,
+    read_bytes_per_sec: f64,
+    write_bytes_per_sec: f64,
+}
+
+/// Read /proc/diskstats twice and turn the sector counters into bytes per second,
+/// keyed by (major, minor).
+async fn sample_io_rates() -> Result<HashMap<(u64, u64), IoRate>, String> {
+    let before = read_proc_diskstats().await?;
+    tokio::time::sleep(RATE_SAMPLE_INTERVAL).await;
+    let after = read_proc_diskstats().await?;
+
+    let seconds = RATE_SAMPLE_INTERVAL.as_secs_f64();
+    Ok(after
+        .into_iter()
+        .filter_map(|(id, now)| {
+            let then = before.get(&id)?;
+            let rate = IoRate {
+                read_bytes_per_sec: now.sectorsRead.saturating_sub(then.sectorsRead) as f64
+                    * SECTOR_BYTES
+                    / seconds,
+                write_bytes_per_sec: now.sectorsWritten.saturating_sub(then.sectorsWritten) as f64
+                    * SECTOR_BYTES
+                    / seconds,
+                name: now.name,
+            };
+            Some((id, rate))
+        })
+        .collect())
+}
+
+async fn read_proc_diskstats() -> Result<HashMap<(u64, u64), DiskCounters>, String> {
+    let contents = tokio::fs::read_to_string("/proc/diskstats")
+        .await
+        .map_err(|e| format!("failed to read /proc/diskstats: {e}"))?;
+
+    let mut counters = HashMap::new();
+    for line in contents.lines() {
+        // major minor name reads merged sectors_read ms writes merged sectors_written ...
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 10 {
+            continue;
+        }
+        let name = fields[2];
+        if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+            continue;
+        }
+        let (Ok(major), Ok(minor)) = (fields[0].parse::<u64>(), fields[1].parse::<u64>()) else {
+            continue;
+        };
+        counters.insert(
+            (major, minor),
+            DiskCounters {
+                name: name.to_string(),
+                sectorsRead: fields[5].parse().unwrap_or(0),
+                sectorsWritten: fields[9].parse().unwrap_or(0),
+            },
+        );
+    }
+    Ok(counters)
+}
+
+fn read_disk_stats(mountPoint: &str) -> Result<DiskMetrics, String> {
+    let stat = nix::sys::statvfs::statvfs(mountPoint)
         .map_err(|e| format!("statvfs failed: {e}"))?;
 
     let blockSize = stat.block_size() as u64;
     let totalBytes = stat.blocks() as u64 * blockSize;
     let availableBytes = stat.blocks_available() as u64 * blockSize;
     let usedBytes = totalBytes.saturating_sub(availableBytes);
+    let inodesTotal = stat.files() as u64;
+    let inodesUsed = inodesTotal.saturating_sub(stat.files_available() as u64);
 
     Ok(DiskMetrics {
         total_bytes: totalBytes,
         used_bytes: usedBytes,
         available_bytes: availableBytes,
-        mount_point: "/".into(),
+        mount_point: mountPoint.to_string(),
+        inodes_total: inodesTotal,
+        inodes_used: inodesUsed,
+        ..DiskMetrics::default()
     })
 }
 
-fn mock_disk_metrics() -> DiskMetrics {
+fn mock_disk_metrics(options: &DiskOptions) -> Vec<DiskMetrics> {
     let TOTAL: u64 = 2 * 1024 * 1024 * 1024 * 1024;
     let USED: u64 = 750 * 1024 * 1024 * 1024;
-    DiskMetrics {
-        total_bytes: TOTAL,
-        used_bytes: USED,
-        available_bytes: TOTAL - USED,
-        mount_point: "/".into(),
+    let mut mounts: Vec<&str> = options.mounts.iter().map(String::as_str).collect();
+    if mounts.is_empty() {
+        mounts.push("/");
+    }
+    mounts
+        .into_iter()
+        .map(|mountPoint| DiskMetrics {
+            total_bytes: TOTAL,
+            used_bytes: USED,
+            available_bytes: TOTAL - USED,
+            mount_point: mountPoint.to_string(),
+            inodes_total: 134_217_728,
+            inodes_used: 3_145_728,
+            device: "nvme0n1p2".into(),
+            read_bytes_per_sec: Some(48.0 * 1024.0 * 1024.0),
+            write_bytes_per_sec: Some(6.5 * 1024.0 * 1024.0),
+            fs_type: "ext4".into(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_du_output_without_the_total() {
+        let output = "4096\t/srv/data/empty\n1073741824\t/srv/data/models\nbogus\n1073745920\t/srv/data\n";
+        assert_eq!(
+            parse_du(output, Path::new("/srv/data")),
+            [
+                DirUsage {
+                    path: "/srv/data/empty".into(),
+                    size_bytes: 4096,
+                },
+                DirUsage {
+                    path: "/srv/data/models".into(),
+                    size_bytes: 1073741824,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn usage_is_limited_to_configured_roots() {
+        let dir = std::env::temp_dir().join(format!("spark-du-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("big")).unwrap();
+        std::fs::create_dir_all(dir.join("small")).unwrap();
+        std::fs::write(dir.join("big/blob"), vec![1u8; 256 * 1024]).unwrap();
+        let options = DiskOptions {
+            mounts: Vec::new(),
+            usage_paths: vec![dir.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+
+        let root = usage_root(&options, &dir.to_string_lossy()).await.unwrap();
+        let usage = dir_usage(&options, &root, 1).await;
+        let outside = usage_root(&options, "/etc").await;
+        let below = usage_root(&options, &dir.join("big").to_string_lossy()).await;
+        let escape = usage_root(&options, &dir.join("../").to_string_lossy()).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let usage = usage.unwrap();
+        assert_eq!(usage.len(), 1);
+        assert!(usage[0].path.ends_with("/big"));
+        assert!(outside.is_err() && below.is_err() && escape.is_err());
+    }
+
+    #[test]
+    fn parses_mounts_with_escaped_paths() {
+        let contents = "proc /proc proc rw,relatime 0 0\n\
+                        /dev/nvme0n1p2 / ext4 rw,relatime 0 0\n\
+                        /dev/sdb1 /mnt/model\\040store xfs rw 0 0\n";
+        let mounts = parse_mounts(contents);
+        assert_eq!(mounts.len(), 3);
+        assert_eq!(mounts[0].fs_type, "proc");
+        assert_eq!(
+            mounts[2],
+            MountEntry {
+                device: "/dev/sdb1".into(),
+                mount_point: "/mnt/model store".into(),
+                fs_type: "xfs".into(),
+            }
+        );
     }
 }
@@ -1,14 +1,159 @@
-use spark_types::{ContainerActionResult, ContainerStatus, ContainerSummary};
-use std::collections::HashMap;
-use tokio::time::{timeout, Duration};
-use tracing::warn;
+use serde::Deserialize;
+use spark_types::{
+    ContainerActionResult, ContainerHealth, ContainerStats, ContainerStatus, ContainerSummary,
+    ImageSummary, VolumeSummary,
+};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::Path;
+use std::process::Output;
+use std::sync::OnceLock;
+use tokio::process::Command;
+use tokio::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+use crate::command::run_with_timeout;
+use crate::docker_api;
+use crate::podman::Podman;
 
 const PS_TIMEOUT: Duration = Duration::from_secs(10);
 const STATS_TIMEOUT: Duration = Duration::from_secs(15);
 const INSPECT_TIMEOUT: Duration = Duration::from_secs(10);
+const SIZED_PS_TIMEOUT: Duration = Duration::from_secs(30);
+// `stop` waits up to 10s for a graceful shutdown before killing
+pub(crate) const ACTION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Tries of a listing query while the daemon can't be reached, e.g. just after a restart.
+const DAEMON_ATTEMPTS: u32 = 3;
+/// Pause before the first retry, doubled before each later one.
+const DAEMON_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Actions accepted by [`execute_action`].
+pub const SUPPORTED_ACTIONS: &[&str] =
+    &["start", "stop", "restart", "pause", "unpause", "kill", "remove"];
+
+/// Which container engine to talk to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    /// Docker if its socket or CLI is present, otherwise Podman if its CLI is.
+    #[default]
+    Auto,
+    Docker,
+    Podman,
+}
+
+/// Whether `program` is an executable file in one of the `PATH` directories.
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|paths| {
+        std::env::split_paths(&paths).any(|dir| dir.join(program).is_file())
+    })
+}
+
+/// Resolve [`BackendKind::Auto`]. Docker wins when both are installed, and is also the
+/// answer when neither is, so errors name the engine most people expect.
+fn detect_backend(socketPath: &str) -> BackendKind {
+    if Path::new(socketPath).exists() || on_path("docker") {
+        BackendKind::Docker
+    } else if on_path("podman") {
+        BackendKind::Podman
+    } else {
+        BackendKind::Docker
+    }
+}
+
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Options controlling how container data is collected.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct DockerOptions {
+    /// Query writable-layer and root filesystem sizes (`docker ps --size`).
+    /// Docker has to walk each container's filesystem for this, so it is off by default.
+    pub include_size: bool,
+    pub backend: BackendKind,
+    /// Podman only: start/stop/restart systemd-managed (quadlet) containers
+    /// through `systemctl` so systemd doesn't fight the change.
+    pub systemd: bool,
+    /// Docker only: talk to the Engine API on this socket instead of running the CLI.
+    /// The CLI is used when the socket doesn't exist or the API call fails.
+    pub socket_path: String,
+}
+
+impl Default for DockerOptions {
+    fn default() -> Self {
+        Self {
+            include_size: false,
+            backend: BackendKind::default(),
+            systemd: false,
+            socket_path: DEFAULT_DOCKER_SOCKET.into(),
+        }
+    }
+}
+
+impl DockerOptions {
+    /// The engine in use, with `Auto` resolved. Detection runs once per process since
+    /// installing an engine under a running server is rare enough to need a restart.
+    pub fn backend(&self) -> BackendKind {
+        static DETECTED: OnceLock<BackendKind> = OnceLock::new();
+        match self.backend {
+            BackendKind::Auto => *DETECTED.get_or_init(|| detect_backend(&self.socket_path)),
+            explicit => explicit,
+        }
+    }
+
+    /// CLI binary for the selected backend.
+    pub fn program(&self) -> &'static str {
+        match self.backend() {
+            BackendKind::Podman => podman_backend(self).program(),
+            _ => Docker.program(),
+        }
+    }
+
+    /// The Engine API socket, if the Docker backend is selected and the socket is present.
+    pub(crate) fn engine_socket(&self) -> Option<&str> {
+        (self.backend() == BackendKind::Docker && Path::new(&self.socket_path).exists())
+            .then_some(self.socket_path.as_str())
+    }
+}
+
+/// A container engine driven through a docker-compatible CLI.
+///
+/// Listing, stats and inspect share one code path; only the binary, a few
+/// template fields and how lifecycle actions are carried out differ.
+pub trait ContainerBackend: Sync {
+    /// CLI binary used for ps/stats/inspect.
+    fn program(&self) -> &'static str;
+
+    /// `inspect` template that yields the OCI runtime name.
+    fn runtime_template(&self) -> &'static str {
+        "{{.HostConfig.Runtime}}"
+    }
+
+    /// Run a lifecycle action that has already been checked against [`SUPPORTED_ACTIONS`].
+    fn run_action(
+        &self,
+        container_id: &str,
+        action: &str,
+    ) -> impl Future<Output = ContainerActionResult> + Send;
+}
+
+/// The Docker daemon via the `docker` CLI.
+pub struct Docker;
+
+impl ContainerBackend for Docker {
+    fn program(&self) -> &'static str {
+        "docker"
+    }
+
+    async fn run_action(&self, container_id: &str, action: &str) -> ContainerActionResult {
+        run_cli_action(self.program(), container_id, action).await
+    }
+}
 
 /// Parse a Docker size string like "3.578MiB", "121.7GiB", "15.6kB", "126B" into bytes.
-fn parse_docker_size(s: &str) -> u64 {
+/// Units are matched case-insensitively and may be separated from the number by a space;
+/// anything unparseable ("N/A", "--") is 0.
+pub(crate) fn parse_docker_size(s: &str) -> u64 {
     let s = s.trim();
     if s.is_empty() {
         return 0;
@@ -26,23 +171,39 @@ fn parse_docker_size(s: &str) -> u64 {
         Err(_) => return 0,
     };
 
-    let multiplier: f64 = match unit {
-        "B" => 1.0,
-        "kB" | "KB" => 1_000.0,
-        "MB" => 1_000_000.0,
-        "GB" => 1_000_000_000.0,
-        "TB" => 1_000_000_000_000.0,
-        "KiB" => 1_024.0,
-        "MiB" => 1_048_576.0,
-        "GiB" => 1_073_741_824.0,
-        "TiB" => 1_099_511_627_776.0,
-        _ => 1.0,
+    let multiplier: f64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "kb" => 1_000.0,
+        "mb" => 1_000_000.0,
+        "gb" => 1_000_000_000.0,
+        "tb" => 1_000_000_000_000.0,
+        "kib" => 1_024.0,
+        "mib" => 1_048_576.0,
+        "gib" => 1_073_741_824.0,
+        "tib" => 1_099_511_627_776.0,
+        _ => return 0,
     };
 
     (num * multiplier) as u64
 }
 
-fn parse_status(state: &str) -> ContainerStatus {
+/// Parse the `{{.Size}}` column of `docker ps --size`, e.g. "12.3kB (virtual 1.09GB)",
+/// into (writable layer bytes, root filesystem bytes).
+fn parse_size_column(raw: &str) -> (u64, u64) {
+    let raw = raw.trim();
+    match raw.split_once("(virtual") {
+        Some((rw, virt)) => (
+            parse_docker_size(rw),
+            parse_docker_size(virt.trim_end_matches(')')),
+        ),
+        None => {
+            let rw = parse_docker_size(raw);
+            (rw, rw)
+        }
+    }
+}
+
+pub(crate) fn parse_status(state: &str) -> ContainerStatus {
     match state.trim().to_lowercase().as_str() {
         "running" => ContainerStatus::Running,
         "exited" => ContainerStatus::Stopped,
@@ -53,8 +214,98 @@ fn parse_status(state: &str) -> ContainerStatus {
     }
 }
 
-pub async fn collect() -> Result<Vec<ContainerSummary>, String> {
-    let containers = collect_container_list().await?;
+/// `.State.Health.Status` from inspect. Containers without a healthcheck report nothing
+/// (or "none" on older podman), which maps to `None` rather than an unknown state.
+pub(crate) fn parse_health(status: &str) -> Option<ContainerHealth> {
+    match status.trim().to_lowercase().as_str() {
+        "healthy" => Some(ContainerHealth::Healthy),
+        "unhealthy" => Some(ContainerHealth::Unhealthy),
+        "starting" => Some(ContainerHealth::Starting),
+        _ => None,
+    }
+}
+
+/// Health from the `ps` status text, e.g. "Up 2 hours (healthy)" or "Up 5 seconds
+/// (health: starting)", for listings that skip inspect.
+pub(crate) fn health_from_status_text(text: &str) -> Option<ContainerHealth> {
+    if text.contains("(unhealthy)") {
+        Some(ContainerHealth::Unhealthy)
+    } else if text.contains("(healthy)") {
+        Some(ContainerHealth::Healthy)
+    } else if text.contains("(health: starting)") || text.contains("(starting)") {
+        Some(ContainerHealth::Starting)
+    } else {
+        None
+    }
+}
+
+/// Exit code and time in state from a status line: "Exited (137) 2 days ago" gives
+/// `(Some(137), Some("2 days"))` and "Up 3 hours (healthy)" gives `(None, Some("3 hours"))`.
+/// Anything else, such as "Created", gives `(None, None)`.
+pub(crate) fn parse_state_text(text: &str) -> (Option<i32>, Option<String>) {
+    let since = |rest: &str| {
+        // Drop a trailing "(healthy)" or "(Paused)"; Podman also says "Up 3 hours ago"
+        let rest = rest.split(" (").next().unwrap_or_default().trim();
+        let rest = rest.strip_suffix(" ago").unwrap_or(rest).trim();
+        (!rest.is_empty()).then(|| rest.to_string())
+    };
+    let text = text.trim();
+    if let Some(rest) = text.strip_prefix("Up ") {
+        return (None, since(rest));
+    }
+    let exited = text
+        .strip_prefix("Exited (")
+        .or_else(|| text.strip_prefix("Restarting ("))
+        .and_then(|rest| rest.split_once(')'));
+    match exited {
+        Some((code, rest)) => match code.trim().parse() {
+            Ok(code) => (Some(code), since(rest)),
+            Err(_) => (None, None),
+        },
+        None => (None, None),
+    }
+}
+
+/// List containers with inspect details. `withStats` also fills in CPU, memory and
+/// network usage, which costs a stats sampling cycle (1-2s); without it those fields are
+/// zero and callers can fetch them separately with [`collect_stats`].
+pub async fn collect(options: &DockerOptions, withStats: bool) -> Result<Vec<ContainerSummary>, String> {
+    if let Some(socket) = options.engine_socket() {
+        match docker_api::collect(socket, options.include_size, withStats).await {
+            Ok(containers) => return Ok(containers),
+            Err(e) => warn!("Docker Engine API unavailable, falling back to the CLI: {e}"),
+        }
+    }
+    match options.backend() {
+        BackendKind::Podman => collect_with(&podman_backend(options), options, withStats).await,
+        _ => collect_with(&Docker, options, withStats).await,
+    }
+}
+
+/// Usage of every running container.
+pub async fn collect_stats(options: &DockerOptions) -> Result<Vec<ContainerStats>, String> {
+    if let Some(socket) = options.engine_socket() {
+        match docker_api::collect_stats(socket).await {
+            Ok(stats) => return Ok(stats),
+            Err(e) => warn!("Docker Engine API unavailable, falling back to the CLI: {e}"),
+        }
+    }
+    Ok(collect_cli_stats(options.program()).await?.into_values().collect())
+}
+
+fn podman_backend(options: &DockerOptions) -> Podman {
+    Podman {
+        systemd: options.systemd,
+    }
+}
+
+async fn collect_with<B: ContainerBackend>(
+    backend: &B,
+    options: &DockerOptions,
+    withStats: bool,
+) -> Result<Vec<ContainerSummary>, String> {
+    let program = backend.program();
+    let containers = list_containers(program).await?;
 
     if containers.is_empty() {
         return Ok(Vec::new());
@@ -62,140 +313,443 @@ pub async fn collect() -> Result<Vec<ContainerSummary>, String> {
 
     // Collect stats for running containers
     let hasRunning = containers.iter().any(|c| c.status == ContainerStatus::Running);
-    let statsMap = if hasRunning {
-        collect_stats().await.unwrap_or_default()
+    let statsMap = if withStats && hasRunning {
+        collect_cli_stats(program).await.unwrap_or_default()
     } else {
         HashMap::new()
     };
 
     // Collect inspect data for all containers
     let ids: Vec<String> = containers.iter().map(|c| c.id.clone()).collect();
-    let inspectMap = collect_inspect(&ids).await;
+    let inspectMap = collect_inspect(program, backend.runtime_template(), &ids).await;
+
+    let sizeMap = if options.include_size {
+        collect_sizes(program).await.unwrap_or_else(|e| {
+            warn!("container size collection failed: {e}");
+            HashMap::new()
+        })
+    } else {
+        HashMap::new()
+    };
 
-    // Merge everything
-    Ok(containers
+    Ok(merge_details(containers, &statsMap, &inspectMap, &sizeMap))
+}
+
+/// Attach stats, inspect data and sizes to the `ps` listing. Every map is keyed by
+/// container ID, since names can collide (e.g. mid-rename or across compose projects).
+fn merge_details(
+    containers: Vec<ContainerSummary>,
+    statsMap: &HashMap<String, ContainerStats>,
+    inspectMap: &HashMap<String, InspectData>,
+    sizeMap: &HashMap<String, (u64, u64)>,
+) -> Vec<ContainerSummary> {
+    containers
         .into_iter()
         .map(|mut c| {
-            if let Some(stats) = statsMap.get(&c.name) {
-                c.cpu_pct = stats.cpu_pct;
-                c.memory_usage_bytes = stats.memory_usage_bytes;
-                c.memory_limit_bytes = stats.memory_limit_bytes;
-                c.net_rx_bytes = stats.net_rx_bytes;
-                c.net_tx_bytes = stats.net_tx_bytes;
+            if let Some(stats) = statsMap.get(&c.id) {
+                c.apply_stats(stats);
             }
             if let Some(inspect) = inspectMap.get(&c.id) {
                 c.runtime = inspect.runtime.clone();
                 c.restart_policy = inspect.restart_policy.clone();
                 c.mounts = inspect.mounts.clone();
+                c.health = inspect.health;
+                c.cpu_limit = inspect.cpu_limit;
+                c.mem_limit_bytes = inspect.mem_limit_bytes;
+                c.env = inspect.env.clone();
+            }
+            if let Some(&(rwSize, rootFsSize)) = sizeMap.get(&c.id) {
+                c.rw_size_bytes = Some(rwSize);
+                c.root_fs_size_bytes = Some(rootFsSize);
             }
             c
         })
-        .collect())
-}
-
-struct StatsData {
-    cpu_pct: f64,
-    memory_usage_bytes: u64,
-    memory_limit_bytes: u64,
-    net_rx_bytes: u64,
-    net_tx_bytes: u64,
+        .collect()
 }
 
 struct InspectData {
     runtime: String,
     restart_policy: String,
+    health: Option<ContainerHealth>,
     mounts: Vec<String>,
+    cpu_limit: Option<f64>,
+    mem_limit_bytes: Option<u64>,
+    env: Vec<String>,
+}
+
+/// Env keys ending in one of these have their value replaced before it leaves the server.
+const SECRET_ENV_SUFFIXES: &[&str] = &["_TOKEN", "_PASSWORD", "_KEY", "_SECRET"];
+
+/// A `KEY=value` entry from `.Config.Env`, with the value hidden when the key looks like a
+/// credential. Keys are matched case-insensitively.
+pub(crate) fn redact_env(entry: &str) -> String {
+    match entry.split_once('=') {
+        Some((key, _)) if SECRET_ENV_SUFFIXES.iter().any(|s| key.to_ascii_uppercase().ends_with(s)) => {
+            format!("{key}=<redacted>")
+        }
+        _ => entry.to_string(),
+    }
+}
+
+/// `.HostConfig.NanoCpus` and `.HostConfig.Memory` as a CPU count and bytes, where 0
+/// (or less) means no limit.
+pub(crate) fn parse_limits(nanoCpus: i64, memory: i64) -> (Option<f64>, Option<u64>) {
+    (
+        (nanoCpus > 0).then(|| nanoCpus as f64 / 1e9),
+        (memory > 0).then_some(memory as u64),
+    )
+}
+
+/// Images from `docker images`, largest first.
+pub async fn list_images(options: &DockerOptions) -> Result<Vec<ImageSummary>, String> {
+    let program = options.program();
+    let output = run_with_timeout(
+        Command::new(program).args([
+            "images",
+            "--format",
+            "{{.ID}}\t{{.Repository}}\t{{.Tag}}\t{{.Size}}\t{{.CreatedSince}}",
+        ]),
+        PS_TIMEOUT,
+    )
+    .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{program} images failed: {}", stderr.trim()));
+    }
+
+    let mut images: Vec<ImageSummary> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            if fields.len() < 5 {
+                return None;
+            }
+            Some(ImageSummary {
+                id: fields[0].trim().to_string(),
+                repo: fields[1].trim().to_string(),
+                tag: fields[2].trim().to_string(),
+                size_bytes: parse_docker_size(fields[3]),
+                created: fields[4].trim().to_string(),
+            })
+        })
+        .collect();
+    images.sort_by_key(|i| std::cmp::Reverse(i.size_bytes));
+    Ok(images)
+}
+
+/// `docker rmi <id>`. Fails if a container still uses the image.
+pub async fn remove_image(options: &DockerOptions, imageId: &str) -> ContainerActionResult {
+    if !crate::logs::is_valid_container_ref(imageId) {
+        return ContainerActionResult {
+            success: false,
+            message: format!("invalid image: {imageId}"),
+        };
+    }
+    run_cli_command(options.program(), &["rmi", imageId], "rmi").await
+}
+
+/// `docker image prune -f`: remove dangling (untagged, unreferenced) images.
+pub async fn prune_images(options: &DockerOptions) -> ContainerActionResult {
+    run_cli_command(options.program(), &["image", "prune", "-f"], "image prune").await
+}
+
+/// Volumes from `docker volume ls`, with sizes from `docker system df -v` and whether any
+/// container (running or not) mounts them. A failed size or usage query is logged and
+/// leaves those fields empty rather than failing the listing.
+pub async fn list_volumes(options: &DockerOptions) -> Result<Vec<VolumeSummary>, String> {
+    let program = options.program();
+    let mut listCmd = Command::new(program);
+    listCmd.args(["volume", "ls", "--format", "{{.Name}}\t{{.Driver}}\t{{.Mountpoint}}"]);
+    let (listed, sizes, used) = tokio::join!(
+        run_with_timeout(&mut listCmd, PS_TIMEOUT),
+        collect_volume_sizes(program),
+        collect_mounted_volumes(program),
+    );
+    let output = listed?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{program} volume ls failed: {}", stderr.trim()));
+    }
+
+    let mut volumes: Vec<VolumeSummary> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let name = fields.first()?.trim();
+            if name.is_empty() {
+                return None;
+            }
+            Some(VolumeSummary {
+                name: name.to_string(),
+                driver: fields.get(1).map(|s| s.trim().to_string()).unwrap_or_default(),
+                mountpoint: fields.get(2).map(|s| s.trim().to_string()).unwrap_or_default(),
+                size_bytes: sizes.get(name).copied(),
+                in_use: used.as_ref().map(|u| u.contains(name)),
+            })
+        })
+        .collect();
+    volumes.sort_by_key(|v| std::cmp::Reverse(v.size_bytes.unwrap_or(0)));
+    Ok(volumes)
+}
+
+/// Volume sizes from the "Local Volumes space usage" table of `docker system df -v`.
+/// Podman prints the same table. Sizes of "N/A" (non-local drivers) are left out.
+async fn collect_volume_sizes(program: &str) -> HashMap<String, u64> {
+    let output = match run_with_timeout(
+        Command::new(program).args(["system", "df", "-v"]),
+        SIZED_PS_TIMEOUT,
+    )
+    .await
+    {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            warn!("{program} system df failed: {}", String::from_utf8_lossy(&o.stderr).trim());
+            return HashMap::new();
+        }
+        Err(e) => {
+            warn!("{program} system df: {e}");
+            return HashMap::new();
+        }
+    };
+    parse_volume_sizes(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_volume_sizes(stdout: &str) -> HashMap<String, u64> {
+    stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("VOLUME NAME"))
+        .skip(1)
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?;
+            let size = fields.nth(1)?;
+            (size != "N/A").then(|| (name.to_string(), parse_docker_size(size)))
+        })
+        .collect()
+}
+
+/// Names of volumes mounted by any container, or `None` if `ps` failed.
+async fn collect_mounted_volumes(program: &str) -> Option<HashSet<String>> {
+    let output = match run_with_timeout(
+        Command::new(program).args(["ps", "-a", "--no-trunc", "--format", "{{.Mounts}}"]),
+        PS_TIMEOUT,
+    )
+    .await
+    {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            warn!("{program} ps failed: {}", String::from_utf8_lossy(&o.stderr).trim());
+            return None;
+        }
+        Err(e) => {
+            warn!("{program} ps: {e}");
+            return None;
+        }
+    };
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .flat_map(|line| line.split(','))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect(),
+    )
 }
 
-async fn collect_container_list() -> Result<Vec<ContainerSummary>, String> {
-    let output = timeout(
+/// `docker volume prune -f`. Docker only removes unused anonymous volumes unless
+/// `--all` is given; named volumes are left for the user to remove deliberately.
+pub async fn prune_volumes(options: &DockerOptions) -> ContainerActionResult {
+    run_cli_command(options.program(), &["volume", "prune", "-f"], "volume prune").await
+}
+
+/// Whether a container is crash-looping, dead, or failing its healthcheck.
+pub fn is_unhealthy(container: &ContainerSummary) -> bool {
+    matches!(
+        container.status,
+        ContainerStatus::Restarting | ContainerStatus::Dead
+    ) || container.health == Some(ContainerHealth::Unhealthy)
+}
+
+/// List containers from `ps` only, without the slower stats and inspect calls.
+pub async fn collect_container_list(
+    options: &DockerOptions,
+) -> Result<Vec<ContainerSummary>, String> {
+    if let Some(socket) = options.engine_socket() {
+        match docker_api::list_containers(socket).await {
+            Ok(containers) => return Ok(containers),
+            Err(e) => warn!("Docker Engine API unavailable, falling back to the CLI: {e}"),
+        }
+    }
+    list_containers(options.program()).await
+}
+
+/// Whether CLI stderr says the daemon couldn't be reached, which a restart causes for a
+/// moment, rather than an error that retrying won't fix.
+fn is_daemon_unreachable(stderr: &str) -> bool {
+    let stderr = stderr.to_ascii_lowercase();
+    [
+        "cannot connect to the docker daemon",
+        "is the docker daemon running",
+        "cannot connect to podman",
+        "connection refused",
+    ]
+    .iter()
+    .any(|pattern| stderr.contains(pattern))
+}
+
+/// Run a read-only CLI query, trying again with a short backoff while the daemon is
+/// unreachable. All attempts share `limit`, so retries never stretch a call past it. Any
+/// other outcome, including a successful empty listing, is returned as is.
+async fn run_query(program: &str, args: &[&str], limit: Duration) -> Result<Output, String> {
+    let deadline = Instant::now() + limit;
+    let mut delay = DAEMON_RETRY_DELAY;
+    let mut attempt = 1;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let output = run_with_timeout(Command::new(program).args(args), remaining).await?;
+        if output.status.success()
+            || attempt == DAEMON_ATTEMPTS
+            || Instant::now() + delay >= deadline
+            || !is_daemon_unreachable(&String::from_utf8_lossy(&output.stderr))
+        {
+            return Ok(output);
+        }
+        debug!("{program} {} couldn't reach the daemon, retrying in {delay:?}", args[0]);
+        tokio::time::sleep(delay).await;
+        delay *= 2;
+        attempt += 1;
+    }
+}
+
+async fn list_containers(program: &str) -> Result<Vec<ContainerSummary>, String> {
+    let output = run_query(
+        program,
+        &[
+            "ps",
+            "-a",
+            "--format",
+            "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.State}}\t{{.Status}}\t{{.Ports}}\t{{.CreatedAt}}",
+        ],
         PS_TIMEOUT,
-        tokio::process::Command::new("docker")
-            .args([
-                "ps",
-                "-a",
-                "--format",
-                "{{.ID}}\t{{.Names}}\t{{.Image}}\t{{.State}}\t{{.Status}}\t{{.Ports}}\t{{.CreatedAt}}",
-            ])
-            .output(),
     )
     .await
-    .map_err(|_| "docker ps timed out".to_string())?
-    .map_err(|e| format!("failed to run docker ps: {e}"))?;
+    .map_err(|e| format!("{program} ps: {e}"))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("docker ps failed: {stderr}"));
+        return Err(format!("{program} ps failed: {stderr}"));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut containers = Vec::new();
-
     for line in stdout.lines() {
-        let line = line.trim();
-        if line.is_empty() {
+        if line.trim().is_empty() {
             continue;
         }
-
-        let fields: Vec<&str> = line.split('\t').collect();
-        if fields.len() < 7 {
-            warn!("unexpected docker ps line format: {line}");
-            continue;
+        match parse_ps_line(line) {
+            Some(container) => containers.push(container),
+            None => warn!("unexpected {program} ps line format: {line}"),
         }
+    }
 
-        let id = fields[0].trim().to_string();
-        let name = fields[1].trim().to_string();
-        let image = fields[2].trim().to_string();
-        let state = fields[3].trim();
-        let statusText = fields[4].trim().to_string();
-        let portsRaw = fields[5].trim();
-        let created = fields[6].trim().to_string();
-
-        let ports = if portsRaw.is_empty() {
-            Vec::new()
-        } else {
-            portsRaw.split(", ").map(|s| s.to_string()).collect()
-        };
+    Ok(containers)
+}
 
-        containers.push(ContainerSummary {
-            id,
-            name,
-            image,
-            status: parse_status(state),
-            state_text: statusText,
-            ports,
-            created,
-            ..Default::default()
-        });
+/// One tab-separated `ps` line: ID, name, image, state, status, ports, created. Empty
+/// fields collapse at the end of a line, so missing ones default to empty, and anything
+/// past the sixth tab stays in `created`. `None` only when there is no ID.
+fn parse_ps_line(line: &str) -> Option<ContainerSummary> {
+    let mut fields = line.splitn(7, '\t').map(str::trim);
+    let mut next = || fields.next().unwrap_or_default();
+
+    let id = next().to_string();
+    if id.is_empty() {
+        return None;
     }
+    let name = next().to_string();
+    let image = next().to_string();
+    let state = next();
+    let statusText = next().to_string();
+    let portsRaw = next();
+    let created = next().to_string();
 
-    Ok(containers)
+    let ports = if portsRaw.is_empty() {
+        Vec::new()
+    } else {
+        portsRaw.split(", ").map(|s| s.to_string()).collect()
+    };
+
+    let (exitCode, since) = parse_state_text(&statusText);
+    Some(ContainerSummary {
+        id,
+        name,
+        image,
+        status: parse_status(state),
+        health: health_from_status_text(&statusText),
+        exit_code: exitCode,
+        since,
+        state_text: statusText,
+        ports,
+        created,
+        ..Default::default()
+    })
 }
 
-async fn collect_stats() -> Result<HashMap<String, StatsData>, String> {
-    let output = timeout(
-        STATS_TIMEOUT,
-        tokio::process::Command::new("docker")
-            .args([
-                "stats",
-                "--no-stream",
-                "--format",
-                "{{.Name}}\t{{.CPUPerc}}\t{{.MemUsage}}\t{{.NetIO}}",
-            ])
-            .output(),
+/// Run a separate `ps --size` query, keyed by container ID.
+/// Kept apart from the main listing so the expensive size walk is opt-in.
+async fn collect_sizes(program: &str) -> Result<HashMap<String, (u64, u64)>, String> {
+    let output = run_with_timeout(
+        Command::new(program).args(["ps", "-a", "--size", "--format", "{{.ID}}\t{{.Size}}"]),
+        SIZED_PS_TIMEOUT,
     )
     .await
-    .map_err(|_| "docker stats timed out".to_string())?
-    .map_err(|e| format!("failed to run docker stats: {e}"))?;
+    .map_err(|e| format!("{program} ps --size: {e}"))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("docker stats failed: {stderr}"));
+        return Err(format!("{program} ps --size failed: {stderr}"));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut map = HashMap::new();
 
+    for line in stdout.lines() {
+        let Some((id, size)) = line.trim().split_once('\t') else {
+            continue;
+        };
+        map.insert(id.trim().to_string(), parse_size_column(size));
+    }
+
+    Ok(map)
+}
+
+async fn collect_cli_stats(program: &str) -> Result<HashMap<String, ContainerStats>, String> {
+    let output = run_query(
+        program,
+        &[
+            "stats",
+            "--no-stream",
+            "--format",
+            "{{.ID}}\t{{.CPUPerc}}\t{{.MemUsage}}\t{{.NetIO}}",
+        ],
+        STATS_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("{program} stats: {e}"))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{program} stats failed: {stderr}"));
+    }
+
+    Ok(parse_stats_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `stats --no-stream` lines into a map keyed by the (short) container ID.
+fn parse_stats_output(stdout: &str) -> HashMap<String, ContainerStats> {
+    let mut map = HashMap::new();
+
     for line in stdout.lines() {
         let line = line.trim();
         if line.is_empty() {
@@ -207,7 +761,7 @@ async fn collect_stats() -> Result<HashMap<String, StatsData>, String> {
             continue;
         }
 
-        let name = fields[0].trim().to_string();
+        let id = fields[0].trim().to_string();
 
         // CPU%: strip trailing "%"
         let cpuStr = fields[1].trim().trim_end_matches('%');
@@ -228,8 +782,9 @@ async fn collect_stats() -> Result<HashMap<String, StatsData>, String> {
         };
 
         map.insert(
-            name,
-            StatsData {
+            id.clone(),
+            ContainerStats {
+                id,
                 cpu_pct: cpuPct,
                 memory_usage_bytes: memUsage,
                 memory_limit_bytes: memLimit,
@@ -239,10 +794,14 @@ async fn collect_stats() -> Result<HashMap<String, StatsData>, String> {
         );
     }
 
-    Ok(map)
+    map
 }
 
-async fn collect_inspect(ids: &[String]) -> HashMap<String, InspectData> {
+async fn collect_inspect(
+    program: &str,
+    runtimeTemplate: &str,
+    ids: &[String],
+) -> HashMap<String, InspectData> {
     if ids.is_empty() {
         return HashMap::new();
     }
@@ -250,32 +809,27 @@ async fn collect_inspect(ids: &[String]) -> HashMap<String, InspectData> {
     let mut args = vec![
         "inspect".to_string(),
         "--format".to_string(),
-        "{{.Id}}\t{{.HostConfig.Runtime}}\t{{.HostConfig.RestartPolicy.Name}}\t{{json .Mounts}}".to_string(),
+        format!(
+            "{{{{.Id}}}}\t{runtimeTemplate}\t{{{{.HostConfig.RestartPolicy.Name}}}}\t\
+             {{{{if .State.Health}}}}{{{{.State.Health.Status}}}}{{{{end}}}}\t\
+             {{{{.HostConfig.NanoCpus}}}}\t{{{{.HostConfig.Memory}}}}\t{{{{json .Config.Env}}}}\t\
+             {{{{json .Mounts}}}}"
+        ),
     ];
     args.extend(ids.iter().cloned());
 
-    let output = match timeout(
-        INSPECT_TIMEOUT,
-        tokio::process::Command::new("docker")
-            .args(&args)
-            .output(),
-    )
-    .await
+    let output = match run_with_timeout(Command::new(program).args(&args), INSPECT_TIMEOUT).await
     {
-        Ok(Ok(o)) => o,
-        Ok(Err(e)) => {
-            warn!("docker inspect failed: {e}");
-            return HashMap::new();
-        }
-        Err(_) => {
-            warn!("docker inspect timed out");
+        Ok(o) => o,
+        Err(e) => {
+            warn!("{program} inspect: {e}");
             return HashMap::new();
         }
     };
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        warn!("docker inspect failed: {stderr}");
+        warn!("{program} inspect failed: {stderr}");
         return HashMap::new();
     }
 
@@ -287,26 +841,53 @@ async fn collect_inspect(ids: &[String]) -> HashMap<String, InspectData> {
         if line.is_empty() {
             continue;
         }
-        let fields: Vec<&str> = line.splitn(4, '\t').collect();
-        if fields.len() < 4 {
+        // JSON escapes tabs, so only the template's own separators split fields
+        let fields: Vec<&str> = line.splitn(8, '\t').collect();
+        if fields.len() < 8 {
             continue;
         }
 
         let fullId = fields[0].trim().to_string();
         let runtime = fields[1].trim().to_string();
         let restartPolicy = fields[2].trim().to_string();
-        let mounts = parse_mounts_json(fields[3].trim());
+        let health = parse_health(fields[3]);
+        let (cpuLimit, memLimit) = parse_limits(
+            fields[4].trim().parse().unwrap_or(0),
+            fields[5].trim().parse().unwrap_or(0),
+        );
+        // `null` for images without any environment
+        let env = serde_json::from_str::<Option<Vec<String>>>(fields[6].trim())
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| redact_env(entry))
+            .collect();
+        let mounts = parse_mounts_json(fields[7].trim());
 
         // Match on short ID prefix since docker ps returns short IDs
         if let Some(originalId) = ids.iter().find(|i| fullId.starts_with(i.as_str()) || i.starts_with(&fullId)) {
-            map.insert(originalId.clone(), InspectData { runtime, restart_policy: restartPolicy, mounts });
+            map.insert(
+                originalId.clone(),
+                InspectData {
+                    runtime,
+                    restart_policy: restartPolicy,
+                    health,
+                    mounts,
+                    cpu_limit: cpuLimit,
+                    mem_limit_bytes: memLimit,
+                    env,
+                },
+            );
         }
     }
 
     map
 }
 
-fn parse_mounts_json(raw: &str) -> Vec<String> {
+/// `{{json .Mounts}}` from inspect as `source:destination` pairs. Entries missing either
+/// field are skipped, and anything that isn't a JSON array gives no mounts.
+pub(crate) fn parse_mounts_json(raw: &str) -> Vec<String> {
     // Parse as JSON array of objects with "Source" and "Destination" fields
     let parsed: Result<Vec<serde_json::Value>, _> = serde_json::from_str(raw);
     match parsed {
@@ -322,41 +903,314 @@ fn parse_mounts_json(raw: &str) -> Vec<String> {
     }
 }
 
-pub async fn execute_action(container_id: &str, action: &str) -> ContainerActionResult {
-    let cmd = match action {
-        "start" | "stop" | "restart" => action,
-        _ => {
-            return ContainerActionResult {
-                success: false,
-                message: format!("unknown action: {action}"),
-            };
-        }
-    };
+pub async fn execute_action(
+    options: &DockerOptions,
+    container_id: &str,
+    action: &str,
+) -> ContainerActionResult {
+    if !SUPPORTED_ACTIONS.contains(&action) {
+        return ContainerActionResult {
+            success: false,
+            message: format!("unknown action: {action}"),
+        };
+    }
+    // The ID ends up in an Engine API path or after CLI flags
+    if !crate::logs::is_valid_container_ref(container_id) {
+        return ContainerActionResult {
+            success: false,
+            message: format!("invalid container: {container_id}"),
+        };
+    }
 
-    let output = match tokio::process::Command::new("docker")
-        .args([cmd, container_id])
-        .output()
-        .await
-    {
+    // Actions aren't retried on the CLI: a failed API call may already have taken effect
+    if let Some(socket) = options.engine_socket() {
+        return docker_api::run_action(socket, container_id, action).await;
+    }
+    match options.backend() {
+        BackendKind::Podman => podman_backend(options).run_action(container_id, action).await,
+        _ => Docker.run_action(container_id, action).await,
+    }
+}
+
+/// CLI arguments for an action. `remove` force-removes, so running containers go too.
+fn cli_action_args(action: &str) -> &[&str] {
+    match action {
+        "remove" => &["rm", "-f"],
+        "start" => &["start"],
+        "stop" => &["stop"],
+        "restart" => &["restart"],
+        "pause" => &["pause"],
+        "unpause" => &["unpause"],
+        "kill" => &["kill"],
+        _ => &[],
+    }
+}
+
+/// Run `<program> <action> <target>` and report the outcome.
+pub(crate) async fn run_cli_action(
+    program: &str,
+    target: &str,
+    action: &str,
+) -> ContainerActionResult {
+    let args = cli_action_args(action);
+    if args.is_empty() {
+        return ContainerActionResult {
+            success: false,
+            message: format!("unknown action: {action}"),
+        };
+    }
+
+    let mut args = args.to_vec();
+    args.push(target);
+    let mut result = run_cli_command(program, &args, action).await;
+    if result.success {
+        result.message = format!("{program} {action} {target} succeeded");
+    }
+    result
+}
+
+/// Run `<program> <args>` and report the outcome. `label` names the operation in messages;
+/// on success the message is the command's output, since prune reports what it reclaimed.
+pub(crate) async fn run_cli_command(program: &str, args: &[&str], label: &str) -> ContainerActionResult {
+    let output = match run_with_timeout(Command::new(program).args(args), ACTION_TIMEOUT).await {
         Ok(o) => o,
         Err(e) => {
             return ContainerActionResult {
                 success: false,
-                message: format!("failed to run docker {cmd}: {e}"),
+                message: format!("{program} {label}: {e}"),
             };
         }
     };
 
     if output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
         ContainerActionResult {
             success: true,
-            message: format!("docker {cmd} {container_id} succeeded"),
+            message: format!("{program} {label} succeeded: {}", stdout.trim()),
         }
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
         ContainerActionResult {
             success: false,
-            message: format!("docker {cmd} failed: {stderr}"),
+            message: format!("{program} {label} failed: {stderr}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn queries_retry_only_while_the_daemon_is_unreachable() {
+        let dir = std::env::temp_dir().join(format!("spark-retry-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let calls = dir.join("calls");
+        // Fails like a restarting daemon until the third call
+        let flaky = format!(
+            "echo x >> {0}; [ $(wc -l < {0}) -ge 3 ] && exit 0; \
+             echo 'Cannot connect to the Docker daemon at unix:///var/run/docker.sock' >&2; exit 1",
+            calls.display()
+        );
+        let output = run_query("sh", &["-c", &flaky], Duration::from_secs(10)).await.unwrap();
+        let flakyCalls = std::fs::read_to_string(&calls).unwrap().lines().count();
+
+        std::fs::remove_file(&calls).unwrap();
+        let broken = format!("echo x >> {}; echo 'unknown flag: --bogus' >&2; exit 1", calls.display());
+        let failed = run_query("sh", &["-c", &broken], Duration::from_secs(10)).await.unwrap();
+        let brokenCalls = std::fs::read_to_string(&calls).unwrap().lines().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(flakyCalls, 3);
+        assert!(!failed.status.success());
+        assert_eq!(brokenCalls, 1);
+    }
+
+    #[tokio::test]
+    async fn actions_reject_ids_that_could_change_the_request() {
+        let options = DockerOptions::default();
+        for id in ["../images/alpine", "abc?force=true", "-f", "web/../../info", ""] {
+            let result = execute_action(&options, id, "remove").await;
+            assert!(!result.success, "{id} was accepted");
+            assert!(result.message.starts_with("invalid container"), "{}", result.message);
+        }
+    }
+
+    fn container(id: &str, name: &str) -> ContainerSummary {
+        ContainerSummary {
+            id: id.into(),
+            name: name.into(),
+            status: ContainerStatus::Running,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stats_follow_container_id_when_names_collide() {
+        let stats = parse_stats_output(
+            "aaaaaaaaaaaa\t12.50%\t1GiB / 8GiB\t1kB / 2kB\n\
+             bbbbbbbbbbbb\t0.25%\t10MiB / 8GiB\t3kB / 4kB\n",
+        );
+        let containers = vec![container("aaaaaaaaaaaa", "web"), container("bbbbbbbbbbbb", "web")];
+
+        let merged = merge_details(containers, &stats, &HashMap::new(), &HashMap::new());
+
+        assert_eq!(merged[0].cpu_pct, 12.5);
+        assert_eq!(merged[0].memory_usage_bytes, 1_073_741_824);
+        assert_eq!(merged[1].cpu_pct, 0.25);
+        assert_eq!(merged[1].memory_usage_bytes, 10_485_760);
+        assert_eq!(merged[1].net_tx_bytes, 4_000);
+    }
+
+    #[test]
+    fn containers_without_healthcheck_have_no_health() {
+        assert_eq!(parse_health("healthy"), Some(ContainerHealth::Healthy));
+        assert_eq!(parse_health("starting\n"), Some(ContainerHealth::Starting));
+        assert_eq!(parse_health(""), None);
+        assert_eq!(parse_health("none"), None);
+        assert_eq!(
+            health_from_status_text("Up 3 minutes (unhealthy)"),
+            Some(ContainerHealth::Unhealthy)
+        );
+        assert_eq!(health_from_status_text("Up 3 minutes"), None);
+    }
+
+    #[test]
+    fn state_text_yields_exit_code_and_since() {
+        let parsed = parse_state_text;
+        assert_eq!(parsed("Up 3 hours"), (None, Some("3 hours".into())));
+        assert_eq!(parsed("Up About a minute (healthy)"), (None, Some("About a minute".into())));
+        assert_eq!(parsed("Up 2 days (Paused)"), (None, Some("2 days".into())));
+        assert_eq!(parsed("Up 5 seconds ago"), (None, Some("5 seconds".into())));
+        assert_eq!(parsed("Exited (0) 2 days ago"), (Some(0), Some("2 days".into())));
+        assert_eq!(parsed("Exited (137) 5 seconds ago"), (Some(137), Some("5 seconds".into())));
+        assert_eq!(parsed("Restarting (1) Less than a second ago"), (Some(1), Some("Less than a second".into())));
+        assert_eq!(parsed("Exited (-1)"), (Some(-1), None));
+        assert_eq!(parsed("Created"), (None, None));
+        assert_eq!(parsed("Exited (abc) 1 hour ago"), (None, None));
+        assert_eq!(parsed(""), (None, None));
+    }
+
+    #[test]
+    fn restarting_containers_are_not_labelled_exited() {
+        let line = "3f2a9c1b\tworker\tapp:latest\trestarting\tRestarting (1) 3 seconds ago\t\t2024-01-01";
+        let container = parse_ps_line(line).unwrap();
+        assert_eq!(container.status, ContainerStatus::Restarting);
+        assert_eq!(container.exit_code, Some(1));
+        assert_eq!(container.since.as_deref(), Some("3 seconds"));
+        assert_eq!(container.exit_label().as_deref(), Some("Restarting (1)"));
+
+        let line = "3f2a9c1b\tworker\tapp:latest\texited\tExited (137) 2 days ago\t\t2024-01-01";
+        let container = parse_ps_line(line).unwrap();
+        assert_eq!(container.exit_label().as_deref(), Some("Exited (137)"));
+    }
+
+    #[test]
+    fn tolerates_short_and_odd_ps_lines() {
+        // Empty state and ports, with a tab inside the last field
+        let line = "3f2a9c1b\tweb\tnginx:latest\t\tCreated\t\t2024-01-01 10:00:00\t+0000 UTC";
+        let container = parse_ps_line(line).unwrap();
+        assert_eq!(container.name, "web");
+        assert_eq!(container.status, ContainerStatus::Unknown);
+        assert_eq!(container.state_text, "Created");
+        assert!(container.ports.is_empty());
+        assert_eq!(container.created, "2024-01-01 10:00:00\t+0000 UTC");
+
+        // Trailing empty fields missing entirely
+        let container = parse_ps_line("3f2a9c1b\tweb\tnginx:latest\trunning").unwrap();
+        assert_eq!(container.image, "nginx:latest");
+        assert_eq!(container.status, ContainerStatus::Running);
+        assert_eq!(container.created, "");
+
+        assert!(parse_ps_line("\tweb\tnginx").is_none());
+    }
+
+    #[test]
+    fn redacts_credential_env_values() {
+        assert_eq!(redact_env("HF_TOKEN=hf_abc123"), "HF_TOKEN=<redacted>");
+        assert_eq!(redact_env("db_password=hunter2"), "db_password=<redacted>");
+        assert_eq!(redact_env("OPENAI_API_KEY=sk-1"), "OPENAI_API_KEY=<redacted>");
+        assert_eq!(redact_env("KEYBOARD=us"), "KEYBOARD=us");
+        assert_eq!(redact_env("PATH=/usr/bin"), "PATH=/usr/bin");
+        assert_eq!(parse_limits(1_500_000_000, 0), (Some(1.5), None));
+    }
+
+    #[test]
+    fn parses_docker_sizes() {
+        let cases = [
+            ("126B", 126),
+            ("15.6kB", 15_600),
+            ("3.5MiB", 3_670_016),
+            ("1.5GB", 1_500_000_000),
+            ("1.5 GB", 1_500_000_000),
+            (" 1.5 GB ", 1_500_000_000),
+            ("1.5gb", 1_500_000_000),
+            ("2gib", 2_147_483_648),
+            ("1TiB", 1_099_511_627_776),
+            ("0B", 0),
+            ("42", 42),
+            ("[N/A]", 0),
+            ("N/A", 0),
+            ("--", 0),
+            ("", 0),
+            ("1.5 parsecs", 0),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_docker_size(input), expected, "{input:?}");
         }
     }
+
+    #[test]
+    fn parses_container_states() {
+        let cases = [
+            ("running", ContainerStatus::Running),
+            ("Running\n", ContainerStatus::Running),
+            ("exited", ContainerStatus::Stopped),
+            ("EXITED", ContainerStatus::Stopped),
+            ("restarting", ContainerStatus::Restarting),
+            ("paused", ContainerStatus::Paused),
+            ("dead", ContainerStatus::Dead),
+            ("created", ContainerStatus::Unknown),
+            ("", ContainerStatus::Unknown),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_status(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn parses_mounts_json() {
+        let cases: [(&str, &[&str]); 7] = [
+            (
+                r#"[{"Source":"/srv/data","Destination":"/data"},{"Source":"/tmp","Destination":"/tmp"}]"#,
+                &["/srv/data:/data", "/tmp:/tmp"],
+            ),
+            (r#"[{"Source":"/srv/data"},{"Destination":"/only-dest"}]"#, &[]),
+            (r#"[{"Source":1,"Destination":"/data"}]"#, &[]),
+            ("[]", &[]),
+            ("null", &[]),
+            ("", &[]),
+            (r#"[{"Source":"/srv""#, &[]),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse_mounts_json(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn volume_sizes_come_from_df_table() {
+        let sizes = parse_volume_sizes(
+            "Images space usage:\n\n\
+             REPOSITORY   TAG   IMAGE ID   CREATED   SIZE   SHARED SIZE   UNIQUE SIZE   CONTAINERS\n\n\
+             Local Volumes space usage:\n\n\
+             VOLUME NAME   LINKS   SIZE\n\
+             pgdata        1       52.4MB\n\
+             nfs-share     0       N/A\n\n\
+             Build cache usage: 0B\n",
+        );
+        assert_eq!(sizes.get("pgdata"), Some(&52_400_000));
+        assert!(!sizes.contains_key("nfs-share"));
+        assert_eq!(sizes.len(), 1);
+    }
 }
@@ -0,0 +1,536 @@
+//! Docker Engine API over the daemon's Unix socket.
+//!
+//! One listing request plus concurrent per-container inspect/stats requests, instead of
+//! spawning the CLI for each. Used whenever the socket exists; `docker.rs` falls back
+//! to the CLI otherwise.
+
+use futures_util::future::join_all;
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use spark_types::{ContainerActionResult, ContainerStats, ContainerStatus, ContainerSummary};
+use std::collections::HashMap;
+use tokio::net::UnixStream;
+use tokio::time::{timeout, Duration};
+
+use crate::docker::{
+    health_from_status_text, parse_health, parse_limits, parse_state_text, parse_status, redact_env,
+    ACTION_TIMEOUT,
+};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+// Stats without one-shot wait a full sampling cycle so the CPU delta is meaningful
+const STATS_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Send one request over a fresh connection and return the status and body.
+async fn request(
+    socket: &str,
+    method: Method,
+    path: &str,
+    limit: Duration,
+) -> Result<(StatusCode, Bytes), String> {
+    let exchange = async {
+        let stream = UnixStream::connect(socket)
+            .await
+            .map_err(|e| format!("failed to connect to {socket}: {e}"))?;
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| format!("handshake with {socket} failed: {e}"))?;
+        tokio::spawn(connection);
+
+        let request = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(hyper::header::HOST, "docker")
+            .body(Empty::<Bytes>::new())
+            .map_err(|e| format!("invalid request {path}: {e}"))?;
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(|e| format!("{path}: {e}"))?;
+        let status = response.status();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| format!("{path}: {e}"))?
+            .to_bytes();
+        Ok((status, body))
+    };
+
+    timeout(limit, exchange)
+        .await
+        .map_err(|_| format!("{path} timed out after {}s", limit.as_secs()))?
+}
+
+async fn get_json<T: DeserializeOwned>(socket: &str, path: &str, limit: Duration) -> Result<T, String> {
+    let (status, body) = request(socket, Method::GET, path, limit).await?;
+    if !status.is_success() {
+        return Err(format!("{path} returned {status}: {}", error_message(&body)));
+    }
+    serde_json::from_slice(&body).map_err(|e| format!("{path}: invalid JSON: {e}"))
+}
+
+/// The daemon's `{"message": "..."}` error body, or the raw text.
+fn error_message(body: &[u8]) -> String {
+    #[derive(Deserialize)]
+    struct ApiError {
+        message: String,
+    }
+    serde_json::from_slice::<ApiError>(body)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| String::from_utf8_lossy(body).trim().to_string())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListedContainer {
+    id: String,
+    #[serde(default)]
+    names: Vec<String>,
+    image: String,
+    state: String,
+    status: String,
+    #[serde(default)]
+    ports: Vec<ListedPort>,
+    #[serde(default)]
+    mounts: Vec<ListedMount>,
+    size_rw: Option<u64>,
+    size_root_fs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListedPort {
+    #[serde(rename = "IP")]
+    ip: Option<String>,
+    private_port: u16,
+    public_port: Option<u16>,
+    #[serde(rename = "Type")]
+    protocol: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListedMount {
+    #[serde(default)]
+    source: String,
+    destination: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Inspected {
+    created: String,
+    host_config: InspectedHostConfig,
+    state: Option<InspectedState>,
+    config: Option<InspectedConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedConfig {
+    /// `null` for images without any environment.
+    env: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedState {
+    /// Absent for containers without a healthcheck.
+    health: Option<InspectedHealth>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedHealth {
+    #[serde(default)]
+    status: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedHostConfig {
+    #[serde(default)]
+    runtime: String,
+    restart_policy: Option<InspectedRestartPolicy>,
+    #[serde(default)]
+    nano_cpus: i64,
+    #[serde(default)]
+    memory: i64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InspectedRestartPolicy {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Deserialize, Default)]
+struct Stats {
+    #[serde(default)]
+    cpu_stats: CpuStats,
+    #[serde(default)]
+    precpu_stats: CpuStats,
+    #[serde(default)]
+    memory_stats: MemoryStats,
+    #[serde(default)]
+    networks: HashMap<String, NetworkStats>,
+}
+
+#[derive(Deserialize, Default)]
+struct CpuStats {
+    #[serde(default)]
+    cpu_usage: CpuUsage,
+    system_cpu_usage: Option<u64>,
+    online_cpus: Option<u32>,
+}
+
+#[derive(Deserialize, Default)]
+struct CpuUsage {
+    #[serde(default)]
+    total_usage: u64,
+}
+
+#[derive(Deserialize, Default)]
+struct MemoryStats {
+    #[serde(default)]
+    usage: u64,
+    #[serde(default)]
+    limit: u64,
+    #[serde(default)]
+    stats: HashMap<String, u64>,
+}
+
+#[derive(Deserialize, Default)]
+struct NetworkStats {
+    rx_bytes: u64,
+    tx_bytes: u64,
+}
+
+/// `0.0.0.0:8080->80/tcp`, matching the CLI's `{{.Ports}}` entries.
+fn format_port(port: &ListedPort) -> String {
+    match port.public_port {
+        Some(public) => format!(
+            "{}:{public}->{}/{}",
+            port.ip.as_deref().unwrap_or("0.0.0.0"),
+            port.private_port,
+            port.protocol
+        ),
+        None => format!("{}/{}", port.private_port, port.protocol),
+    }
+}
+
+/// RFC 3339 `2024-05-01T12:34:56.789Z` as `2024-05-01 12:34:56 UTC`, close to the CLI's format.
+fn format_created(created: &str) -> String {
+    match created.get(..19) {
+        Some(seconds) => format!("{} UTC", seconds.replacen('T', " ", 1)),
+        None => created.to_string(),
+    }
+}
+
+fn summarize(listed: ListedContainer) -> ContainerSummary {
+    let name = listed
+        .names
+        .first()
+        .map(|n| n.trim_start_matches('/').to_string())
+        .unwrap_or_default();
+    let (exitCode, since) = parse_state_text(&listed.status);
+    ContainerSummary {
+        // The CLI shows 12-character IDs; keep the same so IDs look alike either way
+        id: listed.id.chars().take(12).collect(),
+        name,
+        image: listed.image,
+        status: parse_status(&listed.state),
+        health: health_from_status_text(&listed.status),
+        exit_code: exitCode,
+        since,
+        state_text: listed.status,
+        ports: listed.ports.iter().map(format_port).collect(),
+        mounts: listed
+            .mounts
+            .iter()
+            .map(|m| format!("{}:{}", m.source, m.destination))
+            .collect(),
+        rw_size_bytes: listed.size_rw,
+        root_fs_size_bytes: listed.size_root_fs,
+        ..Default::default()
+    }
+}
+
+/// Containers from `/containers/json` only, without inspect or stats.
+pub(crate) async fn list_containers(socket: &str) -> Result<Vec<ContainerSummary>, String> {
+    let listed: Vec<ListedContainer> =
+        get_json(socket, "/containers/json?all=1", REQUEST_TIMEOUT).await?;
+    Ok(listed.into_iter().map(summarize).collect())
+}
+
+pub(crate) async fn collect(
+    socket: &str,
+    includeSize: bool,
+    withStats: bool,
+) -> Result<Vec<ContainerSummary>, String> {
+    let path = if includeSize {
+        "/containers/json?all=1&size=1"
+    } else {
+        "/containers/json?all=1"
+    };
+    let listed: Vec<ListedContainer> = get_json(socket, path, REQUEST_TIMEOUT).await?;
+    let fullIds: Vec<String> = listed.iter().map(|c| c.id.clone()).collect();
+    let mut containers: Vec<ContainerSummary> = listed.into_iter().map(summarize).collect();
+
+    let inspects = join_all(fullIds.iter().map(|id| async move {
+        get_json::<Inspected>(socket, &format!("/containers/{id}/json"), REQUEST_TIMEOUT).await
+    }));
+    let stats = join_all(fullIds.iter().zip(&containers).map(|(id, c)| async move {
+        if !withStats || c.status != ContainerStatus::Running {
+            return None;
+        }
+        get_stats(socket, id, &c.id, &c.name).await
+    }));
+    let (inspects, stats) = tokio::join!(inspects, stats);
+
+    for ((container, inspect), stats) in containers.iter_mut().zip(inspects).zip(stats) {
+        match inspect {
+            Ok(inspect) => {
+                container.created = format_created(&inspect.created);
+                (container.cpu_limit, container.mem_limit_bytes) =
+                    parse_limits(inspect.host_config.nano_cpus, inspect.host_config.memory);
+                container.env = inspect
+                    .config
+                    .and_then(|c| c.env)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|entry| redact_env(entry))
+                    .collect();
+                container.runtime = inspect.host_config.runtime;
+                container.restart_policy = inspect
+                    .host_config
+                    .restart_policy
+                    .map(|p| p.name)
+                    .unwrap_or_default();
+                container.health = inspect
+                    .state
+                    .and_then(|s| s.health)
+                    .and_then(|h| parse_health(&h.status));
+            }
+            Err(e) => tracing::warn!("inspect {}: {e}", container.name),
+        }
+        if let Some(stats) = stats {
+            container.apply_stats(&stats);
+        }
+    }
+
+    Ok(containers)
+}
+
+/// Stats for every running container, sampled concurrently.
+pub(crate) async fn collect_stats(socket: &str) -> Result<Vec<ContainerStats>, String> {
+    let listed: Vec<ListedContainer> = get_json(socket, "/containers/json", REQUEST_TIMEOUT).await?;
+    let stats = join_all(listed.into_iter().map(|c| async move {
+        let fullId = c.id.clone();
+        let summary = summarize(c);
+        get_stats(socket, &fullId, &summary.id, &summary.name).await
+    }))
+    .await;
+    Ok(stats.into_iter().flatten().collect())
+}
+
+/// One stats sample for `fullId`, reported under the short `id`. Failures are logged
+/// and skipped, since one wedged container shouldn't hide everyone else's usage.
+async fn get_stats(socket: &str, fullId: &str, id: &str, name: &str) -> Option<ContainerStats> {
+    get_json::<Stats>(socket, &format!("/containers/{fullId}/stats?stream=false"), STATS_TIMEOUT)
+        .await
+        .map(|stats| to_container_stats(id, &stats))
+        .map_err(|e| tracing::warn!("stats for {name}: {e}"))
+        .ok()
+}
+
+/// Same arithmetic as `docker stats`.
+fn to_container_stats(id: &str, stats: &Stats) -> ContainerStats {
+    let mut container = ContainerStats {
+        id: id.to_string(),
+        ..Default::default()
+    };
+    let cpuDelta = stats
+        .cpu_stats
+        .cpu_usage
+        .total_usage
+        .saturating_sub(stats.precpu_stats.cpu_usage.total_usage) as f64;
+    let systemDelta = stats
+        .cpu_stats
+        .system_cpu_usage
+        .unwrap_or(0)
+        .saturating_sub(stats.precpu_stats.system_cpu_usage.unwrap_or(0)) as f64;
+    let onlineCpus = stats.cpu_stats.online_cpus.unwrap_or(1) as f64;
+    if systemDelta > 0.0 {
+        container.cpu_pct = cpuDelta / systemDelta * onlineCpus * 100.0;
+    }
+
+    // Page cache is reclaimable, so the CLI leaves it out: cgroup v2 reports
+    // `inactive_file`, v1 `total_inactive_file`
+    let memory = &stats.memory_stats;
+    let cache = memory
+        .stats
+        .get("inactive_file")
+        .or_else(|| memory.stats.get("total_inactive_file"))
+        .copied()
+        .unwrap_or(0);
+    container.memory_usage_bytes = memory.usage.saturating_sub(cache);
+    container.memory_limit_bytes = memory.limit;
+
+    container.net_rx_bytes = stats.networks.values().map(|n| n.rx_bytes).sum();
+    container.net_tx_bytes = stats.networks.values().map(|n| n.tx_bytes).sum();
+    container
+}
+
+/// `POST /containers/{id}/{action}` for an action already checked against `SUPPORTED_ACTIONS`.
+/// `remove` is `DELETE /containers/{id}?force=true`, matching `docker rm -f`.
+pub(crate) async fn run_action(socket: &str, containerId: &str, action: &str) -> ContainerActionResult {
+    let (method, path) = match action {
+        "remove" => (Method::DELETE, format!("/containers/{containerId}?force=true")),
+        _ => (Method::POST, format!("/containers/{containerId}/{action}")),
+    };
+    match request(socket, method, &path, ACTION_TIMEOUT).await {
+        // 304 means it was already in the requested state
+        Ok((status, _)) if status.is_success() || status == StatusCode::NOT_MODIFIED => {
+            ContainerActionResult {
+                success: true,
+                message: format!("docker {action} {containerId} succeeded"),
+            }
+        }
+        Ok((_, body)) => ContainerActionResult {
+            success: false,
+            message: format!("docker {action} failed: {}", error_message(&body)),
+        },
+        Err(e) => ContainerActionResult {
+            success: false,
+            message: format!("docker {action}: {e}"),
+        },
+    }
+}
+
+/// `GET /containers/{id}/logs` for the last `tail` lines of stdout and stderr, raw.
+/// Containers without a TTY come back multiplexed; see [`demux_log_stream`].
+pub(crate) async fn logs(socket: &str, containerId: &str, tail: usize) -> Result<Vec<u8>, String> {
+    let path = format!("/containers/{containerId}/logs?stdout=1&stderr=1&timestamps=1&tail={tail}");
+    let (status, body) = request(socket, Method::GET, &path, REQUEST_TIMEOUT).await?;
+    if !status.is_success() {
+        return Err(format!("docker logs failed: {}", error_message(&body)));
+    }
+    Ok(demux_log_stream(&body))
+}
+
+/// Strip the 8-byte frame headers (stream type, 3 zero bytes, big-endian length) the
+/// daemon puts in front of each chunk when the container has no TTY. TTY output has no
+/// framing and is returned unchanged.
+fn demux_log_stream(body: &[u8]) -> Vec<u8> {
+    let isFrame = |b: &[u8]| b.len() >= 8 && b[0] <= 2 && b[1..4] == [0, 0, 0];
+    if !isFrame(body) {
+        return body.to_vec();
+    }
+
+    let mut out = Vec::with_capacity(body.len());
+    let mut rest = body;
+    while isFrame(rest) {
+        let len = u32::from_be_bytes([rest[4], rest[5], rest[6], rest[7]]) as usize;
+        let end = (8 + len).min(rest.len());
+        out.extend_from_slice(&rest[8..end]);
+        rest = &rest[end..];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(stream: u8, payload: &[u8]) -> Vec<u8> {
+        let mut frame = vec![stream, 0, 0, 0];
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    #[test]
+    fn demuxes_stdout_and_stderr_frames_in_order() {
+        let mut body = frame(1, b"out one\n");
+        body.extend(frame(2, b"err one\n"));
+        body.extend(frame(1, b"out two\n"));
+        assert_eq!(demux_log_stream(&body), b"out one\nerr one\nout two\n");
+
+        // TTY output has no headers and passes through untouched
+        assert_eq!(demux_log_stream(b"plain tty output\n"), b"plain tty output\n");
+        assert_eq!(demux_log_stream(b""), b"");
+    }
+
+    #[test]
+    fn keeps_what_arrived_of_a_truncated_frame() {
+        let mut body = frame(1, b"complete\n");
+        let mut cut = frame(2, b"cut short\n");
+        cut.truncate(8 + 3);
+        body.extend(cut);
+        assert_eq!(demux_log_stream(&body), b"complete\ncut");
+
+        // A header with no payload yet ends the stream cleanly
+        let mut body = frame(1, b"done\n");
+        body.extend_from_slice(&[1, 0, 0]);
+        assert_eq!(demux_log_stream(&body), b"done\n");
+    }
+
+    #[test]
+    fn stats_match_docker_stats_arithmetic() {
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "cpu_stats": {"cpu_usage": {"total_usage": 300000000}, "system_cpu_usage": 2000000000, "online_cpus": 4},
+                "precpu_stats": {"cpu_usage": {"total_usage": 100000000}, "system_cpu_usage": 1000000000},
+                "memory_stats": {"usage": 524288000, "limit": 8589934592, "stats": {"inactive_file": 104857600}},
+                "networks": {"eth0": {"rx_bytes": 1000, "tx_bytes": 2000}, "eth1": {"rx_bytes": 10, "tx_bytes": 20}}
+            }"#,
+        )
+        .unwrap();
+        let container = to_container_stats("abc", &stats);
+        // 0.2s of CPU over 1s of system time on 4 CPUs
+        assert!((container.cpu_pct - 80.0).abs() < 1e-9);
+        assert_eq!(container.memory_usage_bytes, 419_430_400);
+        assert_eq!(container.memory_limit_bytes, 8_589_934_592);
+        assert_eq!(container.net_rx_bytes, 1010);
+        assert_eq!(container.net_tx_bytes, 2020);
+
+        // cgroup v1 names the cache differently; a first sample has no previous reading
+        let stats: Stats = serde_json::from_str(
+            r#"{
+                "cpu_stats": {"cpu_usage": {"total_usage": 5}},
+                "memory_stats": {"usage": 1000, "limit": 2000, "stats": {"total_inactive_file": 400}}
+            }"#,
+        )
+        .unwrap();
+        let container = to_container_stats("abc", &stats);
+        assert_eq!(container.cpu_pct, 0.0);
+        assert_eq!(container.memory_usage_bytes, 600);
+    }
+
+    #[test]
+    fn formats_ports_like_the_cli() {
+        let port = |ip: Option<&str>, public: Option<u16>| ListedPort {
+            ip: ip.map(Into::into),
+            private_port: 80,
+            public_port: public,
+            protocol: "tcp".into(),
+        };
+        assert_eq!(format_port(&port(Some("127.0.0.1"), Some(8080))), "127.0.0.1:8080->80/tcp");
+        assert_eq!(format_port(&port(None, Some(8080))), "0.0.0.0:8080->80/tcp");
+        assert_eq!(format_port(&port(None, None)), "80/tcp");
+    }
+
+    #[test]
+    fn error_message_prefers_the_json_message() {
+        assert_eq!(error_message(br#"{"message": "No such container: web"}"#), "No such container: web");
+        assert_eq!(error_message(b"  page not found\n"), "page not found");
+        assert_eq!(error_message(b""), "");
+    }
+}
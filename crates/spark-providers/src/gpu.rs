@@ -1,6 +1,49 @@
-use spark_types::{GpuMetrics, GpuProcess};
+use nix::errno::Errno;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use serde::Deserialize;
+use spark_types::{ContainerActionResult, GpuMetrics, GpuProcess};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::Instant;
 use tracing::warn;
 
+use crate::command::{run_with_timeout, DEFAULT_TIMEOUT};
+use crate::rocm;
+
+const DEFAULT_NVIDIA_SMI: &str = "nvidia-smi";
+/// How long a process gets to exit after SIGTERM before it is sent SIGKILL.
+const KILL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct GpuOptions {
+    /// Full path to nvidia-smi, for hosts where it isn't on the service's PATH.
+    /// Defaults to looking up `nvidia-smi` on PATH.
+    pub nvidia_smi_path: Option<String>,
+    /// Allow killing GPU processes from the dashboard. Off by default; the console also
+    /// needs to run as the process owner or root for the signal to be delivered.
+    pub allow_kill: bool,
+}
+
+impl GpuOptions {
+    /// The nvidia-smi binary to run. A configured path that doesn't exist is
+    /// ignored (with a warning) in favour of PATH lookup.
+    fn nvidia_smi(&self) -> &str {
+        match self.nvidia_smi_path.as_deref() {
+            Some(path) if Path::new(path).exists() => path,
+            Some(path) => {
+                warn!("configured nvidia_smi_path {path} does not exist, using PATH");
+                DEFAULT_NVIDIA_SMI
+            }
+            None => DEFAULT_NVIDIA_SMI,
+        }
+    }
+}
+
 /// Try to parse a numeric value from an nvidia-smi field.
 /// Strips brackets, whitespace, and unit suffixes (e.g. "MiB", "W").
 /// Returns None for N/A variants like "[N/A]", "N/A", "N/A MiB", etc.
@@ -15,13 +58,12 @@ fn parse_nvsmi_field<T: std::str::FromStr>(raw: &str) -> Option<T> {
 }
 
 /// Read MemTotal from /proc/meminfo and return it in MiB.
-async fn read_proc_meminfo_total_mib() -> Option<u64> {
+pub(crate) async fn read_proc_meminfo_total_mib() -> Option<u64> {
     let contents = tokio::fs::read_to_string("/proc/meminfo").await.ok()?;
     for line in contents.lines() {
         if let Some(rest) = line.strip_prefix("MemTotal:") {
             // Value is typically in kB, e.g. "MemTotal:       131841024 kB"
             let kb: u64 = rest
-                .trim()
                 .split_whitespace()
                 .next()?
                 .parse()
@@ -32,25 +74,37 @@ async fn read_proc_meminfo_total_mib() -> Option<u64> {
     None
 }
 
-pub async fn collect() -> GpuMetrics {
-    match collect_from_nvidia_smi().await {
-        Ok(metrics) => metrics,
-        Err(e) => {
-            warn!("nvidia-smi unavailable, returning mock GPU data: {e}");
-            mock_gpu_metrics()
+/// Try each vendor tool in turn (nvidia-smi, then rocm-smi) and fall back to mock data.
+/// Builds with the `nvml` feature read NVML first and only run the tools when it fails.
+pub async fn collect(options: &GpuOptions) -> Vec<GpuMetrics> {
+    #[cfg(feature = "nvml")]
+    {
+        match crate::nvml::collect().await {
+            Ok(metrics) => return metrics,
+            Err(e) => tracing::debug!("{e}, trying nvidia-smi"),
         }
     }
+    let nvidiaErr = match collect_from_nvidia_smi(options.nvidia_smi()).await {
+        Ok(metrics) => return metrics,
+        Err(e) => e,
+    };
+    let rocmErr = match rocm::collect().await {
+        Ok(metrics) => return metrics,
+        Err(e) => e,
+    };
+    warn!("no GPU tool available, returning mock GPU data (nvidia-smi: {nvidiaErr}; rocm-smi: {rocmErr})");
+    mock_gpu_metrics()
 }
 
-async fn collect_from_nvidia_smi() -> Result<GpuMetrics, String> {
-    let gpuOutput = tokio::process::Command::new("nvidia-smi")
-        .args([
-            "--query-gpu=name,utilization.gpu,temperature.gpu,memory.used,memory.total,power.draw",
+async fn collect_from_nvidia_smi(nvidiaSmi: &str) -> Result<Vec<GpuMetrics>, String> {
+    let gpuOutput = run_with_timeout(
+        Command::new(nvidiaSmi).args([
+            "--query-gpu=index,uuid,name,utilization.gpu,temperature.gpu,memory.used,memory.total,power.draw,fan.speed,clocks.sm,clocks.mem,utilization.encoder,utilization.decoder,power.limit",
             "--format=csv,noheader,nounits",
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("failed to run nvidia-smi: {e}"))?;
+        ]),
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
 
     if !gpuOutput.status.success() {
         return Err(format!(
@@ -60,70 +114,128 @@ async fn collect_from_nvidia_smi() -> Result<GpuMetrics, String> {
     }
 
     let gpuCsv = String::from_utf8_lossy(&gpuOutput.stdout);
-    let gpuLine = gpuCsv.lines().next().ok_or("empty nvidia-smi output")?;
+    let (processesResult, smUtil) = tokio::join!(
+        collect_gpu_processes(nvidiaSmi),
+        collect_process_sm_util(nvidiaSmi)
+    );
+    let mut processes = processesResult.unwrap_or_default();
+    let users = read_passwd_users().await;
+    for (_, process) in &mut processes {
+        process.sm_util_pct = smUtil.get(&process.pid).copied();
+        enrich_from_proc(process, &users).await;
+    }
+    let mut gpus = Vec::new();
+
+    for gpuLine in gpuCsv.lines().filter(|l| !l.trim().is_empty()) {
+        let mut gpu = parse_gpu_line(gpuLine).await?;
+        // Hand each device the processes running on it
+        let (own, rest): (Vec<_>, Vec<_>) = processes
+            .into_iter()
+            .partition(|(uuid, _)| *uuid == gpu.uuid);
+        gpu.processes = own.into_iter().map(|(_, p)| p).collect();
+        processes = rest;
+        // No device-wide figure on unified memory, but each process still reports its share
+        if gpu.unified_memory {
+            gpu.memory_used_mib = gpu.processes.iter().map(|p| p.memory_mib).sum();
+        }
+        gpus.push(gpu);
+    }
+
+    if gpus.is_empty() {
+        return Err("empty nvidia-smi output".into());
+    }
+
+    gpus.sort_by_key(|g| g.index);
+    Ok(gpus)
+}
+
+/// Parse one `--query-gpu` CSV line into metrics for that device (without processes).
+async fn parse_gpu_line(gpuLine: &str) -> Result<GpuMetrics, String> {
     let gpuFields: Vec<&str> = gpuLine.split(", ").collect();
 
-    if gpuFields.len() < 6 {
+    if gpuFields.len() < 14 {
         return Err(format!(
             "unexpected nvidia-smi output format: {}",
             gpuLine
         ));
     }
 
-    let name = gpuFields[0].trim().to_string();
-    let utilizationPct = parse_nvsmi_field::<f32>(gpuFields[1]).unwrap_or_else(|| {
-        warn!("could not parse GPU utilization '{}'", gpuFields[1].trim());
+    let index = parse_nvsmi_field::<u32>(gpuFields[0]).unwrap_or_else(|| {
+        warn!("could not parse GPU index '{}'", gpuFields[0].trim());
+        0
+    });
+    let uuid = gpuFields[1].trim().to_string();
+    let name = gpuFields[2].trim().to_string();
+    let utilizationPct = parse_nvsmi_field::<f32>(gpuFields[3]).unwrap_or_else(|| {
+        warn!("could not parse GPU utilization '{}'", gpuFields[3].trim());
         0.0
     });
-    let temperatureC = parse_nvsmi_field::<u32>(gpuFields[2]).unwrap_or_else(|| {
-        warn!("could not parse GPU temperature '{}'", gpuFields[2].trim());
+    let temperatureC = parse_nvsmi_field::<u32>(gpuFields[4]).unwrap_or_else(|| {
+        warn!("could not parse GPU temperature '{}'", gpuFields[4].trim());
         0
     });
 
     // On unified-memory systems (e.g. DGX Spark GB10), nvidia-smi returns [N/A]
     // for memory fields. Fall back to /proc/meminfo for total memory.
-    let memoryUsedMib = parse_nvsmi_field::<u64>(gpuFields[3]).unwrap_or(0);
+    let memoryUsedMib = parse_nvsmi_field::<u64>(gpuFields[5]).unwrap_or(0);
     let mut unifiedMemory = false;
-    let memoryTotalMib = match parse_nvsmi_field::<u64>(gpuFields[4]) {
+    let memoryTotalMib = match parse_nvsmi_field::<u64>(gpuFields[6]) {
         Some(v) => v,
         None => {
             warn!(
-                "nvidia-smi memory.total is N/A ('{}'), falling back to /proc/meminfo",
-                gpuFields[4].trim()
+                "nvidia-smi memory.total for GPU {index} is N/A ('{}'), falling back to /proc/meminfo",
+                gpuFields[6].trim()
             );
             unifiedMemory = true;
             read_proc_meminfo_total_mib().await.unwrap_or(0)
         }
     };
 
-    let powerDrawW = parse_nvsmi_field::<f32>(gpuFields[5]).unwrap_or_else(|| {
-        warn!("could not parse GPU power draw '{}'", gpuFields[5].trim());
+    let powerDrawW = parse_nvsmi_field::<f32>(gpuFields[7]).unwrap_or_else(|| {
+        warn!("could not parse GPU power draw '{}'", gpuFields[7].trim());
         0.0
     });
 
-    let processes = collect_gpu_processes().await.unwrap_or_default();
+    let fanSpeedPct = parse_nvsmi_field::<u32>(gpuFields[8]);
+    let smClockMhz = parse_nvsmi_field::<u32>(gpuFields[9]);
+    let memClockMhz = parse_nvsmi_field::<u32>(gpuFields[10]);
+    let encoderUtilPct = parse_nvsmi_field::<f32>(gpuFields[11]);
+    let decoderUtilPct = parse_nvsmi_field::<f32>(gpuFields[12]);
+    // [N/A] on GPUs without power management (e.g. some integrated/unified parts)
+    let powerLimitW = parse_nvsmi_field::<f32>(gpuFields[13]).unwrap_or(0.0);
 
     Ok(GpuMetrics {
+        index,
+        uuid,
+        vendor: "nvidia".into(),
         name,
         utilization_pct: utilizationPct,
         temperature_c: temperatureC,
         memory_used_mib: memoryUsedMib,
         memory_total_mib: memoryTotalMib,
         power_draw_w: powerDrawW,
+        power_limit_w: powerLimitW,
+        fan_speed_pct: fanSpeedPct,
+        sm_clock_mhz: smClockMhz,
+        mem_clock_mhz: memClockMhz,
+        encoder_util_pct: encoderUtilPct,
+        decoder_util_pct: decoderUtilPct,
         unified_memory: unifiedMemory,
-        processes,
+        processes: Vec::new(),
     })
 }
 
-async fn collect_gpu_processes() -> Result<Vec<GpuProcess>, String> {
-    let processOutput = tokio::process::Command::new("nvidia-smi")
-        .args([
-            "--query-compute-apps=pid,process_name,used_gpu_memory",
+/// Compute processes across all GPUs, tagged with the UUID of the GPU they run on.
+async fn collect_gpu_processes(nvidiaSmi: &str) -> Result<Vec<(String, GpuProcess)>, String> {
+    let processOutput = run_with_timeout(
+        Command::new(nvidiaSmi).args([
+            "--query-compute-apps=gpu_uuid,pid,process_name,used_gpu_memory",
             "--format=csv,noheader,nounits",
-        ])
-        .output()
-        .await
-        .map_err(|e| format!("failed to query GPU processes: {e}"))?;
+        ]),
+        DEFAULT_TIMEOUT,
+    )
+    .await
+    .map_err(|e| format!("failed to query GPU processes: {e}"))?;
 
     if !processOutput.status.success() {
         return Ok(Vec::new());
@@ -139,51 +251,273 @@ async fn collect_gpu_processes() -> Result<Vec<GpuProcess>, String> {
         }
 
         let fields: Vec<&str> = line.split(", ").collect();
-        if fields.len() >= 3 {
-            let pid = fields[0].trim().parse::<u32>()
-                .inspect_err(|e| warn!("failed to parse GPU process PID '{}': {e}", fields[0].trim()))
+        if fields.len() >= 4 {
+            let gpuUuid = fields[0].trim().to_string();
+            let pid = fields[1].trim().parse::<u32>()
+                .inspect_err(|e| warn!("failed to parse GPU process PID '{}': {e}", fields[1].trim()))
                 .unwrap_or(0);
-            let name = fields[1].trim().to_string();
-            let memoryMib = fields[2].trim().parse::<u64>()
-                .inspect_err(|e| warn!("failed to parse GPU process memory '{}': {e}", fields[2].trim()))
+            let name = fields[2].trim().to_string();
+            let memoryMib = fields[3].trim().parse::<u64>()
+                .inspect_err(|e| warn!("failed to parse GPU process memory '{}': {e}", fields[3].trim()))
                 .unwrap_or(0);
 
-            processes.push(GpuProcess {
-                pid,
-                name,
-                memory_mib: memoryMib,
-            });
+            processes.push((
+                gpuUuid,
+                GpuProcess {
+                    pid,
+                    name,
+                    memory_mib: memoryMib,
+                    sm_util_pct: None,
+                    cmdline: String::new(),
+                    user: String::new(),
+                },
+            ));
         }
     }
 
     Ok(processes)
 }
 
-fn mock_gpu_metrics() -> GpuMetrics {
-    GpuMetrics {
-        name: "NVIDIA GH200 (mock)".into(),
-        utilization_pct: 42.0,
-        temperature_c: 55,
-        memory_used_mib: 15360,
-        memory_total_mib: 98304,
-        power_draw_w: 185.0,
-        unified_memory: false,
-        processes: vec![
-            GpuProcess {
-                pid: 1234,
-                name: "python3".into(),
-                memory_mib: 8192,
-            },
-            GpuProcess {
-                pid: 5678,
-                name: "comfyui".into(),
-                memory_mib: 4096,
-            },
-            GpuProcess {
+/// Map of UID to user name from /etc/passwd.
+pub(crate) async fn read_passwd_users() -> HashMap<u32, String> {
+    let contents = tokio::fs::read_to_string("/etc/passwd").await.unwrap_or_default();
+    contents
+        .lines()
+        .filter_map(|line| {
+            // name:password:uid:gid:...
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let uid = fields.nth(1)?.parse::<u32>().ok()?;
+            Some((uid, name.to_string()))
+        })
+        .collect()
+}
+
+/// Fill in the command line and owner from /proc, falling back to the
+/// nvidia-smi process name when /proc isn't readable (e.g. another PID namespace).
+pub(crate) async fn enrich_from_proc(process: &mut GpuProcess, users: &HashMap<u32, String>) {
+    let procDir = format!("/proc/{}", process.pid);
+
+    process.cmdline = tokio::fs::read(format!("{procDir}/cmdline"))
+        .await
+        .ok()
+        .map(|raw| {
+            // Arguments are NUL-separated, with a trailing NUL
+            raw.split(|b| *b == 0)
+                .filter(|arg| !arg.is_empty())
+                .map(|arg| String::from_utf8_lossy(arg).into_owned())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|cmd| !cmd.is_empty())
+        .unwrap_or_else(|| process.name.clone());
+
+    let uid = tokio::fs::read_to_string(format!("{procDir}/status"))
+        .await
+        .ok()
+        .and_then(|status| {
+            // "Uid:\t1000\t1000\t1000\t1000" — real UID first
+            status
+                .lines()
+                .find_map(|l| l.strip_prefix("Uid:"))
+                .and_then(|rest| rest.split_whitespace().next())
+                .and_then(|uid| uid.parse::<u32>().ok())
+        });
+
+    process.user = match uid {
+        Some(uid) => users.get(&uid).cloned().unwrap_or_else(|| uid.to_string()),
+        None => String::new(),
+    };
+}
+
+/// Per-PID SM utilization from a single `nvidia-smi pmon` sample.
+/// Returns an empty map if pmon is unavailable (e.g. unsupported GPU or driver).
+async fn collect_process_sm_util(nvidiaSmi: &str) -> HashMap<u32, f32> {
+    let output = match run_with_timeout(
+        Command::new(nvidiaSmi).args(["pmon", "-c", "1", "-s", "u"]),
+        DEFAULT_TIMEOUT,
+    )
+    .await
+    {
+        Ok(o) if o.status.success() => o,
+        Ok(o) => {
+            warn!("nvidia-smi pmon exited with status {}", o.status);
+            return HashMap::new();
+        }
+        Err(e) => {
+            warn!("nvidia-smi pmon: {e}");
+            return HashMap::new();
+        }
+    };
+
+    // Lines look like "    0      12345     C     45     12     -     -    python3",
+    // with '#'-prefixed header lines and '-' for values that weren't sampled.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut map = HashMap::new();
+    for line in stdout.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let (Ok(pid), Ok(sm)) = (fields[1].parse::<u32>(), fields[3].parse::<f32>()) else {
+            continue;
+        };
+        // A process spanning several GPUs shows up once per device
+        *map.entry(pid).or_insert(0.0) += sm;
+    }
+    map
+}
+
+fn mock_gpu_metrics() -> Vec<GpuMetrics> {
+    vec![
+        GpuMetrics {
+            index: 0,
+            uuid: "GPU-mock-0".into(),
+            vendor: "mock".into(),
+            name: "NVIDIA GH200 (mock)".into(),
+            utilization_pct: 42.0,
+            temperature_c: 55,
+            memory_used_mib: 15360,
+            memory_total_mib: 98304,
+            power_draw_w: 185.0,
+            power_limit_w: 900.0,
+            fan_speed_pct: Some(38),
+            sm_clock_mhz: Some(1755),
+            mem_clock_mhz: Some(2619),
+            encoder_util_pct: Some(0.0),
+            decoder_util_pct: Some(14.0),
+            unified_memory: false,
+            processes: vec![
+                GpuProcess {
+                    pid: 1234,
+                    name: "python3".into(),
+                    memory_mib: 8192,
+                    sm_util_pct: Some(31.0),
+                    cmdline: "python3 train.py --config configs/llama-8b.yaml".into(),
+                    user: "auxidus-spark".into(),
+                },
+                GpuProcess {
+                    pid: 5678,
+                    name: "comfyui".into(),
+                    memory_mib: 4096,
+                    sm_util_pct: Some(11.0),
+                    cmdline: "python3 main.py --listen 0.0.0.0 --port 8188".into(),
+                    user: "auxidus-spark".into(),
+                },
+            ],
+        },
+        GpuMetrics {
+            index: 1,
+            uuid: "GPU-mock-1".into(),
+            vendor: "mock".into(),
+            name: "NVIDIA GH200 (mock)".into(),
+            utilization_pct: 87.0,
+            temperature_c: 71,
+            memory_used_mib: 61440,
+            memory_total_mib: 98304,
+            power_draw_w: 412.0,
+            power_limit_w: 900.0,
+            fan_speed_pct: None,
+            sm_clock_mhz: Some(1980),
+            mem_clock_mhz: Some(2619),
+            encoder_util_pct: None,
+            decoder_util_pct: None,
+            unified_memory: false,
+            processes: vec![GpuProcess {
                 pid: 9012,
                 name: "ollama".into(),
                 memory_mib: 3072,
-            },
-        ],
+                sm_util_pct: Some(87.0),
+                cmdline: "/usr/local/bin/ollama runner --model /root/.ollama/models/blobs/sha256-6a0746a1ec1a".into(),
+                user: "ollama".into(),
+            }],
+        },
+    ]
+}
+
+/// Terminate a GPU process: SIGTERM, then SIGKILL if it is still running after
+/// [`KILL_GRACE_PERIOD`]. Only PIDs that a fresh collection lists as GPU processes are
+/// signalled, so the endpoint can't be used to kill arbitrary processes, and mock data
+/// (whose PIDs are made up) never matches.
+pub async fn kill_process(options: &GpuOptions, pid: u32) -> ContainerActionResult {
+    let failure = |message: String| ContainerActionResult {
+        success: false,
+        message,
+    };
+    if !options.allow_kill {
+        return failure("killing GPU processes is disabled (set gpu.allow_kill = true)".to_string());
+    }
+    let gpus = collect(options).await;
+    let Some(process) = find_gpu_process(&gpus, pid) else {
+        return failure(format!("PID {pid} is not a running GPU process"));
+    };
+    let name = process.name.clone();
+    let target = match i32::try_from(pid) {
+        Ok(raw) if raw > 1 && pid != std::process::id() => Pid::from_raw(raw),
+        _ => return failure(format!("refusing to kill PID {pid}")),
+    };
+
+    if let Err(e) = kill(target, Signal::SIGTERM) {
+        return failure(signal_error(pid, &name, e));
+    }
+    let deadline = Instant::now() + KILL_GRACE_PERIOD;
+    while Instant::now() < deadline {
+        tokio::time::sleep(KILL_POLL_INTERVAL).await;
+        // No signal only checks that the process still exists
+        if kill(target, None) == Err(Errno::ESRCH) {
+            return ContainerActionResult {
+                success: true,
+                message: format!("PID {pid} ({name}) exited after SIGTERM"),
+            };
+        }
+    }
+
+    match kill(target, Signal::SIGKILL) {
+        Ok(()) | Err(Errno::ESRCH) => ContainerActionResult {
+            success: true,
+            message: format!(
+                "PID {pid} ({name}) ignored SIGTERM for {}s and was sent SIGKILL",
+                KILL_GRACE_PERIOD.as_secs()
+            ),
+        },
+        Err(e) => failure(signal_error(pid, &name, e)),
+    }
+}
+
+/// The process with `pid` on a real (not mock) GPU.
+fn find_gpu_process(gpus: &[GpuMetrics], pid: u32) -> Option<&GpuProcess> {
+    gpus.iter()
+        .filter(|gpu| gpu.vendor != "mock")
+        .flat_map(|gpu| &gpu.processes)
+        .find(|process| process.pid == pid)
+}
+
+fn signal_error(pid: u32, name: &str, errno: Errno) -> String {
+    match errno {
+        Errno::EPERM => format!(
+            "permission denied signalling PID {pid} ({name}); the console must run as its owner or root"
+        ),
+        Errno::ESRCH => format!("PID {pid} ({name}) is no longer running"),
+        e => format!("failed to signal PID {pid} ({name}): {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_real_gpu_processes_can_be_killed() {
+        let mut gpus = mock_gpu_metrics();
+        let mockPid = gpus[1].processes[0].pid;
+        assert!(find_gpu_process(&gpus, mockPid).is_none());
+
+        gpus[1].vendor = "nvidia".into();
+        assert_eq!(find_gpu_process(&gpus, mockPid).map(|p| p.pid), Some(mockPid));
+        assert!(find_gpu_process(&gpus, 1).is_none());
     }
 }
@@ -1,29 +1,174 @@
 #![allow(non_snake_case)]
 
+pub mod command;
 pub mod cpu;
 pub mod disk;
 pub mod docker;
+mod docker_api;
 pub mod gpu;
+pub mod logs;
 pub mod memory;
 pub mod models;
+pub mod net;
+#[cfg(feature = "nvml")]
+mod nvml;
+pub mod podman;
+mod pressure;
+pub mod rocm;
+pub mod services;
+pub mod updates;
 pub mod uptime;
 
-use spark_types::SystemMetrics;
+use spark_types::{ContainerStatus, InventorySummary, SystemMetrics};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
 
-pub async fn collect_system_metrics() -> SystemMetrics {
-    let (gpuResult, memoryResult, cpuResult, diskResult, uptimeResult) = tokio::join!(
-        gpu::collect(),
-        memory::collect(),
+/// Time between the two counter reads that disk and network throughput are computed
+/// from. Both run inside one concurrent collection, so they share a single wait.
+pub(crate) const RATE_SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Per-provider settings from the console config, shared by the API routes
+/// (through `AppState`) and the UI server functions (through Leptos context).
+#[derive(Clone, Debug, Default)]
+pub struct ProviderOptions {
+    pub docker: docker::DockerOptions,
+    pub gpu: gpu::GpuOptions,
+    pub disk: disk::DiskOptions,
+    pub models: models::ModelOptions,
+    pub services: services::ServiceOptions,
+    pub net: net::NetOptions,
+    pub memory: memory::MemoryOptions,
+    pub metrics_cache: MetricsCache,
+}
+
+/// How long a collection is reused when no TTL is configured.
+pub const DEFAULT_METRICS_CACHE_TTL: Duration = Duration::from_secs(1);
+
+/// The latest [`collect_system_metrics`] result, shared by every clone of the
+/// [`ProviderOptions`] it lives in. Each dashboard tab, the history sampler and the API
+/// would otherwise each spawn `nvidia-smi` and friends on their own schedule.
+#[derive(Clone, Debug)]
+pub struct MetricsCache {
+    ttl: Duration,
+    latest: Arc<Mutex<Option<(Instant, SystemMetrics)>>>,
+}
+
+impl Default for MetricsCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_METRICS_CACHE_TTL)
+    }
+}
+
+impl MetricsCache {
+    /// Reuse a collection for `ttl`; zero collects on every call.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            latest: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The cached metrics if they are younger than the TTL, else the result of `collect`.
+    /// The lock is held while collecting, so callers arriving meanwhile wait for that
+    /// collection instead of starting their own.
+    pub async fn get_or_collect<F>(&self, collect: impl FnOnce() -> F) -> SystemMetrics
+    where
+        F: Future<Output = SystemMetrics>,
+    {
+        if self.ttl.is_zero() {
+            return collect().await;
+        }
+        let mut latest = self.latest.lock().await;
+        if let Some((collectedAt, metrics)) = latest.as_ref() {
+            if collectedAt.elapsed() < self.ttl {
+                return metrics.clone();
+            }
+        }
+        let metrics = collect().await;
+        *latest = Some((Instant::now(), metrics.clone()));
+        metrics
+    }
+}
+
+/// Current metrics from every provider, reused from `options.metrics_cache` when recent.
+pub async fn collect_system_metrics(options: &ProviderOptions) -> SystemMetrics {
+    options
+        .metrics_cache
+        .get_or_collect(|| collect_system_metrics_uncached(options))
+        .await
+}
+
+async fn collect_system_metrics_uncached(options: &ProviderOptions) -> SystemMetrics {
+    let (gpuResult, memoryResult, cpuResult, diskResult, uptimeResult, netResult) = tokio::join!(
+        gpu::collect(&options.gpu),
+        memory::collect(&options.memory),
         cpu::collect(),
-        disk::collect(),
+        disk::collect(&options.disk),
         uptime::collect(),
+        net::collect(&options.net),
     );
 
     SystemMetrics {
-        gpu: gpuResult,
+        schema_version: spark_types::METRICS_SCHEMA_VERSION,
+        gpus: gpuResult,
         memory: memoryResult,
         cpu: cpuResult,
-        disk: diskResult,
+        disk: diskResult.first().cloned().unwrap_or_default(),
+        disks: diskResult,
         uptime: uptimeResult,
+        net: netResult,
+    }
+}
+
+/// Container and model counts, using only the cheap listing calls.
+pub async fn collect_inventory_summary(options: &ProviderOptions) -> InventorySummary {
+    let (containersResult, models) = tokio::join!(
+        docker::collect_container_list(&options.docker),
+        models::collect(&options.models)
+    );
+
+    let containers = containersResult.unwrap_or_else(|e| {
+        tracing::warn!("container listing unavailable for summary: {e}");
+        Vec::new()
+    });
+
+    InventorySummary {
+        containers_running: containers
+            .iter()
+            .filter(|c| c.status == ContainerStatus::Running)
+            .count(),
+        containers_total: containers.len(),
+        containers_unhealthy: containers.iter().filter(|c| docker::is_unhealthy(c)).count(),
+        models_total: models.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn concurrent_callers_share_one_collection() {
+        let cache = MetricsCache::new(Duration::from_secs(60));
+        let collections = Arc::new(AtomicUsize::new(0));
+        let collect = || {
+            let collections = collections.clone();
+            async move {
+                collections.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                SystemMetrics::default()
+            }
+        };
+
+        let calls: Vec<_> = (0..8).map(|_| cache.get_or_collect(collect)).collect();
+        futures_util::future::join_all(calls).await;
+        cache.get_or_collect(collect).await;
+        assert_eq!(collections.load(Ordering::SeqCst), 1);
+
+        MetricsCache::new(Duration::ZERO).get_or_collect(collect).await;
+        assert_eq!(collections.load(Ordering::SeqCst), 2);
     }
 }
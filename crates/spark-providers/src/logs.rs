@@ -0,0 +1,206 @@
+//! Container logs: the last N lines, or a live `--follow` stream.
+
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::time::Duration;
+
+use crate::command::run_with_timeout;
+use crate::docker::DockerOptions;
+use crate::docker_api;
+
+/// Lines returned when the caller doesn't ask for a specific tail.
+pub const DEFAULT_TAIL: usize = 200;
+/// Upper bound on `tail`, so one request can't pull a multi-gigabyte log into memory.
+pub const MAX_TAIL: usize = 10_000;
+
+const LOGS_TIMEOUT: Duration = Duration::from_secs(15);
+// Lines buffered for a follower before the reader waits on it
+const FOLLOW_BUFFER: usize = 256;
+
+/// Whether `reference` looks like a container ID or name. Anything else is rejected
+/// before it reaches the CLI (where a leading `-` would be read as a flag) or an API path.
+pub fn is_valid_container_ref(reference: &str) -> bool {
+    !reference.is_empty()
+        && !reference.starts_with(['-', '.'])
+        && reference
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// The last `tail` lines of a container's stdout and stderr, interleaved in the order they
+/// were written, with terminal escape codes removed.
+pub async fn tail(options: &DockerOptions, containerId: &str, tail: usize) -> Result<String, String> {
+    if !is_valid_container_ref(containerId) {
+        return Err(format!("invalid container: {containerId}"));
+    }
+    let tail = tail.min(MAX_TAIL);
+
+    // Both paths ask for timestamps so the two streams can be merged back into order
+    let raw = match options.engine_socket() {
+        Some(socket) => docker_api::logs(socket, containerId, tail).await?,
+        None => {
+            let program = options.program();
+            let output = run_with_timeout(
+                Command::new(program).args(["logs", "--timestamps", "--tail", &tail.to_string(), containerId]),
+                LOGS_TIMEOUT,
+            )
+            .await?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("{program} logs failed: {}", stderr.trim()));
+            }
+            let mut raw = output.stdout;
+            raw.extend_from_slice(&output.stderr);
+            raw
+        }
+    };
+
+    Ok(merge_timestamped(&String::from_utf8_lossy(&raw), tail))
+}
+
+/// Order timestamped lines by their RFC 3339 prefix, keep the last `tail`, and drop the
+/// timestamps. Docker pads the fractional seconds, so the prefixes sort as strings.
+fn merge_timestamped(raw: &str, tail: usize) -> String {
+    let mut lines: Vec<(&str, &str)> = raw
+        .lines()
+        .map(|line| line.split_once(' ').unwrap_or(("", line)))
+        .collect();
+    // Stable, so lines sharing a timestamp keep the order they arrived in
+    lines.sort_by(|a, b| a.0.cmp(b.0));
+
+    let skip = lines.len().saturating_sub(tail);
+    lines[skip..]
+        .iter()
+        .map(|(_, text)| strip_ansi(text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Start `logs --follow`, sending the last `tail` lines and then each new one as it is
+/// written. The process is killed once the receiver is dropped.
+pub async fn follow(
+    options: &DockerOptions,
+    containerId: &str,
+    tail: usize,
+) -> Result<mpsc::Receiver<String>, String> {
+    if !is_valid_container_ref(containerId) {
+        return Err(format!("invalid container: {containerId}"));
+    }
+    let program = options.program();
+    let mut child = Command::new(program)
+        .args(["logs", "--follow", "--tail", &tail.min(MAX_TAIL).to_string(), containerId])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to run {program}: {e}"))?;
+
+    let (tx, rx) = mpsc::channel(FOLLOW_BUFFER);
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(forward_lines(stdout, tx.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(forward_lines(stderr, tx.clone()));
+    }
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = child.wait() => {}
+            // Dropping the child kills it
+            _ = tx.closed() => {}
+        }
+    });
+
+    Ok(rx)
+}
+
+async fn forward_lines(pipe: impl AsyncRead + Unpin, tx: mpsc::Sender<String>) {
+    let mut reader = BufReader::new(pipe);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {
+                let line = strip_ansi(String::from_utf8_lossy(&buf).trim_end_matches(['\n', '\r']));
+                if tx.send(line).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Remove ANSI escape sequences (colours, cursor movement, titles) and other control
+/// characters, keeping tabs.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => match chars.next() {
+                // CSI: parameters then one final byte in @..~
+                Some('[') => {
+                    for c in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: runs to BEL or ST (ESC \)
+                Some(']') => {
+                    while let Some(c) = chars.next() {
+                        if c == '\u{7}' {
+                            break;
+                        }
+                        if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                // Two-character sequences like ESC ( B
+                Some('(' | ')') => {
+                    chars.next();
+                }
+                _ => {}
+            },
+            '\t' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escape_sequences() {
+        assert_eq!(strip_ansi("\u{1b}[1;31merror\u{1b}[0m: boom"), "error: boom");
+        assert_eq!(strip_ansi("\u{1b}]0;title\u{7}ready"), "ready");
+        assert_eq!(strip_ansi("a\tb\r\u{0}c"), "a\tbc");
+    }
+
+    #[test]
+    fn merges_streams_by_timestamp() {
+        let raw = "2024-01-01T00:00:01.000000000Z out one\n\
+                   2024-01-01T00:00:03.000000000Z out two\n\
+                   2024-01-01T00:00:02.000000000Z err one\n";
+        assert_eq!(merge_timestamped(raw, 10), "out one\nerr one\nout two");
+        assert_eq!(merge_timestamped(raw, 2), "err one\nout two");
+    }
+
+    #[test]
+    fn rejects_flag_like_references() {
+        assert!(is_valid_container_ref("3f2a9c1b7d4e"));
+        assert!(is_valid_container_ref("my_app-1.web"));
+        assert!(!is_valid_container_ref("--help"));
+        assert!(!is_valid_container_ref("../etc"));
+        assert!(!is_valid_container_ref(""));
+    }
+}
@@ -1,25 +1,48 @@
+use serde::Deserialize;
 use spark_types::MemoryMetrics;
 use tracing::warn;
 
-pub async fn collect() -> MemoryMetrics {
-    match read_proc_meminfo().await {
+use crate::pressure;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MemoryOptions {
+    /// Report `used` as `total - free`, counting page cache as used, rather than the
+    /// default `total - available`.
+    pub count_cache_as_used: bool,
+}
+
+pub async fn collect(options: &MemoryOptions) -> MemoryMetrics {
+    let mut metrics = match read_proc_meminfo(options).await {
         Ok(metrics) => metrics,
         Err(e) => {
             warn!("/proc/meminfo unavailable, returning mock memory data: {e}");
-            mock_memory_metrics()
+            return mock_memory_metrics();
         }
+    };
+    if let Some(psi) = pressure::read_some("memory").await {
+        metrics.pressure_some_avg10 = Some(psi.avg10);
+        metrics.pressure_some_avg60 = Some(psi.avg60);
     }
+    metrics
 }
 
-async fn read_proc_meminfo() -> Result<MemoryMetrics, String> {
+async fn read_proc_meminfo(options: &MemoryOptions) -> Result<MemoryMetrics, String> {
     let contents = tokio::fs::read_to_string("/proc/meminfo")
         .await
         .map_err(|e| format!("failed to read /proc/meminfo: {e}"))?;
+    Ok(parse_meminfo(&contents, options))
+}
 
+fn parse_meminfo(contents: &str, options: &MemoryOptions) -> MemoryMetrics {
     let mut memTotalKb: u64 = 0;
     let mut memAvailableKb: u64 = 0;
+    let mut memFreeKb: u64 = 0;
     let mut swapTotalKb: u64 = 0;
     let mut swapFreeKb: u64 = 0;
+    let mut buffersKb: u64 = 0;
+    let mut cachedKb: u64 = 0;
+    let mut sReclaimableKb: u64 = 0;
 
     for line in contents.lines() {
         let parts: Vec<&str> = line.split_whitespace().collect();
@@ -32,8 +55,12 @@ async fn read_proc_meminfo() -> Result<MemoryMetrics, String> {
         match parts[0] {
             "MemTotal:" => memTotalKb = valueKb,
             "MemAvailable:" => memAvailableKb = valueKb,
+            "MemFree:" => memFreeKb = valueKb,
             "SwapTotal:" => swapTotalKb = valueKb,
             "SwapFree:" => swapFreeKb = valueKb,
+            "Buffers:" => buffersKb = valueKb,
+            "Cached:" => cachedKb = valueKb,
+            "SReclaimable:" => sReclaimableKb = valueKb,
             _ => {}
         }
     }
@@ -41,17 +68,30 @@ async fn read_proc_meminfo() -> Result<MemoryMetrics, String> {
     let KB_TO_BYTES: u64 = 1024;
     let totalBytes = memTotalKb * KB_TO_BYTES;
     let availableBytes = memAvailableKb * KB_TO_BYTES;
-    let usedBytes = totalBytes.saturating_sub(availableBytes);
+    let freeBytes = memFreeKb * KB_TO_BYTES;
+    let appUsedBytes = totalBytes.saturating_sub(availableBytes);
+    let usedBytes = if options.count_cache_as_used {
+        totalBytes.saturating_sub(freeBytes)
+    } else {
+        appUsedBytes
+    };
     let swapTotalBytes = swapTotalKb * KB_TO_BYTES;
     let swapUsedBytes = swapTotalBytes.saturating_sub(swapFreeKb * KB_TO_BYTES);
 
-    Ok(MemoryMetrics {
+    MemoryMetrics {
         total_bytes: totalBytes,
         used_bytes: usedBytes,
+        used_includes_cache: options.count_cache_as_used,
         available_bytes: availableBytes,
+        free_bytes: freeBytes,
+        app_used_bytes: appUsedBytes,
+        buffers_bytes: buffersKb * KB_TO_BYTES,
+        cached_bytes: (cachedKb + sReclaimableKb) * KB_TO_BYTES,
         swap_total_bytes: swapTotalBytes,
         swap_used_bytes: swapUsedBytes,
-    })
+        pressure_some_avg10: None,
+        pressure_some_avg60: None,
+    }
 }
 
 fn mock_memory_metrics() -> MemoryMetrics {
@@ -60,8 +100,37 @@ fn mock_memory_metrics() -> MemoryMetrics {
     MemoryMetrics {
         total_bytes: TOTAL,
         used_bytes: USED,
+        used_includes_cache: false,
         available_bytes: TOTAL - USED,
+        free_bytes: TOTAL - USED - 37 * 1024 * 1024 * 1024,
+        app_used_bytes: USED,
+        buffers_bytes: 1024 * 1024 * 1024,
+        cached_bytes: 36 * 1024 * 1024 * 1024,
         swap_total_bytes: 8 * 1024 * 1024 * 1024,
         swap_used_bytes: 512 * 1024 * 1024,
+        pressure_some_avg10: Some(1.2),
+        pressure_some_avg60: Some(0.8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn used_memory_follows_cache_setting() {
+        let meminfo = "MemTotal:       1000 kB\nMemFree:         100 kB\nMemAvailable:    600 kB\n\
+                       Buffers:          50 kB\nCached:          400 kB\n";
+        let options = |count_cache_as_used| MemoryOptions { count_cache_as_used };
+
+        let metrics = parse_meminfo(meminfo, &options(false));
+        assert_eq!(metrics.used_bytes, 400 * 1024);
+        assert_eq!(metrics.free_bytes, 100 * 1024);
+        assert!(!metrics.used_includes_cache);
+
+        let metrics = parse_meminfo(meminfo, &options(true));
+        assert_eq!(metrics.used_bytes, 900 * 1024);
+        assert_eq!(metrics.app_used_bytes, 400 * 1024);
+        assert!(metrics.used_includes_cache);
     }
 }
@@ -1,7 +1,16 @@
 #![allow(non_snake_case)]
 
 use spark_types::ModelEntry;
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufReader, Read, Seek};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
 use tokio::fs;
+use tokio::sync::Mutex;
+use serde::Deserialize;
 use tracing::warn;
 
 const DEFAULT_MODEL_DIRS: &[&str] = &[
@@ -14,21 +23,219 @@ const MODEL_EXTENSIONS: &[&str] = &[
     "gguf", "safetensors", "bin", "pt", "pth", "onnx", "ckpt",
 ];
 
-pub async fn collect() -> Vec<ModelEntry> {
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct ModelOptions {
+    /// Directories scanned for model files. Deletes are only allowed inside these.
+    pub dirs: Vec<String>,
+    /// Directory levels below each scan dir that are walked; 0 scans only the dir itself.
+    pub max_depth: usize,
+    /// Glob patterns for paths to skip, e.g. `**/datasets/**`.
+    pub exclude: Vec<String>,
+    /// The last scan, shared by every clone of these options.
+    #[serde(skip)]
+    pub scan_cache: ModelScanCache,
+}
+
+impl Default for ModelOptions {
+    fn default() -> Self {
+        Self {
+            dirs: DEFAULT_MODEL_DIRS.iter().map(|d| d.to_string()).collect(),
+            max_depth: 6,
+            exclude: vec!["**/.git/**".to_string()],
+            scan_cache: ModelScanCache::default(),
+        }
+    }
+}
+
+impl ModelOptions {
+    /// The exclude globs that parse; bad ones are logged and ignored.
+    fn exclude_patterns(&self) -> Vec<glob::Pattern> {
+        self.exclude
+            .iter()
+            .filter_map(|p| match glob::Pattern::new(p) {
+                Ok(pattern) => Some(pattern),
+                Err(e) => {
+                    warn!("ignoring models.exclude pattern {p:?}: {e}");
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Whether `path` matches an exclude pattern. Directories are also tried with a trailing
+/// slash, so `**/.git/**` skips the `.git` directory itself rather than each file in it.
+fn is_excluded(path: &Path, isDir: bool, patterns: &[glob::Pattern]) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    let text = path.to_string_lossy();
+    let asDir = format!("{text}/");
+    patterns.iter().any(|p| {
+        p.matches_with(&text, options) || (isDir && p.matches_with(&asDir, options))
+    })
+}
+
+/// Modification time and size of a path a scan read, or `None` if it didn't exist.
+type Stamp = Option<(Option<SystemTime>, u64)>;
+
+fn stamp_of(metadata: &std::fs::Metadata) -> Stamp {
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+async fn stamp(path: &Path) -> Stamp {
+    fs::metadata(path).await.ok().as_ref().and_then(stamp_of)
+}
+
+/// The result of the last [`collect`], with a stamp of every directory it walked and every
+/// file it read. Adding, removing or renaming a model changes its directory's mtime, and
+/// rewriting one changes the file's, so while every stamp matches the listing is reused
+/// without walking the tree or reading headers again.
+#[derive(Clone, Debug, Default)]
+pub struct ModelScanCache(Arc<Mutex<Option<CachedScan>>>);
+
+#[derive(Debug)]
+struct CachedScan {
+    watched: Vec<(PathBuf, Stamp)>,
+    entries: Vec<ModelEntry>,
+}
+
+impl CachedScan {
+    async fn is_current(&self) -> bool {
+        for (path, before) in &self.watched {
+            if stamp(path).await != *before {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Every model in the scan directories, sorted by name. Reuses the previous scan from
+/// `options.scan_cache` when none of the directories or files it read have changed.
+pub async fn collect(options: &ModelOptions) -> Vec<ModelEntry> {
+    let mut cached = options.scan_cache.0.lock().await;
+    if let Some(scan) = cached.as_ref() {
+        if scan.is_current().await {
+            return scan.entries.clone();
+        }
+    }
+
     let mut entries = Vec::new();
-    for dir in DEFAULT_MODEL_DIRS {
-        if let Err(e) = scan_dir(dir, &mut entries).await {
+    let mut watched = Vec::new();
+    let mut seen = HashSet::new();
+    let exclude = options.exclude_patterns();
+    for dir in &options.dirs {
+        let root = Path::new(dir);
+        let manifests = root.join("manifests");
+        // Stamped before reading, so a change made mid-scan shows up on the next check
+        watched.push((root.to_path_buf(), stamp(root).await));
+        watched.push((manifests.clone(), stamp(&manifests).await));
+        // Ollama stores weights as content-addressed blobs; the names live in its manifests
+        let result = if manifests.is_dir() {
+            scan_ollama(root, &mut entries, &mut watched).await
+        } else {
+            scan_dir(dir, options.max_depth, &exclude, &mut entries, &mut seen, &mut watched).await
+        };
+        if let Err(e) = result {
             warn!("failed to scan {dir}: {e}");
         }
     }
     entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    *cached = Some(CachedScan {
+        watched,
+        entries: entries.clone(),
+    });
     entries
 }
 
-async fn scan_dir(dir: &str, entries: &mut Vec<ModelEntry>) -> Result<(), String> {
-    let mut stack = vec![std::path::PathBuf::from(dir)];
+/// A cheap tag for a model listing, built from the count, the newest modification time,
+/// the total size and the paths, for `ETag`s and skipping unchanged refreshes.
+pub fn fingerprint(entries: &[ModelEntry]) -> String {
+    let newest = entries.iter().filter_map(|e| e.modified).max().unwrap_or(0);
+    let totalBytes: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    let mut paths = DefaultHasher::new();
+    for entry in entries {
+        entry.path.hash(&mut paths);
+    }
+    format!("{:x}-{newest:x}-{totalBytes:x}-{:x}", entries.len(), paths.finish())
+}
 
-    while let Some(path) = stack.pop() {
+/// Delete a model file. `path` must have a model extension and, with symlinks resolved,
+/// both it and the file it points to must sit inside one of the scan directories. For a
+/// symlink (a HuggingFace snapshot entry) the link and its target are both removed, since
+/// removing only the link frees nothing.
+pub async fn delete(options: &ModelOptions, path: &str) -> Result<(), String> {
+    let requested = Path::new(path);
+    let ext = requested.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if !MODEL_EXTENSIONS.contains(&ext) {
+        return Err(format!("not a model file: {path}"));
+    }
+
+    let target = fs::canonicalize(requested)
+        .await
+        .map_err(|e| format!("{path}: {e}"))?;
+    // The link itself, with only its directory resolved
+    let location = match (requested.parent(), requested.file_name()) {
+        (Some(parent), Some(fileName)) => fs::canonicalize(parent)
+            .await
+            .map_err(|e| format!("{path}: {e}"))?
+            .join(fileName),
+        _ => return Err(format!("not a model file: {path}")),
+    };
+
+    let mut roots = Vec::new();
+    for dir in &options.dirs {
+        if let Ok(root) = fs::canonicalize(dir).await {
+            roots.push(root);
+        }
+    }
+    let allowed = |p: &Path| roots.iter().any(|root| p.starts_with(root) && p != root);
+    if !allowed(&target) || !allowed(&location) {
+        return Err(format!("{path} is outside the model directories"));
+    }
+    if !fs::metadata(&target).await.map(|m| m.is_file()).unwrap_or(false) {
+        return Err(format!("not a model file: {path}"));
+    }
+
+    let isLink = fs::symlink_metadata(&location)
+        .await
+        .map(|m| m.file_type().is_symlink())
+        .unwrap_or(false);
+    fs::remove_file(&target)
+        .await
+        .map_err(|e| format!("failed to delete {}: {e}", target.display()))?;
+    if isLink {
+        fs::remove_file(&location)
+            .await
+            .map_err(|e| format!("failed to delete {}: {e}", location.display()))?;
+    }
+    Ok(())
+}
+
+/// Walk `dir` for files with a model extension, at most `maxDepth` directories down and
+/// skipping paths matching `exclude`. Symlinks are followed, but every file and directory
+/// is visited once per `(device, inode)` in `seen`, so a HuggingFace snapshot linking into
+/// `blobs/` isn't counted twice and symlink loops terminate.
+async fn scan_dir(
+    dir: &str,
+    maxDepth: usize,
+    exclude: &[glob::Pattern],
+    entries: &mut Vec<ModelEntry>,
+    seen: &mut HashSet<(u64, u64)>,
+    watched: &mut Vec<(PathBuf, Stamp)>,
+) -> Result<(), String> {
+    let root = std::path::PathBuf::from(dir);
+    match fs::metadata(&root).await {
+        Ok(m) if !seen.insert((m.dev(), m.ino())) => return Ok(()),
+        _ => {}
+    }
+    let mut stack = vec![(root, 0)];
+
+    while let Some((path, depth)) = stack.pop() {
         let mut readDir = match fs::read_dir(&path).await {
             Ok(rd) => rd,
             Err(_) => continue,
@@ -36,8 +243,18 @@ async fn scan_dir(dir: &str, entries: &mut Vec<ModelEntry>) -> Result<(), String
 
         while let Ok(Some(entry)) = readDir.next_entry().await {
             let entryPath = entry.path();
-            if entryPath.is_dir() {
-                stack.push(entryPath);
+            let metadata = match fs::metadata(&entryPath).await {
+                Ok(m) => m,
+                Err(_) => continue,
+            };
+            if metadata.is_dir() {
+                if depth < maxDepth
+                    && !is_excluded(&entryPath, true, exclude)
+                    && seen.insert((metadata.dev(), metadata.ino()))
+                {
+                    watched.push((entryPath.clone(), stamp_of(&metadata)));
+                    stack.push((entryPath, depth + 1));
+                }
                 continue;
             }
 
@@ -46,27 +263,21 @@ async fn scan_dir(dir: &str, entries: &mut Vec<ModelEntry>) -> Result<(), String
                 .and_then(|e| e.to_str())
                 .unwrap_or("");
 
-            if !MODEL_EXTENSIONS.contains(&ext) {
+            if !MODEL_EXTENSIONS.contains(&ext)
+                || is_excluded(&entryPath, false, exclude)
+                || !seen.insert((metadata.dev(), metadata.ino()))
+            {
                 continue;
             }
 
-            let metadata = match fs::metadata(&entryPath).await {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-
             let modified = metadata
                 .modified()
                 .ok()
-                .and_then(|t| {
-                    t.duration_since(std::time::UNIX_EPOCH)
-                        .ok()
-                        .map(|d| {
-                            let secs = d.as_secs();
-                            format!("{secs}")
-                        })
-                })
-                .unwrap_or_default();
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+
+            watched.push((entryPath.clone(), stamp_of(&metadata)));
+            let header = read_header(&entryPath, ext).await;
 
             entries.push(ModelEntry {
                 name: entryPath
@@ -78,9 +289,614 @@ async fn scan_dir(dir: &str, entries: &mut Vec<ModelEntry>) -> Result<(), String
                 size_bytes: metadata.len(),
                 format: ext.to_uppercase(),
                 modified,
+                model_name: header.name,
+                architecture: header.architecture,
+                quantization: header.quantization,
+                param_count: header.param_count,
+                dtype: header.dtype,
+                tensor_count: header.tensor_count,
             });
         }
     }
 
     Ok(())
 }
+
+const OLLAMA_MODEL_MEDIA_TYPE: &str = "application/vnd.ollama.image.model";
+const OLLAMA_DEFAULT_REGISTRY: &str = "registry.ollama.ai";
+
+#[derive(serde::Deserialize)]
+struct OllamaManifest {
+    config: Option<OllamaLayer>,
+    #[serde(default)]
+    layers: Vec<OllamaLayer>,
+}
+
+#[derive(serde::Deserialize)]
+struct OllamaLayer {
+    #[serde(rename = "mediaType", default)]
+    media_type: String,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+}
+
+/// List the tagged models in an Ollama store. Each manifest under
+/// `manifests/<registry>/<namespace>/<model>/<tag>` becomes one entry, named the way
+/// `ollama list` shows it and sized as the sum of its blobs. The GGUF header is read from
+/// the model layer's blob.
+async fn scan_ollama(
+    root: &Path,
+    entries: &mut Vec<ModelEntry>,
+    watched: &mut Vec<(PathBuf, Stamp)>,
+) -> Result<(), String> {
+    let manifestsDir = root.join("manifests");
+    let blobsDir = root.join("blobs");
+    let mut stack = vec![manifestsDir.clone()];
+
+    while let Some(path) = stack.pop() {
+        let mut readDir = match fs::read_dir(&path).await {
+            Ok(rd) => rd,
+            Err(_) => continue,
+        };
+
+        while let Ok(Some(entry)) = readDir.next_entry().await {
+            let manifestPath = entry.path();
+            if manifestPath.is_dir() {
+                watched.push((manifestPath.clone(), stamp(&manifestPath).await));
+                stack.push(manifestPath);
+                continue;
+            }
+
+            let Some(name) = manifestPath
+                .strip_prefix(&manifestsDir)
+                .ok()
+                .and_then(ollama_model_name)
+            else {
+                continue;
+            };
+            let manifestMetadata = fs::metadata(&manifestPath).await.ok();
+            watched.push((manifestPath.clone(), manifestMetadata.as_ref().and_then(stamp_of)));
+            let manifest: OllamaManifest = match fs::read(&manifestPath)
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|raw| serde_json::from_slice(&raw).map_err(|e| e.to_string()))
+            {
+                Ok(m) => m,
+                Err(e) => {
+                    warn!("ollama manifest {}: {e}", manifestPath.display());
+                    continue;
+                }
+            };
+
+            // Blobs are stored as `sha256-<hex>` for a digest of `sha256:<hex>`
+            let mut sizeBytes = 0;
+            let mut modelBlob = None;
+            for layer in manifest.config.iter().chain(&manifest.layers) {
+                let blobPath = blobsDir.join(layer.digest.replace(':', "-"));
+                let blob = fs::metadata(&blobPath).await.ok();
+                sizeBytes += blob.as_ref().map_or(layer.size, |m| m.len());
+                watched.push((blobPath.clone(), blob.as_ref().and_then(stamp_of)));
+                if layer.media_type == OLLAMA_MODEL_MEDIA_TYPE {
+                    modelBlob = Some(blobPath);
+                }
+            }
+
+            let modified = manifestMetadata
+                .and_then(|m| m.modified().ok())
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs());
+
+            let header = match &modelBlob {
+                Some(blob) if blob.is_file() => read_header(blob, "gguf").await,
+                _ => ModelHeader::default(),
+            };
+
+            entries.push(ModelEntry {
+                name,
+                path: modelBlob
+                    .unwrap_or(manifestPath)
+                    .to_string_lossy()
+                    .to_string(),
+                size_bytes: sizeBytes,
+                format: "GGUF".to_string(),
+                modified,
+                model_name: header.name,
+                architecture: header.architecture,
+                quantization: header.quantization,
+                param_count: header.param_count,
+                dtype: header.dtype,
+                tensor_count: header.tensor_count,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// `registry.ollama.ai/library/llama3/8b` -> `llama3:8b`; other namespaces and registries
+/// are kept, e.g. `user/model:tag` or `host/user/model:tag`.
+fn ollama_model_name(relative: &Path) -> Option<String> {
+    let parts: Vec<&str> = relative.iter().map(|p| p.to_str()).collect::<Option<_>>()?;
+    let [registry, namespace, model, tag] = parts.as_slice() else {
+        return None;
+    };
+    Some(match (*registry, *namespace) {
+        (OLLAMA_DEFAULT_REGISTRY, "library") => format!("{model}:{tag}"),
+        (OLLAMA_DEFAULT_REGISTRY, _) => format!("{namespace}/{model}:{tag}"),
+        _ => format!("{registry}/{namespace}/{model}:{tag}"),
+    })
+}
+
+const GGUF_MAGIC: &[u8; 4] = b"GGUF";
+// Sanity limits so a corrupt or truncated header fails fast instead of allocating wildly
+const GGUF_MAX_STRING: u64 = 16 * 1024 * 1024;
+const GGUF_MAX_ITEMS: u64 = 100_000_000;
+const GGUF_MAX_DIMS: u32 = 8;
+
+/// Details read from a model file's header, for formats that have one.
+#[derive(Debug, Default, PartialEq)]
+struct ModelHeader {
+    name: Option<String>,
+    architecture: Option<String>,
+    quantization: Option<String>,
+    param_count: Option<u64>,
+    dtype: Option<String>,
+    tensor_count: Option<u64>,
+}
+
+/// Parse the header of a GGUF or safetensors file on a blocking thread. Other formats,
+/// and files that are unreadable or malformed, get no metadata but still show up in the
+/// listing; failures are logged.
+async fn read_header(path: &Path, ext: &str) -> ModelHeader {
+    let parse: fn(&mut BufReader<std::fs::File>) -> Result<ModelHeader, String> = match ext {
+        "gguf" => parse_gguf_header,
+        "safetensors" => parse_safetensors_header,
+        _ => return ModelHeader::default(),
+    };
+    let path = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        parse(&mut BufReader::new(file)).map_err(|e| format!("{}: {e}", path.display()))
+    })
+    .await;
+    match result {
+        Ok(Ok(header)) => header,
+        Ok(Err(e)) => {
+            warn!("model header: {e}");
+            ModelHeader::default()
+        }
+        Err(e) => {
+            warn!("model header task failed: {e}");
+            ModelHeader::default()
+        }
+    }
+}
+
+/// Read the metadata key-value block and tensor infos at the start of a GGUF file,
+/// seeking past large values (tokenizer vocabularies) rather than reading them.
+/// Tensor data is never touched.
+fn parse_gguf_header<R: Read + Seek>(r: &mut R) -> Result<ModelHeader, String> {
+    let mut magic = [0u8; 4];
+    r.read_exact(&mut magic).map_err(|e| e.to_string())?;
+    if &magic != GGUF_MAGIC {
+        return Err("not a GGUF file".into());
+    }
+    let version = read_u32(r)?;
+    // Version 1 used 32-bit counts and string lengths; 2 and later use 64-bit
+    let wide = version >= 2;
+    let read_count = |r: &mut R| if wide { read_u64(r) } else { read_u32(r).map(u64::from) };
+
+    let tensorCount = read_count(r)?;
+    let kvCount = read_count(r)?;
+    if tensorCount > GGUF_MAX_ITEMS || kvCount > GGUF_MAX_ITEMS {
+        return Err(format!("implausible GGUF counts: {tensorCount} tensors, {kvCount} keys"));
+    }
+
+    let mut header = ModelHeader {
+        tensor_count: Some(tensorCount),
+        ..Default::default()
+    };
+    let mut fileType = None;
+    for _ in 0..kvCount {
+        let key = read_string(r, wide)?;
+        let valueType = read_u32(r)?;
+        match (key.as_str(), valueType) {
+            ("general.name", GGUF_TYPE_STRING) => header.name = Some(read_string(r, wide)?),
+            ("general.architecture", GGUF_TYPE_STRING) => {
+                header.architecture = Some(read_string(r, wide)?)
+            }
+            ("general.file_type", GGUF_TYPE_UINT32) => fileType = Some(read_u32(r)?),
+            ("general.parameter_count", GGUF_TYPE_UINT64) => header.param_count = Some(read_u64(r)?),
+            _ => skip_value(r, valueType, wide)?,
+        }
+    }
+    header.quantization = fileType.and_then(file_type_name).map(str::to_string);
+
+    // Older files don't record a parameter count; the tensor shapes that follow the
+    // metadata give it exactly
+    if header.param_count.is_none() {
+        let mut total: u64 = 0;
+        for _ in 0..tensorCount {
+            read_string(r, wide)?;
+            let dims = read_u32(r)?;
+            if dims > GGUF_MAX_DIMS {
+                return Err(format!("implausible tensor rank {dims}"));
+            }
+            let mut elements: u64 = 1;
+            for _ in 0..dims {
+                elements = elements.saturating_mul(read_count(r)?);
+            }
+            // Element type and data offset
+            read_u32(r)?;
+            read_u64(r)?;
+            total = total.saturating_add(elements);
+        }
+        header.param_count = (total > 0).then_some(total);
+    }
+
+    Ok(header)
+}
+
+// Real headers are a few KB to a few MB; anything past this is a corrupt length prefix
+const SAFETENSORS_MAX_HEADER: u64 = 100 * 1024 * 1024;
+
+/// Read the JSON header of a safetensors file: an 8-byte little-endian length, then a map
+/// of tensor name to `{dtype, shape, data_offsets}` plus an optional `__metadata__` entry.
+fn parse_safetensors_header<R: Read>(r: &mut R) -> Result<ModelHeader, String> {
+    #[derive(serde::Deserialize)]
+    struct TensorInfo {
+        dtype: String,
+        shape: Vec<u64>,
+    }
+
+    let len = read_u64(r)?;
+    if len == 0 || len > SAFETENSORS_MAX_HEADER {
+        return Err(format!("implausible safetensors header length {len}"));
+    }
+    let mut buf = vec![0u8; len as usize];
+    r.read_exact(&mut buf).map_err(|e| format!("truncated safetensors header: {e}"))?;
+    let mut entries: std::collections::HashMap<String, serde_json::Value> =
+        serde_json::from_slice(&buf).map_err(|e| format!("invalid safetensors header: {e}"))?;
+    entries.remove("__metadata__");
+
+    let mut perDtype: std::collections::BTreeMap<String, u64> = Default::default();
+    let mut tensors: u64 = 0;
+    for value in entries.into_values() {
+        let Ok(info) = serde_json::from_value::<TensorInfo>(value) else {
+            continue;
+        };
+        let elements = info.shape.iter().fold(1u64, |acc, d| acc.saturating_mul(*d));
+        *perDtype.entry(info.dtype).or_default() += elements;
+        tensors += 1;
+    }
+
+    // BTreeMap order makes ties go to the alphabetically first dtype
+    let dominant = perDtype
+        .iter()
+        .fold(None::<(&String, u64)>, |best, (dtype, &count)| match best {
+            Some((_, bestCount)) if bestCount >= count => best,
+            _ => Some((dtype, count)),
+        })
+        .map(|(dtype, _)| dtype.clone());
+    let total: u64 = perDtype.values().sum();
+
+    Ok(ModelHeader {
+        dtype: dominant,
+        tensor_count: Some(tensors),
+        param_count: (total > 0).then_some(total),
+        ..Default::default()
+    })
+}
+
+const GGUF_TYPE_UINT32: u32 = 4;
+const GGUF_TYPE_STRING: u32 = 8;
+const GGUF_TYPE_ARRAY: u32 = 9;
+const GGUF_TYPE_UINT64: u32 = 10;
+
+/// Size in bytes of a fixed-width GGUF value type.
+fn fixed_size(valueType: u32) -> Option<i64> {
+    match valueType {
+        0 | 1 | 7 => Some(1),  // u8, i8, bool
+        2 | 3 => Some(2),      // u16, i16
+        4..=6 => Some(4),      // u32, i32, f32
+        10..=12 => Some(8),    // u64, i64, f64
+        _ => None,
+    }
+}
+
+fn skip_value<R: Read + Seek>(r: &mut R, valueType: u32, wide: bool) -> Result<(), String> {
+    if let Some(size) = fixed_size(valueType) {
+        return skip(r, size);
+    }
+    match valueType {
+        GGUF_TYPE_STRING => {
+            let len = read_len(r, wide)?;
+            skip(r, len as i64)
+        }
+        GGUF_TYPE_ARRAY => {
+            let itemType = read_u32(r)?;
+            let count = if wide { read_u64(r)? } else { u64::from(read_u32(r)?) };
+            if count > GGUF_MAX_ITEMS {
+                return Err(format!("implausible GGUF array length {count}"));
+            }
+            match fixed_size(itemType) {
+                Some(size) => skip(r, size * count as i64),
+                None => (0..count).try_for_each(|_| skip_value(r, itemType, wide)),
+            }
+        }
+        other => Err(format!("unknown GGUF value type {other}")),
+    }
+}
+
+/// `seek_relative` keeps `BufReader`'s buffer when the target is inside it, which matters
+/// for vocabularies of 100k+ short strings.
+fn skip<R: Seek>(r: &mut R, bytes: i64) -> Result<(), String> {
+    r.seek_relative(bytes).map_err(|e| e.to_string())
+}
+
+fn read_u32<R: Read>(r: &mut R) -> Result<u32, String> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64<R: Read>(r: &mut R) -> Result<u64, String> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_len<R: Read>(r: &mut R, wide: bool) -> Result<u64, String> {
+    let len = if wide { read_u64(r)? } else { u64::from(read_u32(r)?) };
+    if len > GGUF_MAX_STRING {
+        return Err(format!("implausible GGUF string length {len}"));
+    }
+    Ok(len)
+}
+
+fn read_string<R: Read>(r: &mut R, wide: bool) -> Result<String, String> {
+    let len = read_len(r, wide)?;
+    let mut buf = vec![0u8; len as usize];
+    r.read_exact(&mut buf).map_err(|e| e.to_string())?;
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+/// llama.cpp's `llama_ftype` names for `general.file_type`.
+fn file_type_name(fileType: u32) -> Option<&'static str> {
+    Some(match fileType {
+        0 => "F32",
+        1 => "F16",
+        2 => "Q4_0",
+        3 => "Q4_1",
+        7 => "Q8_0",
+        8 => "Q5_0",
+        9 => "Q5_1",
+        10 => "Q2_K",
+        11 => "Q3_K_S",
+        12 => "Q3_K_M",
+        13 => "Q3_K_L",
+        14 => "Q4_K_S",
+        15 => "Q4_K_M",
+        16 => "Q5_K_S",
+        17 => "Q5_K_M",
+        18 => "Q6_K",
+        19 => "IQ2_XXS",
+        20 => "IQ2_XS",
+        21 => "Q2_K_S",
+        22 => "IQ3_XS",
+        23 => "IQ3_XXS",
+        24 => "IQ1_S",
+        25 => "IQ4_NL",
+        26 => "IQ3_S",
+        27 => "IQ3_M",
+        28 => "IQ2_S",
+        29 => "IQ2_M",
+        30 => "IQ4_XS",
+        31 => "IQ1_M",
+        32 => "BF16",
+        36 => "TQ1_0",
+        37 => "TQ2_0",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn string(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(&(s.len() as u64).to_le_bytes());
+        buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn tensor(buf: &mut Vec<u8>, name: &str, dims: &[u64]) {
+        string(buf, name);
+        buf.extend_from_slice(&(dims.len() as u32).to_le_bytes());
+        for d in dims {
+            buf.extend_from_slice(&d.to_le_bytes());
+        }
+        buf.extend_from_slice(&0u32.to_le_bytes());
+        buf.extend_from_slice(&0u64.to_le_bytes());
+    }
+
+    #[test]
+    fn reads_gguf_metadata_and_counts_parameters() {
+        let mut buf = b"GGUF".to_vec();
+        buf.extend_from_slice(&3u32.to_le_bytes());
+        buf.extend_from_slice(&2u64.to_le_bytes()); // tensors
+        buf.extend_from_slice(&4u64.to_le_bytes()); // keys
+
+        string(&mut buf, "general.architecture");
+        buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+        string(&mut buf, "llama");
+
+        // A vocabulary-like array of strings that has to be skipped
+        string(&mut buf, "tokenizer.ggml.tokens");
+        buf.extend_from_slice(&GGUF_TYPE_ARRAY.to_le_bytes());
+        buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+        buf.extend_from_slice(&2u64.to_le_bytes());
+        string(&mut buf, "<s>");
+        string(&mut buf, "</s>");
+
+        string(&mut buf, "general.name");
+        buf.extend_from_slice(&GGUF_TYPE_STRING.to_le_bytes());
+        string(&mut buf, "Tiny Llama");
+
+        string(&mut buf, "general.file_type");
+        buf.extend_from_slice(&GGUF_TYPE_UINT32.to_le_bytes());
+        buf.extend_from_slice(&15u32.to_le_bytes());
+
+        tensor(&mut buf, "token_embd.weight", &[64, 32]);
+        tensor(&mut buf, "output_norm.weight", &[64]);
+
+        let header = parse_gguf_header(&mut Cursor::new(buf)).unwrap();
+        assert_eq!(
+            header,
+            ModelHeader {
+                name: Some("Tiny Llama".into()),
+                architecture: Some("llama".into()),
+                quantization: Some("Q4_K_M".into()),
+                param_count: Some(64 * 32 + 64),
+                tensor_count: Some(2),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn reads_safetensors_dtype_and_counts() {
+        let json = br#"{"__metadata__":{"format":"pt"},
+            "a.weight":{"dtype":"BF16","shape":[1024,64],"data_offsets":[0,131072]},
+            "a.bias":{"dtype":"F32","shape":[64],"data_offsets":[131072,131328]},
+            "b.weight":{"dtype":"BF16","shape":[64,64],"data_offsets":[131328,139520]}}"#;
+        let mut buf = (json.len() as u64).to_le_bytes().to_vec();
+        buf.extend_from_slice(json);
+
+        let header = parse_safetensors_header(&mut Cursor::new(buf)).unwrap();
+        assert_eq!(header.dtype.as_deref(), Some("BF16"));
+        assert_eq!(header.tensor_count, Some(3));
+        assert_eq!(header.param_count, Some(1024 * 64 + 64 + 64 * 64));
+    }
+
+    #[test]
+    fn truncated_safetensors_header_is_an_error() {
+        let mut buf = 4096u64.to_le_bytes().to_vec();
+        buf.extend_from_slice(b"{\"a\":");
+        assert!(parse_safetensors_header(&mut Cursor::new(buf)).is_err());
+    }
+
+    #[test]
+    fn names_ollama_manifests_like_ollama_list() {
+        let name = |p: &str| ollama_model_name(Path::new(p));
+        assert_eq!(name("registry.ollama.ai/library/llama3/8b").as_deref(), Some("llama3:8b"));
+        assert_eq!(name("registry.ollama.ai/alice/tiny/latest").as_deref(), Some("alice/tiny:latest"));
+        assert_eq!(name("hf.co/org/repo/Q4_K_M").as_deref(), Some("hf.co/org/repo:Q4_K_M"));
+        assert_eq!(name("registry.ollama.ai/library/llama3"), None);
+    }
+
+    #[tokio::test]
+    async fn symlinked_models_are_counted_once() {
+        let dir = std::env::temp_dir().join(format!("spark-models-{}", std::process::id()));
+        let snapshot = dir.join("snapshots/abc");
+        std::fs::create_dir_all(&snapshot).unwrap();
+        std::fs::write(dir.join("model.bin"), [0u8; 16]).unwrap();
+        std::os::unix::fs::symlink(dir.join("model.bin"), snapshot.join("model.bin")).unwrap();
+        // A loop back to the root must not be walked again
+        std::os::unix::fs::symlink(&dir, snapshot.join("loop")).unwrap();
+
+        let mut entries = Vec::new();
+        let result = scan_dir(dir.to_str().unwrap(), 6, &[], &mut entries, &mut HashSet::new(), &mut Vec::new()).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].size_bytes, 16);
+    }
+
+    #[tokio::test]
+    async fn scan_stops_at_max_depth_and_skips_excluded() {
+        let dir = std::env::temp_dir().join(format!("spark-depth-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("a/b/c")).unwrap();
+        std::fs::create_dir_all(dir.join("repo/.git/lfs")).unwrap();
+        std::fs::write(dir.join("top.gguf"), b"x").unwrap();
+        std::fs::write(dir.join("a/b/c/deep.gguf"), b"x").unwrap();
+        std::fs::write(dir.join("repo/.git/lfs/object.bin"), b"x").unwrap();
+        let exclude = ModelOptions::default().exclude_patterns();
+
+        let mut names = Vec::new();
+        for maxDepth in [0, 2, 3] {
+            let mut entries = Vec::new();
+            let root = dir.to_str().unwrap();
+            scan_dir(root, maxDepth, &exclude, &mut entries, &mut HashSet::new(), &mut Vec::new())
+                .await
+                .unwrap();
+            let mut found: Vec<String> = entries.into_iter().map(|e| e.name).collect();
+            found.sort();
+            names.push(found);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(names[0], ["top"]);
+        assert_eq!(names[1], ["top"]);
+        assert_eq!(names[2], ["deep", "top"]);
+    }
+
+    #[tokio::test]
+    async fn cached_scan_is_replaced_when_models_change() {
+        let dir = std::env::temp_dir().join(format!("spark-rescan-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("sub/a.gguf"), b"x").unwrap();
+        let options = ModelOptions {
+            dirs: vec![dir.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+
+        let first = collect(&options).await;
+        let again = collect(&options).await;
+        std::fs::write(dir.join("sub/b.gguf"), b"x").unwrap();
+        let added = collect(&options).await;
+        std::fs::write(dir.join("sub/a.gguf"), b"xyz").unwrap();
+        let rewritten = collect(&options).await;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(fingerprint(&again), fingerprint(&first));
+        assert_eq!(added.len(), 2);
+        assert_ne!(fingerprint(&added), fingerprint(&first));
+        assert_eq!(rewritten.iter().map(|e| e.size_bytes).sum::<u64>(), 4);
+    }
+
+    #[tokio::test]
+    async fn deletes_only_inside_model_dirs() {
+        let dir = std::env::temp_dir().join(format!("spark-delete-{}", std::process::id()));
+        let models = dir.join("models");
+        std::fs::create_dir_all(&models).unwrap();
+        std::fs::write(dir.join("outside.gguf"), b"x").unwrap();
+        std::fs::write(models.join("inside.gguf"), b"x").unwrap();
+        std::os::unix::fs::symlink(dir.join("outside.gguf"), models.join("escape.gguf")).unwrap();
+        let options = ModelOptions {
+            dirs: vec![models.to_string_lossy().to_string()],
+            ..Default::default()
+        };
+        let path = |p: &Path| p.to_string_lossy().to_string();
+
+        let outside = delete(&options, &path(&dir.join("outside.gguf"))).await;
+        let escape = delete(&options, &path(&models.join("escape.gguf"))).await;
+        let traversal = delete(&options, &path(&models.join("../outside.gguf"))).await;
+        let inside = delete(&options, &path(&models.join("inside.gguf"))).await;
+        let outsideKept = dir.join("outside.gguf").exists();
+        let insideGone = !models.join("inside.gguf").exists();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(outside.is_err() && escape.is_err() && traversal.is_err());
+        assert!(outsideKept);
+        assert!(inside.is_ok() && insideGone);
+    }
+
+    #[test]
+    fn rejects_non_gguf_files() {
+        assert!(parse_gguf_header(&mut Cursor::new(b"PK\x03\x04rest".to_vec())).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use serde::Deserialize;
+use spark_types::NetInterfaceMetrics;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::RATE_SAMPLE_INTERVAL;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct NetOptions {
+    /// Interfaces whose name starts with one of these are left out.
+    pub exclude: Vec<String>,
+}
+
+impl Default for NetOptions {
+    fn default() -> Self {
+        Self {
+            exclude: vec!["lo".into()],
+        }
+    }
+}
+
+/// Received and sent byte counters for one interface.
+#[derive(Debug, PartialEq)]
+struct NetCounters {
+    rx: u64,
+    tx: u64,
+}
+
+/// Per-interface throughput, from two reads of /proc/net/dev. Empty when it can't be read.
+pub async fn collect(options: &NetOptions) -> Vec<NetInterfaceMetrics> {
+    match sample_rates(options).await {
+        Ok(interfaces) => interfaces,
+        Err(e) => {
+            warn!("network throughput unavailable: {e}");
+            Vec::new()
+        }
+    }
+}
+
+async fn sample_rates(options: &NetOptions) -> Result<Vec<NetInterfaceMetrics>, String> {
+    let before: HashMap<String, NetCounters> = read_proc_net_dev().await?.into_iter().collect();
+    tokio::time::sleep(RATE_SAMPLE_INTERVAL).await;
+    let after = read_proc_net_dev().await?;
+
+    let seconds = RATE_SAMPLE_INTERVAL.as_secs_f64();
+    Ok(after
+        .into_iter()
+        .filter(|(name, _)| !options.exclude.iter().any(|prefix| name.starts_with(prefix.as_str())))
+        .filter_map(|(name, now)| {
+            let then = before.get(&name)?;
+            Some(NetInterfaceMetrics {
+                rx_bytes_per_sec: now.rx.saturating_sub(then.rx) as f64 / seconds,
+                tx_bytes_per_sec: now.tx.saturating_sub(then.tx) as f64 / seconds,
+                rx_bytes_total: now.rx,
+                tx_bytes_total: now.tx,
+                name,
+            })
+        })
+        .collect())
+}
+
+async fn read_proc_net_dev() -> Result<Vec<(String, NetCounters)>, String> {
+    let contents = tokio::fs::read_to_string("/proc/net/dev")
+        .await
+        .map_err(|e| format!("failed to read /proc/net/dev: {e}"))?;
+    Ok(parse_net_dev(&contents))
+}
+
+/// Interface counters from /proc/net/dev, in file order. The first two lines are headers.
+fn parse_net_dev(contents: &str) -> Vec<(String, NetCounters)> {
+    contents
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            // name: rx_bytes packets errs drop fifo frame compressed multicast tx_bytes ...
+            let (name, counters) = line.split_once(':')?;
+            let fields: Vec<&str> = counters.split_whitespace().collect();
+            if fields.len() < 9 {
+                return None;
+            }
+            Some((
+                name.trim().to_string(),
+                NetCounters {
+                    rx: fields[0].parse().unwrap_or(0),
+                    tx: fields[8].parse().unwrap_or(0),
+                },
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proc_net_dev() {
+        let contents = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  123456     100    0    0    0     0          0         0   123456     100    0    0    0     0       0          0
+  eth0:98765432   54321    0    0    0     0          0        12 12345678   23456    0    0    0     0       0          0
+";
+        assert_eq!(
+            parse_net_dev(contents),
+            [
+                ("lo".to_string(), NetCounters { rx: 123456, tx: 123456 }),
+                ("eth0".to_string(), NetCounters { rx: 98765432, tx: 12345678 }),
+            ]
+        );
+    }
+}
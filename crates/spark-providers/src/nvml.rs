@@ -0,0 +1,146 @@
+//! NVIDIA GPU metrics read through NVML, built with the `nvml` feature. This is the
+//! library nvidia-smi itself uses, so it reports the same values without spawning the
+//! three nvidia-smi processes each collection otherwise costs.
+
+use nvml_wrapper::enum_wrappers::device::{Clock, TemperatureSensor};
+use nvml_wrapper::enums::device::UsedGpuMemory;
+use nvml_wrapper::{Device, Nvml};
+use spark_types::{GpuMetrics, GpuProcess};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
+
+use crate::gpu::{enrich_from_proc, read_passwd_users, read_proc_meminfo_total_mib};
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+/// Longest process name NVML is asked for.
+const PROCESS_NAME_LEN: usize = 256;
+/// Per-process utilization samples older than this are ignored, like a single pmon sample.
+const PROCESS_SAMPLE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Loaded on first use. Loading `libnvidia-ml.so` and initialising the driver is slow,
+/// and a failure (no driver, no library) won't fix itself, so either result is kept.
+static NVML: OnceLock<Result<Nvml, String>> = OnceLock::new();
+
+fn nvml() -> Result<&'static Nvml, String> {
+    NVML.get_or_init(|| match Nvml::init() {
+        Ok(nvml) => {
+            info!("reading GPU metrics through NVML");
+            Ok(nvml)
+        }
+        Err(e) => {
+            warn!("NVML unavailable, falling back to nvidia-smi: {e}");
+            Err(format!("NVML unavailable: {e}"))
+        }
+    })
+    .as_ref()
+    .map_err(Clone::clone)
+}
+
+/// Metrics for every NVIDIA GPU, in the same shape `nvidia-smi` collection produces.
+pub async fn collect() -> Result<Vec<GpuMetrics>, String> {
+    // NVML calls block, sometimes for tens of milliseconds on a busy device
+    let mut gpus = tokio::task::spawn_blocking(read_devices)
+        .await
+        .map_err(|e| format!("NVML query failed: {e}"))??;
+
+    let users = read_passwd_users().await;
+    let mut systemTotalMib = None;
+    for gpu in &mut gpus {
+        for process in &mut gpu.processes {
+            enrich_from_proc(process, &users).await;
+        }
+        // Same fallback as nvidia-smi's [N/A] memory: system memory, used by GPU processes
+        if gpu.unified_memory {
+            if systemTotalMib.is_none() {
+                systemTotalMib = Some(read_proc_meminfo_total_mib().await.unwrap_or(0));
+            }
+            gpu.memory_total_mib = systemTotalMib.unwrap_or(0);
+            gpu.memory_used_mib = gpu.processes.iter().map(|p| p.memory_mib).sum();
+        }
+    }
+    Ok(gpus)
+}
+
+fn read_devices() -> Result<Vec<GpuMetrics>, String> {
+    let nvml = nvml()?;
+    let count = nvml
+        .device_count()
+        .map_err(|e| format!("NVML device count failed: {e}"))?;
+    if count == 0 {
+        return Err("NVML found no GPUs".into());
+    }
+
+    let mut gpus = (0..count)
+        .map(|index| {
+            let device = nvml
+                .device_by_index(index)
+                .map_err(|e| format!("NVML failed to open GPU {index}: {e}"))?;
+            Ok(read_device(nvml, index, &device))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    gpus.sort_by_key(|g| g.index);
+    Ok(gpus)
+}
+
+/// One device's metrics. Readings the device doesn't support (nvidia-smi's [N/A]) come
+/// back as errors and are mapped to the same defaults the CSV parser uses.
+fn read_device(nvml: &Nvml, index: u32, device: &Device) -> GpuMetrics {
+    let memory = device.memory_info().ok();
+    let smUtil = process_sm_util(device);
+    let processes = device
+        .running_compute_processes()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|process| GpuProcess {
+            pid: process.pid,
+            name: nvml.sys_process_name(process.pid, PROCESS_NAME_LEN).unwrap_or_default(),
+            memory_mib: match process.used_gpu_memory {
+                UsedGpuMemory::Used(bytes) => bytes / BYTES_PER_MIB,
+                UsedGpuMemory::Unavailable => 0,
+            },
+            sm_util_pct: smUtil.get(&process.pid).copied(),
+            cmdline: String::new(),
+            user: String::new(),
+        })
+        .collect();
+
+    GpuMetrics {
+        index: device.index().unwrap_or(index),
+        uuid: device.uuid().unwrap_or_default(),
+        vendor: "nvidia".into(),
+        name: device.name().unwrap_or_default(),
+        utilization_pct: device.utilization_rates().map_or(0.0, |u| u.gpu as f32),
+        temperature_c: device.temperature(TemperatureSensor::Gpu).unwrap_or(0),
+        memory_used_mib: memory.as_ref().map_or(0, |m| m.used / BYTES_PER_MIB),
+        memory_total_mib: memory.as_ref().map_or(0, |m| m.total / BYTES_PER_MIB),
+        power_draw_w: device.power_usage().map_or(0.0, |mw| mw as f32 / 1000.0),
+        power_limit_w: device.enforced_power_limit().map_or(0.0, |mw| mw as f32 / 1000.0),
+        fan_speed_pct: device.fan_speed(0).ok(),
+        sm_clock_mhz: device.clock_info(Clock::SM).ok(),
+        mem_clock_mhz: device.clock_info(Clock::Memory).ok(),
+        encoder_util_pct: device.encoder_utilization().ok().map(|u| u.utilization as f32),
+        decoder_util_pct: device.decoder_utilization().ok().map(|u| u.utilization as f32),
+        // Unified-memory GPUs like the GB10 have no framebuffer for NVML to report
+        unified_memory: memory.is_none(),
+        processes,
+    }
+}
+
+/// Latest SM utilization per PID over the last [`PROCESS_SAMPLE_WINDOW`]. Empty where
+/// the device doesn't keep per-process samples.
+fn process_sm_util(device: &Device) -> HashMap<u32, f32> {
+    let since = SystemTime::now()
+        .checked_sub(PROCESS_SAMPLE_WINDOW)
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_micros() as u64);
+    let mut latest: HashMap<u32, (u64, f32)> = HashMap::new();
+    for sample in device.process_utilization_stats(since).unwrap_or_default() {
+        let entry = latest.entry(sample.pid).or_insert((0, 0.0));
+        if sample.timestamp >= entry.0 {
+            *entry = (sample.timestamp, sample.sm_util as f32);
+        }
+    }
+    latest.into_iter().map(|(pid, (_, sm))| (pid, sm)).collect()
+}
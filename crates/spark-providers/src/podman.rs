@@ -0,0 +1,66 @@
+use spark_types::ContainerActionResult;
+use tokio::process::Command;
+use tracing::warn;
+
+use crate::command::{run_with_timeout, DEFAULT_TIMEOUT};
+use crate::docker::{run_cli_action, ContainerBackend};
+
+/// Label podman sets on containers created from a quadlet or `podman generate systemd` unit.
+const SYSTEMD_UNIT_LABEL: &str = "PODMAN_SYSTEMD_UNIT";
+
+/// Actions with a `systemctl` equivalent; the rest always go to podman.
+const SYSTEMD_ACTIONS: &[&str] = &["start", "stop", "restart"];
+
+/// Podman via the `podman` CLI, optionally deferring to systemd for
+/// containers that a unit owns.
+pub struct Podman {
+    pub systemd: bool,
+}
+
+impl ContainerBackend for Podman {
+    fn program(&self) -> &'static str {
+        "podman"
+    }
+
+    fn runtime_template(&self) -> &'static str {
+        "{{.OCIRuntime}}"
+    }
+
+    async fn run_action(&self, container_id: &str, action: &str) -> ContainerActionResult {
+        if self.systemd && SYSTEMD_ACTIONS.contains(&action) {
+            if let Some(unit) = systemd_unit(container_id).await {
+                return run_cli_action("systemctl", &unit, action).await;
+            }
+        }
+        run_cli_action(self.program(), container_id, action).await
+    }
+}
+
+/// Look up the systemd unit managing a container, if any.
+async fn systemd_unit(container_id: &str) -> Option<String> {
+    let template = format!("{{{{index .Config.Labels \"{SYSTEMD_UNIT_LABEL}\"}}}}");
+    let output = match run_with_timeout(
+        Command::new("podman").args(["inspect", "--format", &template, container_id]),
+        DEFAULT_TIMEOUT,
+    )
+    .await
+    {
+        Ok(o) => o,
+        Err(e) => {
+            warn!("podman inspect: {e}");
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let unit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // Go templates render a missing map key as "<no value>"
+    if unit.is_empty() || unit == "<no value>" {
+        None
+    } else {
+        Some(unit)
+    }
+}
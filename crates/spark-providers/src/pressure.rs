@@ -0,0 +1,25 @@
+/// "some" stall averages from a `/proc/pressure/<resource>` file.
+pub(crate) struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+}
+
+/// Read the `some` line of a PSI file (`cpu`, `memory` or `io`). Returns `None` on
+/// kernels built without PSI or booted with `psi=0`, where the file is missing or unreadable.
+pub(crate) async fn read_some(resource: &str) -> Option<PressureAverages> {
+    let contents = tokio::fs::read_to_string(format!("/proc/pressure/{resource}"))
+        .await
+        .ok()?;
+    // some avg10=0.00 avg60=0.00 avg300=0.00 total=0
+    let line = contents.lines().find(|l| l.starts_with("some "))?;
+    let field = |name: &str| -> Option<f32> {
+        line.split_whitespace()
+            .find_map(|kv| kv.strip_prefix(name)?.strip_prefix('='))?
+            .parse()
+            .ok()
+    };
+    Some(PressureAverages {
+        avg10: field("avg10")?,
+        avg60: field("avg60")?,
+    })
+}
@@ -0,0 +1,158 @@
+use serde_json::{Map, Value};
+use spark_types::GpuMetrics;
+use tokio::process::Command;
+
+use crate::command::{run_with_timeout, DEFAULT_TIMEOUT};
+
+const BYTES_PER_MIB: u64 = 1024 * 1024;
+
+/// Collect AMD GPU metrics from `rocm-smi --json`.
+pub async fn collect() -> Result<Vec<GpuMetrics>, String> {
+    let output = run_with_timeout(
+        Command::new("rocm-smi").args([
+            "--showuse",
+            "--showmeminfo",
+            "vram",
+            "--showtemp",
+            "--showpower",
+            "--showfan",
+            "--showproductname",
+            "--json",
+        ]),
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+
+    if !output.status.success() {
+        return Err(format!("rocm-smi exited with status {}", output.status));
+    }
+
+    let parsed: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("failed to parse rocm-smi JSON: {e}"))?;
+    let cards = parsed.as_object().ok_or("unexpected rocm-smi output")?;
+
+    // Top-level keys are "card0", "card1", ... plus a "system" entry we ignore
+    let mut gpus: Vec<GpuMetrics> = cards
+        .iter()
+        .filter_map(|(key, card)| {
+            let index = key.strip_prefix("card")?.parse::<u32>().ok()?;
+            Some(parse_card(index, card.as_object()?))
+        })
+        .collect();
+
+    if gpus.is_empty() {
+        return Err("rocm-smi reported no GPUs".into());
+    }
+
+    gpus.sort_by_key(|g| g.index);
+    Ok(gpus)
+}
+
+fn parse_card(index: u32, card: &Map<String, Value>) -> GpuMetrics {
+    let name = string_field(card, "Card series")
+        .or_else(|| string_field(card, "Card model"))
+        .unwrap_or_else(|| "AMD GPU".to_string());
+
+    let vramTotal = number_field::<u64>(card, "VRAM Total Memory (B)").unwrap_or(0);
+    let vramUsed = number_field::<u64>(card, "VRAM Total Used Memory (B)").unwrap_or(0);
+
+    // Key names vary between rocm-smi versions and sensors; take the first that matches
+    let temperature = number_field::<f32>(card, "Temperature (Sensor edge)")
+        .or_else(|| number_field::<f32>(card, "Temperature (Sensor junction)"))
+        .unwrap_or(0.0);
+    let power = number_field::<f32>(card, "Average Graphics Package Power")
+        .or_else(|| number_field::<f32>(card, "Current Socket Graphics Package Power"))
+        .unwrap_or(0.0);
+
+    GpuMetrics {
+        index,
+        uuid: format!("card{index}"),
+        vendor: "amd".into(),
+        name,
+        utilization_pct: number_field::<f32>(card, "GPU use (%)").unwrap_or(0.0),
+        temperature_c: temperature.round() as u32,
+        memory_used_mib: vramUsed / BYTES_PER_MIB,
+        memory_total_mib: vramTotal / BYTES_PER_MIB,
+        power_draw_w: power,
+        power_limit_w: number_field::<f32>(card, "Max Graphics Package Power").unwrap_or(0.0),
+        fan_speed_pct: number_field::<f32>(card, "Fan speed (%)").map(|v| v.round() as u32),
+        ..GpuMetrics::default()
+    }
+}
+
+/// Find a value whose key starts with `prefix`, so unit suffixes like " (C)" don't matter.
+fn find_field<'a>(card: &'a Map<String, Value>, prefix: &str) -> Option<&'a Value> {
+    card.iter()
+        .find(|(key, _)| key.starts_with(prefix))
+        .map(|(_, value)| value)
+}
+
+fn string_field(card: &Map<String, Value>, prefix: &str) -> Option<String> {
+    let s = find_field(card, prefix)?.as_str()?.trim();
+    (!s.is_empty()).then(|| s.to_string())
+}
+
+/// rocm-smi reports numbers as strings ("35.0"); accept either form.
+fn number_field<T: std::str::FromStr>(card: &Map<String, Value>, prefix: &str) -> Option<T> {
+    match find_field(card, prefix)? {
+        Value::String(s) => s.trim().parse().ok(),
+        Value::Number(n) => n.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rocm_smi_card() {
+        // Captured from `rocm-smi ... --json` on an MI210 with ROCm 5.7
+        let output = r#"{
+            "card0": {
+                "Temperature (Sensor edge) (C)": "34.0",
+                "Temperature (Sensor junction) (C)": "37.0",
+                "Fan speed (%)": "24.7",
+                "Average Graphics Package Power (W)": "42.0",
+                "Max Graphics Package Power (W)": "300.0",
+                "GPU use (%)": "17",
+                "VRAM Total Memory (B)": "68702699520",
+                "VRAM Total Used Memory (B)": "10737418240",
+                "Card series": "AMD Instinct MI210",
+                "Card model": "0x0c34",
+                "Card vendor": "Advanced Micro Devices, Inc. [AMD/ATI]"
+            },
+            "system": {"Driver version": "6.2.4"}
+        }"#;
+        let parsed: Value = serde_json::from_str(output).unwrap();
+        let card = parse_card(0, parsed["card0"].as_object().unwrap());
+
+        assert_eq!(card.name, "AMD Instinct MI210");
+        assert_eq!(card.vendor, "amd");
+        assert_eq!(card.uuid, "card0");
+        assert_eq!(card.utilization_pct, 17.0);
+        assert_eq!(card.temperature_c, 34);
+        assert_eq!(card.memory_used_mib, 10240);
+        assert_eq!(card.memory_total_mib, 65520);
+        assert_eq!(card.power_draw_w, 42.0);
+        assert_eq!(card.power_limit_w, 300.0);
+        assert_eq!(card.fan_speed_pct, Some(25));
+    }
+
+    #[test]
+    fn falls_back_to_junction_temperature_and_socket_power() {
+        let output = r#"{
+            "Temperature (Sensor junction) (C)": "51.5",
+            "Current Socket Graphics Package Power (W)": "88.0",
+            "Card model": "0x744c"
+        }"#;
+        let parsed: Value = serde_json::from_str(output).unwrap();
+        let card = parse_card(1, parsed.as_object().unwrap());
+
+        assert_eq!(card.name, "0x744c");
+        assert_eq!(card.temperature_c, 52);
+        assert_eq!(card.power_draw_w, 88.0);
+        assert_eq!(card.fan_speed_pct, None);
+        assert_eq!(card.memory_total_mib, 0);
+    }
+}
@@ -0,0 +1,161 @@
+//! systemd services, listed and controlled through `systemctl`.
+
+use serde::Deserialize;
+use spark_types::{ContainerActionResult, ServiceSummary};
+use tokio::process::Command;
+
+use crate::command::{run_with_timeout, DEFAULT_TIMEOUT};
+use crate::docker::run_cli_command;
+
+/// Actions accepted by `run_action`.
+pub const SUPPORTED_ACTIONS: &[&str] = &["start", "stop", "restart"];
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ServiceOptions {
+    /// Allow starting, stopping and restarting units. systemctl needs root (or a polkit
+    /// rule) for these, so they stay off unless the console runs with that access.
+    pub allow_actions: bool,
+}
+
+#[derive(Deserialize)]
+struct ListedUnit {
+    unit: String,
+    load: String,
+    active: String,
+    sub: String,
+    #[serde(default)]
+    description: String,
+}
+
+/// Every service unit systemd has loaded, including inactive ones, sorted by name.
+pub async fn list() -> Result<Vec<ServiceSummary>, String> {
+    let output = run_with_timeout(
+        Command::new("systemctl").args([
+            "list-units",
+            "--type=service",
+            "--all",
+            "--plain",
+            "--no-legend",
+            "--no-pager",
+            "--output=json",
+        ]),
+        DEFAULT_TIMEOUT,
+    )
+    .await?;
+    if !output.status.success() {
+        return Err(systemctl_error(&output.stderr));
+    }
+
+    // systemd before 246 has no JSON output for list-units and prints the plain table
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut services = match serde_json::from_str::<Vec<ListedUnit>>(&stdout) {
+        Ok(units) => units
+            .into_iter()
+            .map(|u| ServiceSummary {
+                name: u.unit,
+                load: u.load,
+                active: u.active,
+                sub: u.sub,
+                description: u.description,
+            })
+            .collect(),
+        Err(_) => parse_plain_units(&stdout),
+    };
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(services)
+}
+
+/// Parse `systemctl list-units --plain --no-legend` rows:
+/// `UNIT LOAD ACTIVE SUB DESCRIPTION...`.
+fn parse_plain_units(text: &str) -> Vec<ServiceSummary> {
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let name = fields.next()?.to_string();
+            let load = fields.next()?.to_string();
+            let active = fields.next()?.to_string();
+            let sub = fields.next()?.to_string();
+            let description = fields.collect::<Vec<_>>().join(" ");
+            Some(ServiceSummary {
+                name,
+                load,
+                active,
+                sub,
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Turn systemctl's stderr into an error, calling out the common case of no reachable
+/// systemd (containers, WSL, or a console without access to the system bus).
+fn systemctl_error(stderr: &[u8]) -> String {
+    let stderr = String::from_utf8_lossy(stderr);
+    let stderr = stderr.trim();
+    if stderr.contains("Failed to connect to bus") || stderr.contains("not been booted with systemd") {
+        format!("systemd is not reachable from the console: {stderr}")
+    } else {
+        format!("systemctl list-units failed: {stderr}")
+    }
+}
+
+/// Whether `unit` is a service unit name that is safe to pass to systemctl.
+pub fn is_valid_unit_name(unit: &str) -> bool {
+    unit.len() > ".service".len()
+        && unit.ends_with(".service")
+        && !unit.starts_with('-')
+        && unit
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '_' | '.' | '-' | '@' | '\\'))
+}
+
+/// Start, stop or restart a service unit, if `services.allow_actions` permits it.
+pub async fn run_action(options: &ServiceOptions, unit: &str, action: &str) -> ContainerActionResult {
+    let failure = |message: String| ContainerActionResult {
+        success: false,
+        message,
+    };
+    if !options.allow_actions {
+        return failure("service actions are disabled (set services.allow_actions = true)".to_string());
+    }
+    if !SUPPORTED_ACTIONS.contains(&action) {
+        return failure(format!("unknown action: {action}"));
+    }
+    if !is_valid_unit_name(unit) {
+        return failure(format!("invalid service unit: {unit}"));
+    }
+
+    // --no-ask-password fails fast instead of waiting on a polkit prompt nobody will see
+    let mut result = run_cli_command("systemctl", &["--no-ask-password", action, unit], action).await;
+    if result.success {
+        result.message = format!("systemctl {action} {unit} succeeded");
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_unit_rows() {
+        let text = "cron.service loaded active running Regular background program processing daemon\n\
+                    nginx.service  loaded failed failed  A high performance web server\n";
+        let units = parse_plain_units(text);
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].name, "cron.service");
+        assert_eq!(units[0].sub, "running");
+        assert_eq!(units[0].description, "Regular background program processing daemon");
+        assert_eq!(units[1].active, "failed");
+    }
+
+    #[test]
+    fn accepts_only_service_unit_names() {
+        assert!(is_valid_unit_name("nginx.service"));
+        assert!(is_valid_unit_name("getty@tty1.service"));
+        assert!(!is_valid_unit_name("--force"));
+        assert!(!is_valid_unit_name("nginx.socket"));
+        assert!(!is_valid_unit_name("a b.service"));
+    }
+}
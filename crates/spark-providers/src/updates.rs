@@ -0,0 +1,124 @@
+//! Pending OS updates on Debian and Ubuntu, for visibility only: nothing is installed.
+
+use spark_types::UpdateStatus;
+use std::path::Path;
+use tokio::process::Command;
+use tokio::time::Duration;
+
+use crate::command::run_with_timeout;
+
+/// Written by Ubuntu's update-notifier after each apt update; also what the login motd shows.
+const UPDATES_AVAILABLE: &str = "/var/lib/update-notifier/updates-available";
+const REBOOT_REQUIRED: &str = "/var/run/reboot-required";
+// Resolving the upgrade reads every package list, which takes a few seconds on a slow disk
+const APT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Update counts from update-notifier's summary when present, else from simulating
+/// `apt-get upgrade`, plus whether a reboot is pending.
+pub async fn collect() -> Result<UpdateStatus, String> {
+    let (total, security) = match tokio::fs::read_to_string(UPDATES_AVAILABLE)
+        .await
+        .ok()
+        .and_then(|contents| parse_updates_available(&contents))
+    {
+        Some(counts) => counts,
+        None => simulate_upgrade().await?,
+    };
+    Ok(UpdateStatus {
+        total,
+        security,
+        reboot_required: Path::new(REBOOT_REQUIRED).exists(),
+    })
+}
+
+/// `(total, security)` from `apt-get -s upgrade`, which needs no root and changes nothing.
+async fn simulate_upgrade() -> Result<(u32, u32), String> {
+    let output = run_with_timeout(
+        Command::new("apt-get").args(["-s", "-q", "upgrade"]).env("LC_ALL", "C"),
+        APT_TIMEOUT,
+    )
+    .await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("apt-get -s upgrade failed: {}", stderr.trim()));
+    }
+    Ok(parse_simulated_upgrade(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Count the `Inst` lines of a simulated upgrade. The parenthesised part names the archive
+/// the new version comes from, e.g. `(2.1-1ubuntu0.1 Ubuntu:22.04/jammy-security [arm64])`.
+fn parse_simulated_upgrade(output: &str) -> (u32, u32) {
+    let mut total = 0;
+    let mut security = 0;
+    for line in output.lines().filter(|line| line.starts_with("Inst ")) {
+        total += 1;
+        let origin = line.split_once('(').map_or("", |(_, rest)| rest);
+        if origin.to_ascii_lowercase().contains("security") {
+            security += 1;
+        }
+    }
+    (total, security)
+}
+
+/// Read update-notifier's summary, which comes in two wordings:
+/// "12 updates can be applied immediately. / 5 of these updates are standard security
+/// updates." and the older "12 packages can be updated. / 5 updates are security updates."
+/// ESM lines ("3 additional security updates can be applied with ESM Apps.") are ignored,
+/// since those can't be installed without a subscription.
+fn parse_updates_available(contents: &str) -> Option<(u32, u32)> {
+    let leadingCount = |line: &str| line.split_whitespace().next()?.parse::<u32>().ok();
+    let mut total = None;
+    let mut security = 0;
+    for line in contents.lines().map(str::trim) {
+        if line.contains("can be applied immediately") || line.contains("can be updated") {
+            total = leadingCount(line);
+        } else if line.contains("of these updates are") || line.contains("updates are security updates") {
+            if let Some(count) = leadingCount(line) {
+                security = count;
+            }
+        }
+    }
+    Some((total?, security))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_simulated_upgrades_by_archive() {
+        let output = "Reading package lists...\n\
+                      Inst bash [5.1-6ubuntu1] (5.1-6ubuntu1.1 Ubuntu:22.04/jammy-updates [arm64])\n\
+                      Inst libssl3 [3.0.2-0ubuntu1.15] (3.0.2-0ubuntu1.18 Ubuntu:22.04/jammy-security [arm64])\n\
+                      Inst tzdata [2024a-1] (2024b-0+deb12u1 Debian-Security:12/stable-security [all])\n\
+                      Conf bash (5.1-6ubuntu1.1 Ubuntu:22.04/jammy-updates [arm64])\n";
+        assert_eq!(parse_simulated_upgrade(output), (3, 2));
+    }
+
+    #[test]
+    fn reads_both_update_notifier_wordings() {
+        let current = "\n12 updates can be applied immediately.\n\
+                       5 of these updates are standard security updates.\n\
+                       To see these additional updates run: apt list --upgradable\n";
+        let older = "3 packages can be updated.\n0 updates are security updates.\n";
+        assert_eq!(parse_updates_available(current), Some((12, 5)));
+        assert_eq!(parse_updates_available(older), Some((3, 0)));
+        assert_eq!(parse_updates_available(""), None);
+    }
+
+    #[test]
+    fn ignores_esm_security_lines() {
+        let contents = "Expanded Security Maintenance for Applications is not enabled.
+
+                        14 updates can be applied immediately.
+                        6 of these updates are standard security updates.
+                        To see these additional updates run: apt list --upgradable
+
+                        2 additional security updates can be applied with ESM Apps.
+                        Learn more about enabling ESM Apps service at https://ubuntu.com/esm
+
+                        Enable ESM Apps to receive additional future security updates.
+";
+        assert_eq!(parse_updates_available(contents), Some((14, 6)));
+    }
+}
@@ -1,4 +1,6 @@
 use spark_types::UptimeMetrics;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 use tracing::warn;
 
 pub async fn collect() -> UptimeMetrics {
@@ -15,23 +17,59 @@ async fn read_proc_uptime() -> Result<UptimeMetrics, String> {
     let contents = tokio::fs::read_to_string("/proc/uptime")
         .await
         .map_err(|e| format!("failed to read /proc/uptime: {e}"))?;
+    parse_proc_uptime(&contents, OffsetDateTime::now_utc())
+}
 
-    let firstField = contents
-        .split_whitespace()
-        .next()
-        .ok_or("empty /proc/uptime")?;
+/// Parse `<uptime> <idle>` from /proc/uptime, both in seconds. The idle field is
+/// optional so a truncated file still yields the uptime.
+fn parse_proc_uptime(contents: &str, now: OffsetDateTime) -> Result<UptimeMetrics, String> {
+    let mut fields = contents.split_whitespace();
+    let firstField = fields.next().ok_or("empty /proc/uptime")?;
 
     let uptimeSeconds = firstField
         .parse::<f64>()
         .map_err(|e| format!("failed to parse uptime: {e}"))?;
+    let idleSeconds = fields
+        .next()
+        .and_then(|f| f.parse::<f64>().ok())
+        .unwrap_or(0.0);
 
     Ok(UptimeMetrics {
         seconds: uptimeSeconds as u64,
+        idle_seconds: idleSeconds as u64,
+        boot_time: boot_time(now, uptimeSeconds as u64),
     })
 }
 
+fn boot_time(now: OffsetDateTime, uptimeSeconds: u64) -> Option<String> {
+    let boot = now.checked_sub(time::Duration::seconds(uptimeSeconds as i64))?;
+    // Whole seconds: uptime is only that precise anyway
+    boot.replace_nanosecond(0).ok()?.format(&Rfc3339).ok()
+}
+
 fn mock_uptime_metrics() -> UptimeMetrics {
+    let seconds = 3 * 86400 + 7 * 3600 + 42 * 60 + 15;
     UptimeMetrics {
-        seconds: 3 * 86400 + 7 * 3600 + 42 * 60 + 15,
+        seconds,
+        idle_seconds: seconds * 18,
+        boot_time: boot_time(OffsetDateTime::now_utc(), seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_idle_and_boot_time() {
+        let now = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        let metrics = parse_proc_uptime("3600.52 14000.25\n", now).unwrap();
+        assert_eq!(metrics.seconds, 3600);
+        assert_eq!(metrics.idle_seconds, 14000);
+        assert_eq!(metrics.boot_time.as_deref(), Some("2023-11-14T21:13:20Z"));
+
+        let metrics = parse_proc_uptime("42.0", now).unwrap();
+        assert_eq!(metrics.idle_seconds, 0);
+        assert!(parse_proc_uptime("", now).is_err());
     }
 }
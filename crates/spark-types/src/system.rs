@@ -1,70 +1,175 @@
 use serde::{Deserialize, Serialize};
 
+/// Bumped when a field of [`SystemMetrics`] or the types inside it changes meaning or is
+/// removed. Adding a field doesn't need a bump: every metrics type is `#[serde(default)]`,
+/// so payloads from older and newer servers both deserialize, with missing fields defaulted.
+pub const METRICS_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct SystemMetrics {
-    pub gpu: GpuMetrics,
+    /// [`METRICS_SCHEMA_VERSION`] of the server that collected these metrics; 0 for
+    /// payloads from before the field existed.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// One entry per GPU, ordered by device index.
+    pub gpus: Vec<GpuMetrics>,
     pub memory: MemoryMetrics,
     pub cpu: CpuMetrics,
+    /// The first configured mount (normally `/`), kept for existing API clients.
     pub disk: DiskMetrics,
+    /// Every configured mount point, in config order.
+    pub disks: Vec<DiskMetrics>,
     pub uptime: UptimeMetrics,
+    /// Host network interfaces, in /proc/net/dev order.
+    pub net: Vec<NetInterfaceMetrics>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct GpuMetrics {
+    pub index: u32,
+    pub uuid: String,
+    /// Which tool the metrics came from: "nvidia", "amd", or "mock".
+    pub vendor: String,
     pub name: String,
     pub utilization_pct: f32,
     pub temperature_c: u32,
+    /// On unified-memory GPUs, the memory held by GPU processes, summed.
     pub memory_used_mib: u64,
+    /// On unified-memory GPUs, the system memory the GPU shares.
     pub memory_total_mib: u64,
     pub power_draw_w: f32,
+    /// Enforced power limit; 0 when the driver doesn't report one.
+    pub power_limit_w: f32,
+    /// `None` on passively cooled GPUs, where nvidia-smi reports [N/A].
+    pub fan_speed_pct: Option<u32>,
+    pub sm_clock_mhz: Option<u32>,
+    pub mem_clock_mhz: Option<u32>,
+    /// NVENC utilization; `None` where the driver reports [N/A].
+    pub encoder_util_pct: Option<f32>,
+    /// NVDEC utilization; `None` where the driver reports [N/A].
+    pub decoder_util_pct: Option<f32>,
     pub unified_memory: bool,
     pub processes: Vec<GpuProcess>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct GpuProcess {
     pub pid: u32,
     pub name: String,
     pub memory_mib: u64,
+    /// SM utilization from `nvidia-smi pmon`; `None` when pmon is unavailable.
+    pub sm_util_pct: Option<f32>,
+    /// Full command line from `/proc/<pid>/cmdline`, or `name` if unreadable.
+    pub cmdline: String,
+    /// Owning user name (or numeric UID if it can't be resolved); empty if unknown.
+    pub user: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct MemoryMetrics {
     pub total_bytes: u64,
+    /// `total - available` by default, which counts unreclaimable cache as used. With
+    /// `memory.count_cache_as_used`, `total - free`, which counts all cache as used.
     pub used_bytes: u64,
+    /// Whether `used_bytes` is `total - free` (`memory.count_cache_as_used`).
+    pub used_includes_cache: bool,
     pub available_bytes: u64,
+    /// `MemFree`: memory holding nothing at all, not even cache.
+    pub free_bytes: u64,
+    /// Memory held by applications (`total - available`), for the breakdown alongside the cache.
+    pub app_used_bytes: u64,
+    pub buffers_bytes: u64,
+    /// Page cache plus reclaimable slab (`Cached + SReclaimable`).
+    pub cached_bytes: u64,
     pub swap_total_bytes: u64,
     pub swap_used_bytes: u64,
+    /// Share of time some task was stalled on memory (PSI), over 10s and 60s.
+    /// `None` on kernels without `/proc/pressure`.
+    pub pressure_some_avg10: Option<f32>,
+    pub pressure_some_avg60: Option<f32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct CpuMetrics {
     pub load_1m: f32,
     pub load_5m: f32,
     pub load_15m: f32,
+    /// Load averages divided by the logical core count; 1.0 means every core is busy.
+    pub load_per_core_1m: f32,
+    pub load_per_core_5m: f32,
+    pub load_per_core_15m: f32,
+    pub model: String,
+    pub physical_cores: u32,
+    pub logical_cores: u32,
+    /// Average current frequency across logical cores; 0 when not reported.
+    pub current_mhz: f32,
+    /// Share of time some task was waiting for a CPU (PSI), over 10s. `None` without PSI.
+    pub pressure_some_avg10: Option<f32>,
+    /// CPU package temperature from hwmon; `None` when no known sensor is present.
+    pub temperature_c: Option<u32>,
+    /// Runnable tasks right now, from /proc/loadavg. Tasks are threads, not just processes.
+    pub procs_running: u32,
+    /// Every task on the system; 0 when /proc/loadavg doesn't list it.
+    pub procs_total: u32,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct DiskMetrics {
     pub total_bytes: u64,
     pub used_bytes: u64,
     pub available_bytes: u64,
     pub mount_point: String,
+    /// 0 on filesystems without a fixed inode table (e.g. btrfs).
+    pub inodes_total: u64,
+    pub inodes_used: u64,
+    /// Block device backing the mount (e.g. "nvme0n1p2"); empty if it isn't in /proc/diskstats.
+    pub device: String,
+    pub read_bytes_per_sec: Option<f64>,
+    pub write_bytes_per_sec: Option<f64>,
+    /// Filesystem type from /proc/mounts (e.g. "ext4"); empty when not known.
+    pub fs_type: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NetInterfaceMetrics {
+    /// Interface name, e.g. "eth0".
+    pub name: String,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    /// Bytes received since the interface came up.
+    pub rx_bytes_total: u64,
+    /// Bytes sent since the interface came up.
+    pub tx_bytes_total: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
 pub struct UptimeMetrics {
     pub seconds: u64,
+    /// Idle time summed across all cores since boot, so up to `seconds` times the core count.
+    pub idle_seconds: u64,
+    /// Wall-clock boot time (now minus uptime) as RFC 3339 in UTC.
+    pub boot_time: Option<String>,
 }
 
 impl Default for SystemMetrics {
     fn default() -> Self {
         Self {
-            gpu: GpuMetrics::default(),
+            schema_version: METRICS_SCHEMA_VERSION,
+            gpus: Vec::new(),
             memory: MemoryMetrics::default(),
             cpu: CpuMetrics::default(),
             disk: DiskMetrics::default(),
+            disks: Vec::new(),
             uptime: UptimeMetrics::default(),
+            net: Vec::new(),
         }
     }
 }
@@ -72,36 +177,44 @@ impl Default for SystemMetrics {
 impl Default for GpuMetrics {
     fn default() -> Self {
         Self {
+            index: 0,
+            uuid: String::new(),
+            vendor: String::new(),
             name: "No GPU detected".into(),
             utilization_pct: 0.0,
             temperature_c: 0,
             memory_used_mib: 0,
             memory_total_mib: 0,
             power_draw_w: 0.0,
+            power_limit_w: 0.0,
+            fan_speed_pct: None,
+            sm_clock_mhz: None,
+            mem_clock_mhz: None,
+            encoder_util_pct: None,
+            decoder_util_pct: None,
             unified_memory: false,
             processes: Vec::new(),
         }
     }
 }
 
-impl Default for MemoryMetrics {
-    fn default() -> Self {
-        Self {
-            total_bytes: 0,
-            used_bytes: 0,
-            available_bytes: 0,
-            swap_total_bytes: 0,
-            swap_used_bytes: 0,
-        }
-    }
-}
-
 impl Default for CpuMetrics {
     fn default() -> Self {
         Self {
             load_1m: 0.0,
             load_5m: 0.0,
             load_15m: 0.0,
+            load_per_core_1m: 0.0,
+            load_per_core_5m: 0.0,
+            load_per_core_15m: 0.0,
+            model: String::new(),
+            physical_cores: 0,
+            logical_cores: 0,
+            current_mhz: 0.0,
+            pressure_some_avg10: None,
+            temperature_c: None,
+            procs_running: 0,
+            procs_total: 0,
         }
     }
 }
@@ -113,23 +226,30 @@ impl Default for DiskMetrics {
             used_bytes: 0,
             available_bytes: 0,
             mount_point: "/".into(),
+            inodes_total: 0,
+            inodes_used: 0,
+            device: String::new(),
+            read_bytes_per_sec: None,
+            write_bytes_per_sec: None,
+            fs_type: String::new(),
         }
     }
 }
 
-impl Default for UptimeMetrics {
-    fn default() -> Self {
-        Self { seconds: 0 }
-    }
-}
-
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ContainerSummary {
     pub id: String,
     pub name: String,
     pub image: String,
     pub status: ContainerStatus,
+    /// The runtime's own status line ("Up 3 hours", "Exited (0) 2 days ago"), kept for
+    /// formats `exit_code` and `since` can't be parsed from.
     pub state_text: String,
+    /// Exit code of the last run, for exited or restarting containers.
+    pub exit_code: Option<i32>,
+    /// How long the container has been in its current state, e.g. "3 hours" for
+    /// "Up 3 hours" or "2 days" for "Exited (0) 2 days ago".
+    pub since: Option<String>,
     pub cpu_pct: f64,
     pub memory_usage_bytes: u64,
     pub memory_limit_bytes: u64,
@@ -140,15 +260,69 @@ pub struct ContainerSummary {
     pub restart_policy: String,
     pub created: String,
     pub mounts: Vec<String>,
+    /// Size of the container's writable layer. `None` unless size collection is enabled.
+    pub rw_size_bytes: Option<u64>,
+    /// Total size of the container's root filesystem (image layers + writable layer).
+    pub root_fs_size_bytes: Option<u64>,
+    /// Healthcheck result. `None` for containers without a HEALTHCHECK.
+    pub health: Option<ContainerHealth>,
+    /// CPUs the container may use (`--cpus`). `None` when unlimited.
+    pub cpu_limit: Option<f64>,
+    /// Memory cap (`--memory`). `None` when unlimited.
+    pub mem_limit_bytes: Option<u64>,
+    /// Environment as `KEY=value`, with values of credential-like keys redacted.
+    pub env: Vec<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+/// Resource usage of one running container, as `docker stats` reports it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContainerStats {
+    pub id: String,
+    pub cpu_pct: f64,
+    pub memory_usage_bytes: u64,
+    pub memory_limit_bytes: u64,
+    pub net_rx_bytes: u64,
+    pub net_tx_bytes: u64,
+}
+
+impl ContainerSummary {
+    /// Copy a stats sample into the summary's usage fields.
+    pub fn apply_stats(&mut self, stats: &ContainerStats) {
+        self.cpu_pct = stats.cpu_pct;
+        self.memory_usage_bytes = stats.memory_usage_bytes;
+        self.memory_limit_bytes = stats.memory_limit_bytes;
+        self.net_rx_bytes = stats.net_rx_bytes;
+        self.net_tx_bytes = stats.net_tx_bytes;
+    }
+
+    /// "Exited (137)", or "Restarting (1)" for a container in a restart loop, naming the
+    /// last exit code. `None` when there is no exit code.
+    pub fn exit_label(&self) -> Option<String> {
+        let code = self.exit_code?;
+        Some(match self.status {
+            ContainerStatus::Restarting => format!("Restarting ({code})"),
+            _ => format!("Exited ({code})"),
+        })
+    }
+}
+
+/// Outcome of a container's HEALTHCHECK, separate from whether it is running.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq)]
+pub enum ContainerHealth {
+    Healthy,
+    Unhealthy,
+    /// Within the start period, before the first check has passed.
+    Starting,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
 pub enum ContainerStatus {
     Running,
     Stopped,
     Restarting,
     Paused,
     Dead,
+    #[default]
     Unknown,
 }
 
@@ -158,6 +332,54 @@ pub struct ContainerAction {
     pub action: String,
 }
 
+/// Pending OS package updates.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct UpdateStatus {
+    /// Packages with an upgrade available, security ones included.
+    pub total: u32,
+    /// Upgrades that come from a security archive.
+    pub security: u32,
+    /// Whether an installed update asked for a reboot (`/var/run/reboot-required`).
+    pub reboot_required: bool,
+}
+
+/// A systemd service unit, as listed by `systemctl list-units`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ServiceSummary {
+    /// Unit name, e.g. `nginx.service`.
+    pub name: String,
+    /// Whether the unit file loaded: `loaded`, `not-found`, `masked`...
+    pub load: String,
+    /// High-level state: `active`, `inactive`, `failed`...
+    pub active: String,
+    /// Unit-specific state: `running`, `exited`, `dead`...
+    pub sub: String,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ServiceAction {
+    pub unit: String,
+    pub action: String,
+}
+
+/// Body of every API error response.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+/// Body of `/api/v1/version`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct VersionInfo {
+    /// Crate version, e.g. "0.1.0".
+    pub version: String,
+    /// Short commit SHA the server was built from, or "unknown".
+    pub git_sha: String,
+    /// RFC 3339 time of the build.
+    pub built_at: String,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct ContainerActionResult {
     pub success: bool,
@@ -172,6 +394,8 @@ impl Default for ContainerSummary {
             image: String::new(),
             status: ContainerStatus::default(),
             state_text: String::new(),
+            exit_code: None,
+            since: None,
             cpu_pct: 0.0,
             memory_usage_bytes: 0,
             memory_limit_bytes: 0,
@@ -182,14 +406,55 @@ impl Default for ContainerSummary {
             restart_policy: String::new(),
             created: String::new(),
             mounts: Vec::new(),
+            rw_size_bytes: None,
+            root_fs_size_bytes: None,
+            health: None,
+            cpu_limit: None,
+            mem_limit_bytes: None,
+            env: Vec::new(),
         }
     }
 }
 
-impl Default for ContainerStatus {
-    fn default() -> Self {
-        Self::Unknown
-    }
+/// One row of `docker images`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ImageSummary {
+    pub id: String,
+    /// `<none>` for dangling images.
+    pub repo: String,
+    pub tag: String,
+    pub size_bytes: u64,
+    /// Age as the CLI reports it, e.g. "3 weeks ago".
+    pub created: String,
+}
+
+/// One row of `docker volume ls`, with usage details.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct VolumeSummary {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    /// `None` when the driver doesn't report a size or the size query failed.
+    pub size_bytes: Option<u64>,
+    /// Whether any container, running or stopped, mounts the volume. `None` if unknown.
+    pub in_use: Option<bool>,
+}
+
+/// Space taken by one directory, as reported by `du`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct DirUsage {
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+/// Lightweight counts for ambient status (nav badges).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct InventorySummary {
+    pub containers_running: usize,
+    pub containers_total: usize,
+    /// Containers reporting an unhealthy healthcheck, restarting, or dead.
+    pub containers_unhealthy: usize,
+    pub models_total: usize,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -198,5 +463,170 @@ pub struct ModelEntry {
     pub path: String,
     pub size_bytes: u64,
     pub format: String,
-    pub modified: String,
+    /// Last modification time in Unix seconds, if the filesystem reports one.
+    pub modified: Option<u64>,
+    /// GGUF only: `general.name` from the file's metadata.
+    pub model_name: Option<String>,
+    /// GGUF only: `general.architecture`, e.g. "llama" or "qwen2".
+    pub architecture: Option<String>,
+    /// GGUF only: quantization scheme, e.g. "Q4_K_M".
+    pub quantization: Option<String>,
+    /// Total parameters, from GGUF metadata or summed over the tensor shapes.
+    pub param_count: Option<u64>,
+    /// safetensors only: the dtype holding the most parameters, e.g. "BF16".
+    pub dtype: Option<String>,
+    /// Number of tensors in the file (GGUF and safetensors).
+    pub tensor_count: Option<u64>,
+}
+
+/// What the caller is allowed to do with their credentials.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum TokenScope {
+    ReadOnly,
+    #[default]
+    ReadWrite,
+}
+
+/// Features enabled on this server, so clients can hide controls that would be rejected.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Capabilities {
+    /// Container actions accepted by `POST /api/v1/containers/action`.
+    pub container_actions: Vec<String>,
+    pub container_size: bool,
+    /// CLI of the container engine in use, `docker` or `podman`.
+    pub container_runtime: String,
+    pub docker_exec: bool,
+    pub container_create: bool,
+    pub container_kill: bool,
+    pub services: bool,
+    /// Whether `POST /api/v1/services/action` may start, stop and restart units.
+    pub service_actions: bool,
+    /// Whether `POST /api/v1/gpu/processes/:pid/kill` is enabled (`gpu.allow_kill`).
+    pub gpu_kill: bool,
+    pub updates: bool,
+    pub alerting: bool,
+    pub token_scope: TokenScope,
+}
+
+impl Capabilities {
+    /// Whether a container action is both enabled and permitted for the caller's scope.
+    pub fn allows_action(&self, action: &str) -> bool {
+        self.token_scope == TokenScope::ReadWrite
+            && self.container_actions.iter().any(|a| a == action)
+    }
+
+    /// Whether service start/stop/restart is both enabled and permitted for the caller's scope.
+    pub fn allows_service_actions(&self) -> bool {
+        self.token_scope == TokenScope::ReadWrite && self.service_actions
+    }
+
+    /// Whether GPU processes may be killed, given the server config and the caller's scope.
+    pub fn allows_gpu_kill(&self) -> bool {
+        self.token_scope == TokenScope::ReadWrite && self.gpu_kill
+    }
+}
+
+/// Which configured threshold an [`Alert`] crossed.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    GpuTemperature,
+    Memory,
+    Disk,
+}
+
+/// A threshold from the `[alerts]` config that the latest metrics sample exceeds.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Alert {
+    pub kind: AlertKind,
+    /// GPU index or mount point the alert is about; empty for memory.
+    pub target: String,
+    pub value: f32,
+    pub threshold: f32,
+    /// Human-readable summary, e.g. "GPU 0 at 87°C (limit 85°C)".
+    pub message: String,
+}
+
+impl Alert {
+    /// Identifies the condition regardless of its current value, so repeated
+    /// notifications for the same alert can be suppressed.
+    pub fn key(&self) -> String {
+        format!("{:?}:{}", self.kind, self.target)
+    }
+}
+
+/// One point in the metrics history. Values are averaged when a tier downsamples.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct HistorySample {
+    /// Unix timestamp (seconds) of the sample, or of the bucket start when downsampled.
+    pub timestamp: u64,
+    pub gpu_utilization_pct: f32,
+    pub gpu_temperature_c: f32,
+    pub gpu_power_draw_w: f32,
+    pub gpu_memory_used_mib: f32,
+    pub memory_used_bytes: f64,
+    pub cpu_load_1m: f32,
+    pub disk_used_bytes: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct HistoryResponse {
+    /// Spacing of the returned samples, in seconds.
+    pub resolution_secs: u64,
+    pub samples: Vec<HistorySample>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metrics_round_trip_and_tolerate_missing_fields() {
+        let metrics = SystemMetrics {
+            gpus: vec![GpuMetrics {
+                fan_speed_pct: Some(40),
+                processes: vec![GpuProcess {
+                    pid: 42,
+                    name: "python".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            disks: vec![DiskMetrics::default()],
+            net: vec![NetInterfaceMetrics {
+                name: "eth0".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&metrics).unwrap();
+        assert_eq!(serde_json::from_str::<SystemMetrics>(&json).unwrap(), metrics);
+
+        // An older server: no schema_version, disks, net, fan speed or process details
+        let old = r#"{
+            "gpus": [{"index": 1, "name": "GB10", "utilization_pct": 12.5,
+                      "processes": [{"pid": 7, "name": "llama-server", "memory_mib": 2048}]}],
+            "memory": {"total_bytes": 1024, "used_bytes": 512},
+            "cpu": {"load_1m": 0.5},
+            "disk": {"total_bytes": 100, "used_bytes": 10, "mount_point": "/data"},
+            "uptime": {"seconds": 60}
+        }"#;
+        let parsed: SystemMetrics = serde_json::from_str(old).unwrap();
+        assert_eq!(parsed.schema_version, 0);
+        assert_eq!(parsed.gpus[0].name, "GB10");
+        assert_eq!(parsed.gpus[0].fan_speed_pct, None);
+        assert_eq!(parsed.gpus[0].processes[0].memory_mib, 2048);
+        assert_eq!(parsed.gpus[0].processes[0].cmdline, "");
+        assert_eq!(parsed.memory.available_bytes, 0);
+        assert_eq!(parsed.cpu.logical_cores, 0);
+        assert_eq!(parsed.disk.mount_point, "/data");
+        assert!(parsed.disks.is_empty() && parsed.net.is_empty());
+
+        // A newer server: fields this build doesn't know are ignored
+        let newer = json.replacen(r#""schema_version":1"#, r#""schema_version":2,"fan_curve":[1,2]"#, 1);
+        let parsed: SystemMetrics = serde_json::from_str(&newer).unwrap();
+        assert_eq!(parsed.schema_version, 2);
+        assert_eq!(parsed.gpus, metrics.gpus);
+    }
 }
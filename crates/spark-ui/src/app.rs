@@ -2,23 +2,42 @@ use leptos::prelude::*;
 use leptos_meta::*;
 use leptos_router::{
     components::{Route, Router, Routes},
-    StaticSegment,
+    ParamSegment, StaticSegment,
 };
 
+use crate::components::branding::{accent_css, branding_meta, site_name, BrandingProvider};
+use crate::components::density::{DensityProvider, DENSITY_INIT_SCRIPT};
 use crate::components::nav::Nav;
+use crate::components::refresh::RefreshProvider;
+use crate::components::temperature::TemperatureProvider;
+use crate::components::theme::THEME_INIT_SCRIPT;
 use crate::components::toast::ToastProvider;
 use crate::pages::containers::ContainersPage;
 use crate::pages::dashboard::DashboardPage;
+use crate::pages::embed::EmbedPage;
+use crate::pages::login::LoginPage;
+use crate::pages::logs::ContainerLogsPage;
 use crate::pages::models::ModelsPage;
+use crate::pages::services::ServicesPage;
+use crate::pages::storage::StoragePage;
+use crate::pages::updates::UpdatesPage;
+use crate::ui_config::UiOptions;
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
+    // Only rendered on the server, so the accent is in place before the first paint
+    let accentCss = use_context::<UiOptions>().as_ref().and_then(accent_css);
+
     view! {
         <!DOCTYPE html>
-        <html lang="en">
+        <html lang="en" data-theme="dark">
             <head>
                 <meta charset="utf-8" />
+                <script inner_html=THEME_INIT_SCRIPT></script>
+                <script inner_html=DENSITY_INIT_SCRIPT></script>
                 <meta name="viewport" content="width=device-width, initial-scale=1" />
                 <link rel="icon" href="/favicon.svg" type="image/svg+xml" />
+                {accentCss.map(|css| view! { <style inner_html=css></style> })}
+                {branding_meta()}
                 <AutoReload options=options.clone() />
                 <HydrationScripts options />
                 <MetaTags />
@@ -36,16 +55,36 @@ pub fn App() -> impl IntoView {
 
     view! {
         <Stylesheet id="leptos" href="/pkg/spark-console.css" />
-        <Title text="Spark Console" />
-        <ToastProvider>
-            <Router>
-                <Routes fallback=|| view! { <p>"Page not found."</p> }.into_any()>
-                    <Route path=StaticSegment("") view=DashboardView />
-                    <Route path=StaticSegment("containers") view=ContainersView />
-                    <Route path=StaticSegment("models") view=ModelsView />
-                </Routes>
-            </Router>
-        </ToastProvider>
+        <BrandingProvider>
+            <Title text=site_name />
+            <ToastProvider>
+                <RefreshProvider>
+                    <TemperatureProvider>
+                        <DensityProvider>
+                            <Router>
+                                <Routes fallback=|| view! { <p>"Page not found."</p> }.into_any()>
+                                    <Route path=StaticSegment("") view=DashboardView />
+                                    <Route path=StaticSegment("containers") view=ContainersView />
+                                    <Route
+                                        path=(StaticSegment("containers"), ParamSegment("id"), StaticSegment("logs"))
+                                        view=ContainerLogsView
+                                    />
+                                    <Route path=StaticSegment("models") view=ModelsView />
+                                    <Route path=StaticSegment("services") view=ServicesView />
+                                    <Route path=StaticSegment("updates") view=UpdatesView />
+                                    <Route path=StaticSegment("storage") view=StorageView />
+                                    <Route path=StaticSegment("login") view=LoginPage />
+                                    <Route
+                                        path=(StaticSegment("embed"), ParamSegment("metric"))
+                                        view=EmbedPage
+                                    />
+                                </Routes>
+                            </Router>
+                        </DensityProvider>
+                    </TemperatureProvider>
+                </RefreshProvider>
+            </ToastProvider>
+        </BrandingProvider>
     }
 }
 
@@ -73,6 +112,18 @@ fn ContainersView() -> impl IntoView {
     }
 }
 
+#[component]
+fn ContainerLogsView() -> impl IntoView {
+    view! {
+        <div class="app-layout">
+            <Nav />
+            <main class="main-content">
+                <ContainerLogsPage />
+            </main>
+        </div>
+    }
+}
+
 #[component]
 fn ModelsView() -> impl IntoView {
     view! {
@@ -84,3 +135,39 @@ fn ModelsView() -> impl IntoView {
         </div>
     }
 }
+
+#[component]
+fn ServicesView() -> impl IntoView {
+    view! {
+        <div class="app-layout">
+            <Nav />
+            <main class="main-content">
+                <ServicesPage />
+            </main>
+        </div>
+    }
+}
+
+#[component]
+fn UpdatesView() -> impl IntoView {
+    view! {
+        <div class="app-layout">
+            <Nav />
+            <main class="main-content">
+                <UpdatesPage />
+            </main>
+        </div>
+    }
+}
+
+#[component]
+fn StorageView() -> impl IntoView {
+    view! {
+        <div class="app-layout">
+            <Nav />
+            <main class="main-content">
+                <StoragePage />
+            </main>
+        </div>
+    }
+}
@@ -0,0 +1,175 @@
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::components::toast::ToastContext;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::ToastLevel;
+
+/// Shown in place of the token until the user reveals it. Fixed length, so it doesn't
+/// give away how long the token is.
+const MASKED_TOKEN: &str = "\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}";
+
+/// What a script needs to call the API as this browser.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ApiAccess {
+    /// The token this browser logged in with; `None` when auth is off.
+    pub token: Option<String>,
+}
+
+#[server]
+async fn get_api_access() -> Result<ApiAccess, ServerFnError> {
+    use spark_api::middleware::auth::{verified_token, SharedAuthToken};
+
+    let Some(expected) = use_context::<SharedAuthToken>().and_then(|shared| shared.get()) else {
+        return Ok(ApiAccess { token: None });
+    };
+    // The page middleware already checked the request; this only hands back the token the
+    // caller presented, so it can't reach anyone who doesn't hold it
+    let headers: http::HeaderMap = leptos_axum::extract().await?;
+    verified_token(&expected, &headers)
+        .map(|token| ApiAccess { token: Some(token) })
+        .ok_or_else(|| ServerFnError::new("authentication required"))
+}
+
+/// Copy `text`, reporting the outcome as a toast. The clipboard API only exists on HTTPS
+/// (and localhost) pages.
+#[cfg(feature = "hydrate")]
+fn copy_to_clipboard(text: String, toasts: Option<ToastContext>) {
+    let toast = move |message: &str, level| {
+        if let Some(toasts) = toasts {
+            toasts.push(message.to_string(), level);
+        }
+    };
+    if !window().is_secure_context() {
+        toast("The clipboard needs HTTPS; select the command and copy it instead", ToastLevel::Error);
+        return;
+    }
+    let promise = window().navigator().clipboard().write_text(&text);
+    wasm_bindgen_futures::spawn_local(async move {
+        match wasm_bindgen_futures::JsFuture::from(promise).await {
+            Ok(_) => toast("Copied to the clipboard", ToastLevel::Success),
+            Err(_) => toast("Couldn't copy to the clipboard", ToastLevel::Error),
+        }
+    });
+}
+
+/// The nav's "API" button and the panel it opens: the token this browser logged in with,
+/// masked until revealed, and a `curl` example for scripting against the REST API. The
+/// token is only fetched while the panel is open.
+#[component]
+pub fn ApiPanel() -> impl IntoView {
+    let open = RwSignal::new(false);
+    let revealed = RwSignal::new(false);
+    let access = RwSignal::new(Option::<Result<ApiAccess, String>>::None);
+    #[allow(unused_variables)]
+    let origin = RwSignal::new(String::new());
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
+
+    let toggle = move |_| {
+        let opening = !open.get_untracked();
+        open.set(opening);
+        revealed.set(false);
+        // Drop the token from memory again once the panel closes
+        access.set(None);
+        #[cfg(feature = "hydrate")]
+        if opening {
+            origin.set(window().location().origin().unwrap_or_default());
+            wasm_bindgen_futures::spawn_local(async move {
+                access.set(Some(get_api_access().await.map_err(|e| e.to_string())));
+            });
+        }
+    };
+
+    let curl = move |token: Option<&str>| match token {
+        Some(token) => format!(
+            "curl -H \"Authorization: Bearer {token}\" {}/api/v1/system",
+            origin.get()
+        ),
+        None => format!("curl {}/api/v1/system", origin.get()),
+    };
+
+    let panel = move || {
+        let body = match access.get() {
+            None => view! {
+                <div class="loading">
+                    <div class="spinner"></div>
+                    "Loading..."
+                </div>
+            }
+            .into_any(),
+            Some(Err(e)) => view! { <p style="color: var(--danger)">{e}</p> }.into_any(),
+            Some(Ok(ApiAccess { token: None })) => {
+                let command = curl(None);
+                view! {
+                    <p class="api-panel-note">"Authentication is off, so requests need no token."</p>
+                    <pre class="api-panel-command">{command.clone()}</pre>
+                    <div class="api-panel-actions">
+                        <button
+                            class="btn btn-sm btn-ghost"
+                            on:click=move |_| {
+                                #[cfg(feature = "hydrate")]
+                                copy_to_clipboard(command.clone(), toasts);
+                            }
+                        >
+                            "Copy curl"
+                        </button>
+                    </div>
+                }
+                .into_any()
+            }
+            Some(Ok(ApiAccess { token: Some(token) })) => {
+                let shown = {
+                    let token = token.clone();
+                    move || if revealed.get() { token.clone() } else { MASKED_TOKEN.to_string() }
+                };
+                let shownCommand = {
+                    let shown = shown.clone();
+                    move || curl(Some(&shown()))
+                };
+                #[allow(unused_variables)]
+                let command = curl(Some(&token));
+                view! {
+                    <p class="api-panel-note">
+                        "Send this browser's token as a bearer header. Anyone holding it has full control of the console."
+                    </p>
+                    <div class="api-panel-token">
+                        <code>{shown}</code>
+                        <button class="btn btn-sm btn-ghost" on:click=move |_| revealed.update(|r| *r = !*r)>
+                            {move || if revealed.get() { "Hide" } else { "Reveal" }}
+                        </button>
+                    </div>
+                    <pre class="api-panel-command">{shownCommand}</pre>
+                    <div class="api-panel-actions">
+                        <button
+                            class="btn btn-sm btn-ghost"
+                            on:click=move |_| {
+                                #[cfg(feature = "hydrate")]
+                                copy_to_clipboard(command.clone(), toasts);
+                            }
+                        >
+                            "Copy curl"
+                        </button>
+                    </div>
+                }
+                .into_any()
+            }
+        };
+        view! {
+            <div class="api-panel card" role="dialog" aria-label="API access">
+                <div class="card-title-row">
+                    <div class="card-title">"API Access"</div>
+                    <button class="btn btn-sm btn-ghost" on:click=toggle>"Close"</button>
+                </div>
+                {body}
+            </div>
+        }
+    };
+
+    view! {
+        <button class="nav-api" title="Token and a curl example for scripting against the API" on:click=toggle>
+            "API"
+        </button>
+        {move || open.get().then(panel)}
+    }
+}
@@ -0,0 +1,125 @@
+use leptos::prelude::*;
+
+use crate::ui_config::{UiOptions, DEFAULT_SITE_NAME};
+
+/// Whether `color` is a `#rgb` or `#rrggbb` hex colour. `ui.accent_color` is written into
+/// a `<style>` element, so anything else is rejected at startup.
+pub fn is_valid_accent_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// CSS overriding the accent colour in both themes, or `None` when `ui.accent_color`
+/// isn't set (or isn't a valid colour).
+pub fn accent_css(options: &UiOptions) -> Option<String> {
+    let color = options.accent_color.as_deref().filter(|c| is_valid_accent_color(c))?;
+    Some(format!(
+        ":root,[data-theme=\"light\"]{{--accent:{color};--accent-hover:color-mix(in srgb,{color} 85%,black)}}"
+    ))
+}
+
+/// `<meta>` names the shell writes the branding into, for the browser to start from.
+const SITE_NAME_META: &str = "spark-site-name";
+const HOST_NAME_META: &str = "spark-host-name";
+
+/// How the console labels itself: `ui.site_name`, and the host it runs on.
+#[derive(Clone, Debug)]
+pub struct Branding {
+    pub site_name: String,
+    pub host_name: Option<String>,
+}
+
+impl Default for Branding {
+    fn default() -> Self {
+        Self {
+            site_name: DEFAULT_SITE_NAME.to_string(),
+            host_name: None,
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl Branding {
+    fn from_options(options: &UiOptions) -> Self {
+        let hostName = std::fs::read_to_string("/proc/sys/kernel/hostname")
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+        Self {
+            site_name: options.site_name.clone(),
+            host_name: hostName,
+        }
+    }
+}
+
+impl Branding {
+    /// What the server wrote into the page's [`branding_meta`] tags.
+    #[cfg(not(feature = "ssr"))]
+    fn from_page() -> Self {
+        #[cfg(feature = "hydrate")]
+        {
+            let meta = |name: &str| {
+                document()
+                    .query_selector(&format!("meta[name=\"{name}\"]"))
+                    .ok()
+                    .flatten()
+                    .and_then(|element| element.get_attribute("content"))
+            };
+            if let Some(siteName) = meta(SITE_NAME_META) {
+                return Self {
+                    site_name: siteName,
+                    host_name: meta(HOST_NAME_META),
+                };
+            }
+        }
+        Self::default()
+    }
+}
+
+/// `<meta>` tags carrying the branding to the browser, so hydration starts from the
+/// server's values (even on the login page, before there is a session). Goes in `<head>`.
+pub fn branding_meta() -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    let branding = Branding::from_options(&use_context::<UiOptions>().unwrap_or_default());
+    #[cfg(not(feature = "ssr"))]
+    let branding = Branding::default();
+
+    view! {
+        <meta name=SITE_NAME_META content=branding.site_name />
+        {branding.host_name.map(|name| view! { <meta name=HOST_NAME_META content=name /> })}
+    }
+}
+
+/// The site and host names, shared by the title, nav and login page.
+#[derive(Clone, Copy)]
+pub struct BrandingContext {
+    pub site_name: RwSignal<String>,
+    pub host_name: RwSignal<Option<String>>,
+}
+
+/// Provides [`BrandingContext`]. The server renders with its own config; the browser
+/// reads the same values from [`branding_meta`]. Place this once near the root of the app.
+#[component]
+pub fn BrandingProvider(children: Children) -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    let initial = Branding::from_options(&use_context::<UiOptions>().unwrap_or_default());
+    #[cfg(not(feature = "ssr"))]
+    let initial = Branding::from_page();
+
+    let ctx = BrandingContext {
+        site_name: RwSignal::new(initial.site_name),
+        host_name: RwSignal::new(initial.host_name),
+    };
+    provide_context(ctx);
+
+    children()
+}
+
+/// The configured site name, or the default outside a [`BrandingProvider`].
+pub fn site_name() -> String {
+    use_context::<BrandingContext>()
+        .map(|b| b.site_name.get())
+        .unwrap_or_else(|| DEFAULT_SITE_NAME.to_string())
+}
+
@@ -0,0 +1,118 @@
+use leptos::prelude::*;
+
+/// `localStorage` key for the chosen density, also read by [`DENSITY_INIT_SCRIPT`].
+#[cfg(feature = "hydrate")]
+const STORAGE_KEY: &str = "spark.density";
+
+/// Class on `<html>` that switches the stylesheet to the compact layout.
+#[cfg(feature = "hydrate")]
+const COMPACT_CLASS: &str = "density-compact";
+
+/// Runs in `<head>` before first paint, so a saved compact layout doesn't jump on load.
+pub const DENSITY_INIT_SCRIPT: &str = r#"try{if(localStorage.getItem("spark.density")==="compact")document.documentElement.classList.add("density-compact")}catch(e){}"#;
+
+/// How tightly the dashboard packs its cards: comfortable for a laptop, compact for a
+/// wall-mounted monitor that should show every metric without scrolling.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Density {
+    #[default]
+    Comfortable,
+    Compact,
+}
+
+impl Density {
+    fn label(self) -> &'static str {
+        match self {
+            Density::Comfortable => "Comfortable",
+            Density::Compact => "Compact",
+        }
+    }
+
+    #[cfg(feature = "hydrate")]
+    fn key(self) -> &'static str {
+        match self {
+            Density::Comfortable => "comfortable",
+            Density::Compact => "compact",
+        }
+    }
+}
+
+/// The layout density picked in the nav.
+#[derive(Clone, Copy)]
+pub struct DensityContext {
+    pub density: RwSignal<Density>,
+}
+
+impl DensityContext {
+    /// Change the density and remember it in `localStorage` for the next visit.
+    pub fn set_density(&self, density: Density) {
+        self.density.set(density);
+        #[cfg(feature = "hydrate")]
+        if let Some(storage) = window().local_storage().ok().flatten() {
+            let _ = storage.set_item(STORAGE_KEY, density.key());
+        }
+    }
+}
+
+/// Provides the density saved in `localStorage`, defaulting to comfortable, and keeps
+/// the root class in step with it. Place this once near the root of the app.
+#[component]
+pub fn DensityProvider(children: Children) -> impl IntoView {
+    let ctx = DensityContext {
+        density: RwSignal::new(Density::default()),
+    };
+    provide_context(ctx);
+
+    #[cfg(feature = "hydrate")]
+    {
+        if let Some(saved) = window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+        {
+            if saved == Density::Compact.key() {
+                ctx.density.set(Density::Compact);
+            }
+        }
+        Effect::new(move |_| {
+            let compact = ctx.density.get() == Density::Compact;
+            if let Some(root) = document().document_element() {
+                let _ = root.class_list().toggle_with_force(COMPACT_CLASS, compact);
+            }
+        });
+    }
+
+    children()
+}
+
+/// The nav's comfortable/compact switch.
+#[component]
+pub fn DensityToggle() -> impl IntoView {
+    let ctx = use_context::<DensityContext>();
+    let density = Signal::derive(move || ctx.map(|c| c.density.get()).unwrap_or_default());
+    let option = move |choice: Density| {
+        view! {
+            <button
+                class=move || if density.get() == choice { "segmented-option active" } else { "segmented-option" }
+                on:click=move |_| {
+                    if let Some(ctx) = ctx {
+                        ctx.set_density(choice);
+                    }
+                }
+            >
+                {choice.label()}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="nav-setting">
+            <span>"Density"</span>
+            <div class="segmented" role="group" aria-label="Layout density">
+                {option(Density::Comfortable)}
+                {option(Density::Compact)}
+            </div>
+        </div>
+    }
+}
@@ -1,10 +1,24 @@
 use leptos::prelude::*;
 
+/// Threshold color for a 0-100 usage value: green, amber from 70, red from 90.
+pub fn gauge_color(value: f32) -> &'static str {
+    if value >= 90.0 {
+        "#ef4444"
+    } else if value >= 70.0 {
+        "#f59e0b"
+    } else {
+        "#76b900"
+    }
+}
+
 /// SVG circular gauge component.
 ///
 /// Renders a 240-degree arc that fills based on `value` (0-100).
 /// Uses stroke-dasharray/stroke-dashoffset technique.
 /// Color transitions from green -> yellow -> red based on thresholds.
+///
+/// With `inverse`, `value` is headroom (e.g. free memory) and the thresholds flip: the
+/// default color turns amber at 30% remaining and red at 10%.
 #[component]
 pub fn Gauge(
     /// Value from 0.0 to 100.0
@@ -13,12 +27,16 @@ pub fn Gauge(
     label: String,
     /// Unit string displayed after value (e.g., "%", "°C")
     unit: String,
-    /// Stroke color for the filled arc
-    color: String,
-    /// Optional override for the center display text.
-    /// When provided, this is shown instead of the computed value.
+    /// Stroke color for the filled arc; by default picked from `value` by the thresholds
+    #[prop(optional)]
+    color: Option<String>,
+    /// Optional override for the center display text, shown verbatim instead of the
+    /// computed value. `value` still drives the arc fill.
     #[prop(optional)]
     display_value: Option<String>,
+    /// `value` is headroom, so low values are the alarming ones
+    #[prop(optional)]
+    inverse: bool,
 ) -> impl IntoView {
     let SIZE: f32 = 120.0;
     let STROKE_WIDTH: f32 = 8.0;
@@ -32,6 +50,10 @@ pub fn Gauge(
 
     // clamp value to 0-100
     let clampedValue = value.clamp(0.0, 100.0);
+    let color = color.unwrap_or_else(|| {
+        let used = if inverse { 100.0 - clampedValue } else { clampedValue };
+        gauge_color(used).to_string()
+    });
     let filledLength = arcLength * (clampedValue / 100.0);
 
     // The gap portion of the dasharray (non-arc part)
@@ -52,10 +74,13 @@ pub fn Gauge(
     let displayText = match display_value {
         Some(dv) => dv,
         None => {
-            if value == value.floor() {
-                format!("{:.0}", clampedValue)
+            // Round first, so 99.96 reads "100" rather than "100.0", and judge the clamped
+            // value rather than the raw one, which may be out of range
+            let rounded = (clampedValue * 10.0).round() / 10.0;
+            if rounded.fract() == 0.0 {
+                format!("{rounded:.0}")
             } else {
-                format!("{:.1}", clampedValue)
+                format!("{rounded:.1}")
             }
         }
     };
@@ -0,0 +1,215 @@
+use leptos::prelude::*;
+
+const WIDTH: f32 = 480.0;
+const HEIGHT: f32 = 220.0;
+// Room for tick labels on the left and the axis label below
+const MARGIN_LEFT: f32 = 48.0;
+const MARGIN_RIGHT: f32 = 8.0;
+const MARGIN_TOP: f32 = 8.0;
+const MARGIN_BOTTOM: f32 = 28.0;
+const GRID_LINES: usize = 4;
+/// Series colors in legend order; they repeat past the sixth series.
+const PALETTE: &[&str] = &["var(--accent)", "#3b82f6", "#f59e0b", "#ef4444", "#a855f7", "#14b8a6"];
+
+/// Round `value` up to 1, 2 or 5 times a power of ten, so auto-scaled axes get
+/// readable tick labels.
+fn nice_ceil(value: f32) -> f32 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f32.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|step| step * magnitude)
+        .find(|candidate| *candidate >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn format_tick(value: f32) -> String {
+    if value.abs() >= 100.0 || value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+/// Full-size SVG line chart of one or more series, with gridlines, axis labels and a
+/// legend.
+///
+/// Series are aligned on their newest sample at the right edge, so series of different
+/// lengths line up in time. Without `y_range` the axis runs from 0 to a rounded-up
+/// maximum of the data. Empty series are skipped; with no data at all the chart says so.
+#[component]
+pub fn LineChart(
+    /// Labeled series, oldest sample first
+    #[prop(into)]
+    series: Signal<Vec<(String, Vec<f32>)>>,
+    /// Bottom and top of the y axis
+    #[prop(optional)]
+    y_range: Option<(f32, f32)>,
+    /// Caption under the x axis, e.g. "Last hour"
+    #[prop(optional, into)]
+    x_label: String,
+    /// Caption beside the y axis, e.g. "°C"
+    #[prop(optional, into)]
+    y_label: String,
+) -> impl IntoView {
+    let plotWidth = WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plotHeight = HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+
+    let range = move || {
+        y_range.unwrap_or_else(|| {
+            let top = series.with(|s| {
+                s.iter()
+                    .flat_map(|(_, values)| values.iter().copied())
+                    .filter(|v| v.is_finite())
+                    .fold(0.0, f32::max)
+            });
+            (0.0, nice_ceil(top))
+        })
+    };
+    let hasData = move || series.with(|s| s.iter().any(|(_, values)| !values.is_empty()));
+
+    let grid = move || {
+        let (bottom, top) = range();
+        (0..=GRID_LINES)
+            .map(|i| {
+                let fraction = i as f32 / GRID_LINES as f32;
+                let y = MARGIN_TOP + plotHeight - fraction * plotHeight;
+                let value = bottom + fraction * (top - bottom);
+                view! {
+                    <line
+                        class="line-chart-grid"
+                        x1=format!("{MARGIN_LEFT}")
+                        x2=format!("{}", WIDTH - MARGIN_RIGHT)
+                        y1=format!("{y:.1}")
+                        y2=format!("{y:.1}")
+                    />
+                    <text
+                        class="line-chart-tick"
+                        x=format!("{}", MARGIN_LEFT - 6.0)
+                        y=format!("{y:.1}")
+                        text-anchor="end"
+                        dominant-baseline="middle"
+                    >
+                        {format_tick(value)}
+                    </text>
+                }
+            })
+            .collect_view()
+    };
+
+    let lines = move || {
+        let (bottom, top) = range();
+        let span = (top - bottom).max(f32::EPSILON);
+        series.with(|all| {
+            let longest = all.iter().map(|(_, v)| v.len()).max().unwrap_or(0);
+            let step = if longest > 1 { plotWidth / (longest - 1) as f32 } else { 0.0 };
+            all.iter()
+                .enumerate()
+                .filter(|(_, (_, values))| !values.is_empty())
+                .map(|(i, (_, values))| {
+                    let color = PALETTE[i % PALETTE.len()];
+                    let offset = longest - values.len();
+                    let points: Vec<(f32, f32)> = values
+                        .iter()
+                        .enumerate()
+                        .map(|(j, v)| {
+                            let x = MARGIN_LEFT + (offset + j) as f32 * step;
+                            let y = MARGIN_TOP + plotHeight - ((v - bottom) / span).clamp(0.0, 1.0) * plotHeight;
+                            (x, y)
+                        })
+                        .collect();
+                    // A lone sample has no line to draw, so mark it with a dot
+                    if let [(x, y)] = points[..] {
+                        return view! {
+                            <circle
+                                cx=format!("{x:.1}")
+                                cy=format!("{y:.1}")
+                                r="3"
+                                style=format!("fill: {color}")
+                            />
+                        }
+                            .into_any();
+                    }
+                    let points = points
+                        .iter()
+                        .map(|(x, y)| format!("{x:.1},{y:.1}"))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    view! {
+                        <polyline
+                            points=points
+                            fill="none"
+                            // A style rather than the attribute, so the palette's CSS variable resolves
+                            style=format!("stroke: {color}")
+                            stroke-width="1.5"
+                            stroke-linejoin="round"
+                        />
+                    }
+                        .into_any()
+                })
+                .collect_view()
+        })
+    };
+
+    let legend = move || {
+        series.with(|all| {
+            all.iter()
+                .enumerate()
+                .map(|(i, (label, _))| {
+                    view! {
+                        <span class="line-chart-legend-item">
+                            <span
+                                class="line-chart-swatch"
+                                style=format!("background-color: {}", PALETTE[i % PALETTE.len()])
+                            ></span>
+                            {label.clone()}
+                        </span>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="line-chart">
+            <svg viewBox=format!("0 0 {WIDTH} {HEIGHT}") role="img">
+                {grid}
+                {lines}
+                {move || {
+                    (!hasData())
+                        .then(|| {
+                            view! {
+                                <text
+                                    class="line-chart-empty"
+                                    x=format!("{}", MARGIN_LEFT + plotWidth / 2.0)
+                                    y=format!("{}", MARGIN_TOP + plotHeight / 2.0)
+                                    text-anchor="middle"
+                                    dominant-baseline="middle"
+                                >
+                                    "No data"
+                                </text>
+                            }
+                        })
+                }}
+                <text
+                    class="line-chart-axis-label"
+                    x=format!("{}", MARGIN_LEFT + plotWidth / 2.0)
+                    y=format!("{}", HEIGHT - 4.0)
+                    text-anchor="middle"
+                >
+                    {x_label}
+                </text>
+                <text
+                    class="line-chart-axis-label"
+                    transform=format!("translate(12 {}) rotate(-90)", MARGIN_TOP + plotHeight / 2.0)
+                    text-anchor="middle"
+                >
+                    {y_label}
+                </text>
+            </svg>
+            <div class="line-chart-legend">{legend}</div>
+        </div>
+    }
+}
@@ -6,11 +6,14 @@ use leptos::prelude::*;
 pub fn MetricCard(
     /// Title displayed at the top of the card
     title: String,
+    /// Highlights the card while an alert threshold is exceeded
+    #[prop(optional, into)]
+    alert: MaybeProp<bool>,
     /// Card content (typically a Gauge or metric rows)
     children: Children,
 ) -> impl IntoView {
     view! {
-        <div class="card">
+        <div class=move || if alert.get().unwrap_or(false) { "card card-alert" } else { "card" }>
             <div class="card-title">{title}</div>
             {children()}
         </div>
@@ -1,4 +1,13 @@
+pub mod api_panel;
+pub mod branding;
+pub mod density;
 pub mod gauge;
+pub mod line_chart;
 pub mod metric_card;
 pub mod nav;
+pub mod pager;
+pub mod refresh;
+pub mod sparkline;
+pub mod temperature;
+pub mod theme;
 pub mod toast;
@@ -1,10 +1,97 @@
 use leptos::prelude::*;
 use leptos_router::hooks::use_location;
+use spark_types::InventorySummary;
+
+use crate::components::api_panel::ApiPanel;
+use crate::components::branding::{site_name, BrandingContext};
+use crate::components::density::DensityToggle;
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::refresh::RefreshControls;
+use crate::components::temperature::TemperatureToggle;
+use crate::components::theme::ThemeToggle;
+
+#[server]
+async fn get_inventory_summary() -> Result<InventorySummary, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::collect_inventory_summary(&options).await)
+}
 
 #[component]
 pub fn Nav() -> impl IntoView {
     let location = use_location();
 
+    #[allow(unused_variables)]
+    let (summary, setSummary) = signal(Option::<InventorySummary>::None);
+
+    #[cfg(feature = "hydrate")]
+    {
+        use wasm_bindgen_futures::spawn_local;
+
+        let fetch = move || {
+            spawn_local(async move {
+                if let Ok(s) = get_inventory_summary().await {
+                    setSummary.set(Some(s));
+                }
+            });
+        };
+
+        fetch();
+
+        // Badges are ambient information, so a slow poll is enough
+        use_poll(30, fetch);
+    }
+
+    let containersBadge = move || {
+        summary.get().map(|s| {
+            let label = format!(
+                "{} of {} containers running",
+                s.containers_running, s.containers_total
+            );
+            let warning = (s.containers_unhealthy > 0).then(|| {
+                let warnLabel = format!("{} unhealthy", s.containers_unhealthy);
+                view! {
+                    <span
+                        class="nav-warning-dot"
+                        role="img"
+                        aria-label=warnLabel.clone()
+                        title=warnLabel
+                    ></span>
+                }
+            });
+            view! {
+                <span class="nav-badge" aria-label=label.clone() title=label>
+                    {format!("{}/{}", s.containers_running, s.containers_total)}
+                </span>
+                {warning}
+            }
+        })
+    };
+
+    let modelsBadge = move || {
+        summary.get().map(|s| {
+            let label = format!("{} models", s.models_total);
+            view! {
+                <span class="nav-badge" aria-label=label.clone() title=label>
+                    {s.models_total}
+                </span>
+            }
+        })
+    };
+
+    let branding = use_context::<BrandingContext>();
+    let brandInitial = move || site_name().chars().next().unwrap_or('S').to_uppercase().to_string();
+    let hostBadge = move || {
+        branding.and_then(|b| b.host_name.get()).map(|host| {
+            view! {
+                <span class="nav-host" title="Host this console runs on">
+                    {host}
+                </span>
+            }
+        })
+    };
+
     let dashboardClass = move || {
         if location.pathname.get() == "/" {
             "nav-item active"
@@ -29,11 +116,38 @@ pub fn Nav() -> impl IntoView {
         }
     };
 
+    let servicesClass = move || {
+        if location.pathname.get() == "/services" {
+            "nav-item active"
+        } else {
+            "nav-item"
+        }
+    };
+
+    let updatesClass = move || {
+        if location.pathname.get() == "/updates" {
+            "nav-item active"
+        } else {
+            "nav-item"
+        }
+    };
+
+    let storageClass = move || {
+        if location.pathname.get() == "/storage" {
+            "nav-item active"
+        } else {
+            "nav-item"
+        }
+    };
+
     view! {
         <nav class="nav-sidebar">
             <div class="nav-brand">
-                <div class="brand-icon">"S"</div>
-                <span class="brand-text">"Spark Console"</span>
+                <div class="brand-icon">{brandInitial}</div>
+                <div class="brand-names">
+                    <span class="brand-text">{site_name}</span>
+                    {hostBadge}
+                </div>
             </div>
             <ul class="nav-links">
                 <li class=dashboardClass>
@@ -46,33 +160,42 @@ pub fn Nav() -> impl IntoView {
                     <a href="/containers">
                         <span class="nav-icon">"\u{2338}"</span>
                         <span>"Containers"</span>
+                        {containersBadge}
                     </a>
                 </li>
                 <li class=modelsClass>
                     <a href="/models">
                         <span class="nav-icon">"\u{2B21}"</span>
                         <span>"Models"</span>
+                        {modelsBadge}
                     </a>
                 </li>
-                <li class="nav-item disabled">
-                    <span>
+                <li class=servicesClass>
+                    <a href="/services">
                         <span class="nav-icon">"\u{26EE}"</span>
                         <span>"Services"</span>
-                    </span>
+                    </a>
                 </li>
-                <li class="nav-item disabled">
-                    <span>
+                <li class=updatesClass>
+                    <a href="/updates">
                         <span class="nav-icon">"\u{21BB}"</span>
                         <span>"Updates"</span>
-                    </span>
+                    </a>
                 </li>
-                <li class="nav-item disabled">
-                    <span>
+                <li class=storageClass>
+                    <a href="/storage">
                         <span class="nav-icon">"\u{26C1}"</span>
                         <span>"Storage"</span>
-                    </span>
+                    </a>
                 </li>
             </ul>
+            <div class="nav-footer">
+                <RefreshControls />
+                <TemperatureToggle />
+                <ThemeToggle />
+                <DensityToggle />
+                <ApiPanel />
+            </div>
         </nav>
     }
 }
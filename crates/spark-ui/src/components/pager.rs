@@ -0,0 +1,57 @@
+use leptos::prelude::*;
+
+/// Rows per page for tables that can grow to hundreds of entries.
+pub const DEFAULT_PAGE_SIZE: usize = 50;
+
+/// The page to show of `total` rows, clamped so a list that shrank between polls
+/// never leaves the table on an empty page past the end.
+pub fn clamp_page(page: usize, total: usize, pageSize: usize) -> usize {
+    let pages = total.div_ceil(pageSize.max(1)).max(1);
+    page.min(pages - 1)
+}
+
+/// The rows of `items` on `page`, after clamping it like [`clamp_page`].
+pub fn page_slice<T: Clone>(items: &[T], page: usize, pageSize: usize) -> Vec<T> {
+    let start = clamp_page(page, items.len(), pageSize) * pageSize;
+    items.iter().skip(start).take(pageSize).cloned().collect()
+}
+
+/// "Showing 51–100 of 230" with previous/next buttons, under a paginated table.
+/// Renders nothing while everything fits on one page. `page` is owned by the caller,
+/// outside any closure that re-renders the table, so it survives each poll.
+#[component]
+pub fn Pager(
+    page: RwSignal<usize>,
+    total: usize,
+    #[prop(default = DEFAULT_PAGE_SIZE)] page_size: usize,
+) -> impl IntoView {
+    (total > page_size).then(|| {
+        let current = move || clamp_page(page.get(), total, page_size);
+        let lastPage = clamp_page(usize::MAX, total, page_size);
+        let onLastPage = move || current() == lastPage;
+        view! {
+            <div class="pager">
+                <span class="pager-status">
+                    {move || {
+                        let start = current() * page_size;
+                        format!("Showing {}\u{2013}{} of {total}", start + 1, (start + page_size).min(total))
+                    }}
+                </span>
+                <button
+                    class="btn btn-sm btn-ghost"
+                    disabled=move || current() == 0
+                    on:click=move |_| page.set(current().saturating_sub(1))
+                >
+                    "Previous"
+                </button>
+                <button
+                    class="btn btn-sm btn-ghost"
+                    disabled=onLastPage
+                    on:click=move |_| page.set((current() + 1).min(lastPage))
+                >
+                    "Next"
+                </button>
+            </div>
+        }
+    })
+}
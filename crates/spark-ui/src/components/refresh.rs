@@ -0,0 +1,145 @@
+use leptos::prelude::*;
+
+use crate::ui_config::{UiOptions, DEFAULT_REFRESH_SECS};
+
+/// Choices offered in the nav's refresh-rate dropdown, in seconds.
+pub const REFRESH_RATES: &[u64] = &[1, 2, 5, 10, 30, 60];
+
+#[cfg(feature = "hydrate")]
+const STORAGE_KEY: &str = "spark.refresh_secs";
+
+#[server]
+async fn get_ui_options() -> Result<UiOptions, ServerFnError> {
+    Ok(use_context::<UiOptions>().unwrap_or_default())
+}
+
+/// The refresh rate picked in the nav, shared by every polling page.
+#[derive(Clone, Copy)]
+pub struct RefreshContext {
+    pub rate_secs: RwSignal<u64>,
+    /// Set from the nav to freeze every page on its current data.
+    pub paused: RwSignal<bool>,
+}
+
+impl RefreshContext {
+    /// Change the rate and remember it in `localStorage` for the next visit.
+    pub fn set_rate(&self, secs: u64) {
+        self.rate_secs.set(secs);
+        #[cfg(feature = "hydrate")]
+        if let Some(storage) = window().local_storage().ok().flatten() {
+            let _ = storage.set_item(STORAGE_KEY, &secs.to_string());
+        }
+    }
+}
+
+/// Provides the refresh rate: the one saved in `localStorage`, else the server's
+/// `ui.refresh_secs`. Place this once near the root of the app.
+#[component]
+pub fn RefreshProvider(children: Children) -> impl IntoView {
+    let ctx = RefreshContext {
+        rate_secs: RwSignal::new(DEFAULT_REFRESH_SECS),
+        paused: RwSignal::new(false),
+    };
+    provide_context(ctx);
+
+    #[cfg(feature = "hydrate")]
+    {
+        let saved = window()
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+            .and_then(|value| value.parse::<u64>().ok())
+            .filter(|secs| *secs > 0);
+        match saved {
+            Some(secs) => ctx.rate_secs.set(secs),
+            None => wasm_bindgen_futures::spawn_local(async move {
+                if let Ok(options) = get_ui_options().await {
+                    ctx.rate_secs.set(options.refresh_secs.max(1));
+                }
+            }),
+        }
+    }
+
+    children()
+}
+
+/// Call `fetch` at the chosen refresh rate, but never more often than every `minSecs`,
+/// so expensive listings keep their own floor. The interval is restarted when the rate
+/// changes, stopped while refresh is paused (with one immediate fetch on resume), and
+/// cleared with the calling component.
+#[cfg(feature = "hydrate")]
+pub fn use_poll(minSecs: u64, fetch: impl Fn() + Clone + 'static) {
+    let refresh = use_context::<RefreshContext>();
+    let handle = StoredValue::new(Option::<IntervalHandle>::None);
+    let clear = move || {
+        handle.update_value(|h| {
+            if let Some(h) = h.take() {
+                h.clear();
+            }
+        })
+    };
+
+    Effect::new(move |wasPaused: Option<bool>| {
+        let rate = refresh.map(|r| r.rate_secs.get()).unwrap_or(DEFAULT_REFRESH_SECS);
+        let paused = refresh.is_some_and(|r| r.paused.get());
+        clear();
+        if paused {
+            return true;
+        }
+        if wasPaused == Some(true) {
+            fetch();
+        }
+        let interval = std::time::Duration::from_secs(rate.max(minSecs));
+        handle.set_value(set_interval_with_handle(fetch.clone(), interval).ok());
+        false
+    });
+    on_cleanup(clear);
+}
+
+/// The nav's refresh-rate dropdown and pause toggle.
+#[component]
+pub fn RefreshControls() -> impl IntoView {
+    let refresh = use_context::<RefreshContext>();
+    let paused = move || refresh.is_some_and(|r| r.paused.get());
+
+    view! {
+        <button
+            class=move || if paused() { "nav-pause paused" } else { "nav-pause" }
+            title="Pause or resume auto-refresh on every page"
+            on:click=move |_| {
+                if let Some(refresh) = refresh {
+                    refresh.paused.update(|p| *p = !*p);
+                }
+            }
+        >
+            {move || if paused() { "\u{25B6} Resume" } else { "\u{23F8} Pause" }}
+        </button>
+        {move || paused().then(|| view! { <span class="nav-paused">"Auto-refresh paused"</span> })}
+        <label
+            class="nav-setting"
+            title="How often pages refresh. Containers and models never poll faster than 5s and 30s."
+        >
+            <span>"Refresh"</span>
+            <select on:change=move |ev| {
+                if let (Some(refresh), Ok(secs)) = (refresh, event_target_value(&ev).parse()) {
+                    refresh.set_rate(secs);
+                }
+            }>
+                {REFRESH_RATES
+                    .iter()
+                    .map(|&secs| {
+                        view! {
+                            <option
+                                value=secs.to_string()
+                                prop:selected=move || refresh.is_some_and(|r| r.rate_secs.get() == secs)
+                            >
+                                {format!("{secs}s")}
+                            </option>
+                        }
+                    })
+                    .collect_view()}
+            </select>
+        </label>
+    }
+}
@@ -0,0 +1,61 @@
+use leptos::prelude::*;
+
+/// Small SVG line chart of recent samples, oldest on the left.
+///
+/// Scales from 0 up to `max`, or to the largest sample when `max` isn't given,
+/// and stretches to the width of its container.
+#[component]
+pub fn Sparkline(
+    /// Samples to plot, oldest first
+    #[prop(into)]
+    values: Signal<Vec<f32>>,
+    /// Value drawn at the top edge
+    #[prop(optional)]
+    max: Option<f32>,
+    /// Line color; defaults to the accent color
+    #[prop(optional, into)]
+    color: Option<String>,
+) -> impl IntoView {
+    let WIDTH: f32 = 100.0;
+    let HEIGHT: f32 = 24.0;
+
+    let points = move || {
+        values.with(|samples| {
+            let top = max
+                .unwrap_or_else(|| samples.iter().copied().fold(0.0, f32::max))
+                .max(f32::EPSILON);
+            let step = if samples.len() > 1 {
+                WIDTH / (samples.len() - 1) as f32
+            } else {
+                0.0
+            };
+            samples
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let y = HEIGHT - (v / top).clamp(0.0, 1.0) * HEIGHT;
+                    format!("{:.1},{:.1}", i as f32 * step, y)
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+    };
+
+    view! {
+        <svg
+            class="sparkline"
+            viewBox=format!("0 0 {WIDTH} {HEIGHT}")
+            preserveAspectRatio="none"
+            aria-hidden="true"
+        >
+            <polyline
+                points=points
+                fill="none"
+                // A style rather than the attribute, so the CSS variable resolves
+                style=format!("stroke: {}", color.unwrap_or_else(|| "var(--accent)".to_string()))
+                stroke-width="1.5"
+                vector-effect="non-scaling-stroke"
+            />
+        </svg>
+    }
+}
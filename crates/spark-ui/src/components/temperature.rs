@@ -0,0 +1,125 @@
+use leptos::prelude::*;
+
+#[cfg(feature = "hydrate")]
+const STORAGE_KEY: &str = "spark.temperature_unit";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+impl TemperatureUnit {
+    /// Convert a Celsius reading to this unit.
+    pub fn convert(self, celsius: f32) -> f32 {
+        match self {
+            TemperatureUnit::Celsius => celsius,
+            TemperatureUnit::Fahrenheit => celsius * 9.0 / 5.0 + 32.0,
+        }
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "\u{00B0}C",
+            TemperatureUnit::Fahrenheit => "\u{00B0}F",
+        }
+    }
+
+    /// A Celsius reading in this unit, rounded, without the symbol.
+    pub fn format_value(self, celsius: f32) -> String {
+        format!("{:.0}", self.convert(celsius))
+    }
+
+    /// A Celsius reading in this unit, e.g. "149°F".
+    pub fn format(self, celsius: f32) -> String {
+        format!("{}{}", self.format_value(celsius), self.symbol())
+    }
+
+    #[cfg(feature = "hydrate")]
+    fn key(self) -> &'static str {
+        match self {
+            TemperatureUnit::Celsius => "c",
+            TemperatureUnit::Fahrenheit => "f",
+        }
+    }
+}
+
+/// The temperature unit picked in the nav; readings are always collected in Celsius.
+#[derive(Clone, Copy)]
+pub struct TemperatureContext {
+    pub unit: RwSignal<TemperatureUnit>,
+}
+
+impl TemperatureContext {
+    /// Change the unit and remember it in `localStorage` for the next visit.
+    pub fn set_unit(&self, unit: TemperatureUnit) {
+        self.unit.set(unit);
+        #[cfg(feature = "hydrate")]
+        if let Some(storage) = window().local_storage().ok().flatten() {
+            let _ = storage.set_item(STORAGE_KEY, unit.key());
+        }
+    }
+}
+
+/// The unit to display temperatures in; Celsius outside a [`TemperatureProvider`].
+pub fn use_temperature_unit() -> Signal<TemperatureUnit> {
+    let ctx = use_context::<TemperatureContext>();
+    Signal::derive(move || ctx.map(|c| c.unit.get()).unwrap_or_default())
+}
+
+/// Provides the temperature unit saved in `localStorage`, defaulting to Celsius.
+/// Place this once near the root of the app.
+#[component]
+pub fn TemperatureProvider(children: Children) -> impl IntoView {
+    let ctx = TemperatureContext {
+        unit: RwSignal::new(TemperatureUnit::default()),
+    };
+    provide_context(ctx);
+
+    #[cfg(feature = "hydrate")]
+    if let Some(saved) = window()
+        .local_storage()
+        .ok()
+        .flatten()
+        .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten())
+    {
+        if saved == TemperatureUnit::Fahrenheit.key() {
+            ctx.unit.set(TemperatureUnit::Fahrenheit);
+        }
+    }
+
+    children()
+}
+
+/// The nav's °C/°F switch.
+#[component]
+pub fn TemperatureToggle() -> impl IntoView {
+    let ctx = use_context::<TemperatureContext>();
+    let unit = use_temperature_unit();
+    let option = move |choice: TemperatureUnit| {
+        view! {
+            <button
+                class=move || if unit.get() == choice { "segmented-option active" } else { "segmented-option" }
+                on:click=move |_| {
+                    if let Some(ctx) = ctx {
+                        ctx.set_unit(choice);
+                    }
+                }
+            >
+                {choice.symbol()}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="nav-setting">
+            <span>"Temperature"</span>
+            <div class="segmented" role="group" aria-label="Temperature unit">
+                {option(TemperatureUnit::Celsius)}
+                {option(TemperatureUnit::Fahrenheit)}
+            </div>
+        </div>
+    }
+}
+
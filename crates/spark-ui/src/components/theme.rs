@@ -0,0 +1,55 @@
+use leptos::prelude::*;
+
+/// `localStorage` key for the chosen theme, also read by [`THEME_INIT_SCRIPT`].
+#[cfg(feature = "hydrate")]
+const STORAGE_KEY: &str = "spark.theme";
+
+/// Runs in `<head>` before first paint, so a saved light theme doesn't flash dark.
+pub const THEME_INIT_SCRIPT: &str = r#"try{var t=localStorage.getItem("spark.theme");if(t==="light"||t==="dark")document.documentElement.setAttribute("data-theme",t)}catch(e){}"#;
+
+/// The nav's dark/light switch. It sets `data-theme` on `<html>`, which selects the
+/// palette in the stylesheet, and remembers the choice in `localStorage`.
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (theme, setTheme) = signal("dark");
+
+    #[cfg(feature = "hydrate")]
+    if let Some(root) = document().document_element() {
+        if root.get_attribute("data-theme").as_deref() == Some("light") {
+            setTheme.set("light");
+        }
+    }
+
+    let option = move |choice: &'static str, label: &'static str| {
+        view! {
+            <button
+                class=move || if theme.get() == choice { "segmented-option active" } else { "segmented-option" }
+                on:click=move |_| {
+                    setTheme.set(choice);
+                    #[cfg(feature = "hydrate")]
+                    {
+                        if let Some(root) = document().document_element() {
+                            let _ = root.set_attribute("data-theme", choice);
+                        }
+                        if let Some(storage) = window().local_storage().ok().flatten() {
+                            let _ = storage.set_item(STORAGE_KEY, choice);
+                        }
+                    }
+                }
+            >
+                {label}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="nav-setting">
+            <span>"Theme"</span>
+            <div class="segmented" role="group" aria-label="Theme">
+                {option("dark", "Dark")}
+                {option("light", "Light")}
+            </div>
+        </div>
+    }
+}
@@ -70,7 +70,7 @@ pub fn ToastProvider(children: Children) -> impl IntoView {
         {children()}
         <div class="toast-container">
             <For
-                each=move || toasts.get()
+                each=move || ctx.toasts.get()
                 key=|toast| toast.id
                 let:toast
             >
@@ -3,6 +3,7 @@
 pub mod app;
 pub mod components;
 pub mod pages;
+pub mod ui_config;
 
 pub use app::{shell, App};
 
@@ -1,11 +1,48 @@
 use leptos::prelude::*;
-use spark_types::{ContainerActionResult, ContainerStatus, ContainerSummary};
+use spark_types::{
+    Capabilities, ContainerActionResult, ContainerHealth, ContainerStats, ContainerStatus,
+    ContainerSummary,
+};
+use std::collections::HashMap;
 
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::toast::ToastContext;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::ToastLevel;
+
+/// The listing without usage, so the page renders before `docker stats` finishes.
 #[server]
 async fn get_containers() -> Result<Vec<ContainerSummary>, ServerFnError> {
-    spark_providers::docker::collect()
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::docker::collect(&options.docker, false)
         .await
-        .map_err(|e| ServerFnError::new(e))
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn get_container_stats() -> Result<Vec<ContainerStats>, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::docker::collect_stats(&options.docker)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+/// Fill in usage from the latest stats sample.
+#[cfg_attr(not(feature = "hydrate"), allow(dead_code))]
+fn merge_stats(containers: &mut [ContainerSummary], stats: &HashMap<String, ContainerStats>) {
+    for container in containers {
+        if let Some(sample) = stats.get(&container.id) {
+            container.apply_stats(sample);
+        }
+    }
+}
+
+#[server]
+async fn get_capabilities() -> Result<Capabilities, ServerFnError> {
+    Ok(use_context::<Capabilities>().unwrap_or_default())
 }
 
 #[server]
@@ -13,7 +50,9 @@ async fn container_action(
     container_id: String,
     action: String,
 ) -> Result<ContainerActionResult, ServerFnError> {
-    Ok(spark_providers::docker::execute_action(&container_id, &action).await)
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::docker::execute_action(&options.docker, &container_id, &action).await)
 }
 
 fn format_net_bytes(bytes: u64) -> String {
@@ -40,6 +79,13 @@ fn format_mem_bytes(bytes: u64) -> String {
     }
 }
 
+/// Shown in place of usage until the first stats sample for a container arrives.
+const STATS_PENDING: &str = "\u{2026}";
+
+/// Writable layers beyond this usually mean logs or data are being written
+/// into the container instead of a volume.
+const LARGE_RW_LAYER_BYTES: u64 = 1_073_741_824;
+
 fn status_class(status: &ContainerStatus) -> &'static str {
     match status {
         ContainerStatus::Running => "status-running",
@@ -59,6 +105,49 @@ fn status_label(status: &ContainerStatus) -> &'static str {
     }
 }
 
+/// The status chips above the container list.
+#[derive(Clone, Copy, PartialEq)]
+enum StatusFilter {
+    All,
+    Running,
+    /// Anything not running, so exited, created and dead containers all show up here.
+    Stopped,
+}
+
+impl StatusFilter {
+    fn label(self) -> &'static str {
+        match self {
+            StatusFilter::All => "All",
+            StatusFilter::Running => "Running",
+            StatusFilter::Stopped => "Stopped",
+        }
+    }
+
+    fn matches(self, status: &ContainerStatus) -> bool {
+        match self {
+            StatusFilter::All => true,
+            StatusFilter::Running => *status == ContainerStatus::Running,
+            StatusFilter::Stopped => *status != ContainerStatus::Running,
+        }
+    }
+}
+
+/// Whether `container`'s name or image contains `needle`, which must already be lowercase.
+fn matches_text(container: &ContainerSummary, needle: &str) -> bool {
+    needle.is_empty()
+        || container.name.to_lowercase().contains(needle)
+        || container.image.to_lowercase().contains(needle)
+}
+
+/// CSS class and tooltip for the health dot.
+fn health_indicator(health: ContainerHealth) -> (&'static str, &'static str) {
+    match health {
+        ContainerHealth::Healthy => ("health-healthy", "Healthy"),
+        ContainerHealth::Unhealthy => ("health-unhealthy", "Unhealthy"),
+        ContainerHealth::Starting => ("health-starting", "Health: starting"),
+    }
+}
+
 #[component]
 pub fn ContainersPage() -> impl IntoView {
     #[allow(unused_variables)]
@@ -66,35 +155,90 @@ pub fn ContainersPage() -> impl IntoView {
         signal(Option::<Result<Vec<ContainerSummary>, String>>::None);
     #[allow(unused_variables)]
     let (pendingAction, setPendingAction) = signal(Option::<String>::None);
+    // Persistent problems only; one-off action results go to toasts
     #[allow(unused_variables)]
-    let (actionError, setActionError) = signal(Option::<String>::None);
+    let (statsError, setStatsError) = signal(Option::<String>::None);
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
     #[allow(unused_variables)]
     let (expandedIds, setExpandedIds) = signal(Vec::<String>::new());
+    #[allow(unused_variables)]
+    let (capabilities, setCapabilities) = signal(Option::<Capabilities>::None);
+    // Usage from the last stats sample, reapplied to each fresh listing so the numbers
+    // don't blank out while the next sample is taken
+    let knownStats = StoredValue::new(HashMap::<String, ContainerStats>::new());
+    // Container awaiting confirmation of a remove, and whether force was ticked for it
+    let (confirmRemove, setConfirmRemove) = signal(Option::<String>::None);
+    let (forceRemove, setForceRemove) = signal(false);
+    // Applied to the fetched list on every render, so filtering never waits for a poll
+    let (textFilter, setTextFilter) = signal(String::new());
+    let (statusFilter, setStatusFilter) = signal(StatusFilter::All);
+    let hasContainers = move || containers.with(|c| matches!(c, Some(Ok(list)) if !list.is_empty()));
+    let statusChip = move |choice: StatusFilter| {
+        view! {
+            <button
+                class=move || {
+                    if statusFilter.get() == choice { "segmented-option active" } else { "segmented-option" }
+                }
+                on:click=move |_| setStatusFilter.set(choice)
+            >
+                {choice.label()}
+            </button>
+        }
+    };
+
+    // Until capabilities arrive, keep the buttons visible rather than flashing them in.
+    let actionHidden = move |action: &str| {
+        capabilities
+            .get()
+            .is_some_and(|caps| !caps.allows_action(action))
+    };
 
     #[cfg(feature = "hydrate")]
     {
         use wasm_bindgen_futures::spawn_local;
 
+        spawn_local(async move {
+            if let Ok(caps) = get_capabilities().await {
+                setCapabilities.set(Some(caps));
+            }
+        });
+
         let fetch = move || {
             spawn_local(async move {
                 let result = get_containers().await.map_err(|e| e.to_string());
-                setContainers.set(Some(result));
+                setContainers.set(Some(result.map(|mut list| {
+                    knownStats.with_value(|stats| merge_stats(&mut list, stats));
+                    list
+                })));
+
+                match get_container_stats().await {
+                    Ok(sample) => {
+                        setStatsError.set(None);
+                        // Stored before the update so the re-render sees which containers have usage
+                        knownStats.set_value(sample.into_iter().map(|s| (s.id.clone(), s)).collect());
+                        setContainers.update(|c| {
+                            if let Some(Ok(list)) = c {
+                                knownStats.with_value(|stats| merge_stats(list, stats));
+                            }
+                        });
+                    }
+                    Err(e) => setStatsError.set(Some(format!("Usage stats unavailable: {e}"))),
+                }
             });
         };
 
         fetch();
-        let handle = set_interval_with_handle(fetch, std::time::Duration::from_secs(5))
-            .expect("failed to set interval");
-        on_cleanup(move || handle.clear());
+        use_poll(5, fetch);
     }
 
     view! {
         <div class="dashboard-header">
             <h1>"Containers"</h1>
-            <p class="subtitle">"Docker container management"</p>
+            <p class="subtitle">"Container management"</p>
         </div>
         {move || {
-            actionError.get().map(|msg| {
+            statsError.get().map(|msg| {
                 view! {
                     <div class="container-action-error">
                         <p>{msg}</p>
@@ -102,6 +246,23 @@ pub fn ContainersPage() -> impl IntoView {
                 }
             })
         }}
+        // Outside the list's closure so the input keeps focus while the list re-renders
+        <Show when=hasContainers>
+            <div class="container-toolbar">
+                <input
+                    class="table-filter"
+                    type="search"
+                    placeholder="Filter by name or image"
+                    prop:value=move || textFilter.get()
+                    on:input=move |ev| setTextFilter.set(event_target_value(&ev))
+                />
+                <div class="segmented" role="group" aria-label="Container status">
+                    {statusChip(StatusFilter::All)}
+                    {statusChip(StatusFilter::Running)}
+                    {statusChip(StatusFilter::Stopped)}
+                </div>
+            </div>
+        </Show>
         {move || {
             match containers.get() {
                 None => {
@@ -125,19 +286,50 @@ pub fn ContainersPage() -> impl IntoView {
                     if list.is_empty() {
                         view! {
                             <div class="container-empty">
-                                <p>"No containers found"</p>
+                                <p>
+                                    {move || match capabilities.get() {
+                                        Some(caps) if !caps.container_runtime.is_empty() => {
+                                            format!("No containers found ({})", caps.container_runtime)
+                                        }
+                                        _ => "No containers found".to_string(),
+                                    }}
+                                </p>
                             </div>
                         }
                             .into_any()
                     } else {
-                        let items = list
+                        let needle = textFilter.get().to_lowercase();
+                        let status = statusFilter.get();
+                        let shown: Vec<ContainerSummary> = list
+                            .into_iter()
+                            .filter(|c| status.matches(&c.status) && matches_text(c, &needle))
+                            .collect();
+                        if shown.is_empty() {
+                            return view! {
+                                <div class="container-empty">
+                                    <p>"No containers match the filter"</p>
+                                </div>
+                            }
+                                .into_any();
+                        }
+                        let items = shown
                             .into_iter()
                             .map(|c| {
                                 let containerId = c.id.clone();
                                 let containerName = c.name.clone();
                                 let containerImage = c.image.clone();
                                 let containerStatus = c.status.clone();
-                                let stateText = c.state_text.clone();
+                                let exitCode = c.exit_code;
+                                let exitLabel = c.exit_label();
+                                let stateText = match (c.exit_code, c.since.clone()) {
+                                    (Some(_), Some(since)) => format!("{since} ago"),
+                                    (Some(_), None) => String::new(),
+                                    (None, Some(since)) if c.state_text.starts_with("Up ") => {
+                                        format!("Up {since}")
+                                    }
+                                    _ => c.state_text.clone(),
+                                };
+                                let hasStats = knownStats.with_value(|s| s.contains_key(&c.id));
                                 let cpuPct = c.cpu_pct;
                                 let memUsage = c.memory_usage_bytes;
                                 let memLimit = c.memory_limit_bytes;
@@ -148,10 +340,19 @@ pub fn ContainersPage() -> impl IntoView {
                                 let restartPolicy = c.restart_policy.clone();
                                 let created = c.created.clone();
                                 let mounts = c.mounts.clone();
+                                let cpuLimit = c.cpu_limit;
+                                let memCap = c.mem_limit_bytes;
+                                let env = c.env.clone();
+                                let rwSize = c.rw_size_bytes;
+                                let rootFsSize = c.root_fs_size_bytes;
                                 let isRunning = containerStatus == ContainerStatus::Running;
                                 let isStopped = containerStatus == ContainerStatus::Stopped;
+                                let isPaused = containerStatus == ContainerStatus::Paused;
+                                // `docker rm -f` kills these first, so removing them needs force
+                                let needsForce = isRunning || isPaused;
                                 let statusCls = status_class(&containerStatus);
                                 let statusLbl = status_label(&containerStatus);
+                                let health = c.health;
 
                                 // Clone IDs for each closure that needs them
                                 let idForToggle = containerId.clone();
@@ -174,31 +375,34 @@ pub fn ContainersPage() -> impl IntoView {
                                         let cid = containerId.clone();
                                         move |_| {
                                             let cid = cid.clone();
-                                            setActionError.set(None);
                                             setPendingAction.set(Some(cid.clone()));
                                             #[cfg(feature = "hydrate")]
                                             {
                                                 use wasm_bindgen_futures::spawn_local;
                                                 let cid2 = cid.clone();
                                                 spawn_local(async move {
-                                                    match container_action(
+                                                    let result = container_action(
                                                         cid2,
                                                         action.to_string(),
                                                     )
-                                                    .await
-                                                    {
-                                                        Ok(res) if !res.success => {
-                                                            setActionError.set(Some(res.message));
-                                                        }
-                                                        Err(e) => {
-                                                            setActionError
-                                                                .set(Some(e.to_string()));
+                                                    .await;
+                                                    if let Some(toasts) = toasts {
+                                                        match result {
+                                                            Ok(res) if res.success => {
+                                                                toasts.push(res.message, ToastLevel::Success)
+                                                            }
+                                                            Ok(res) => toasts.push(res.message, ToastLevel::Error),
+                                                            Err(e) => toasts.push(e.to_string(), ToastLevel::Error),
                                                         }
-                                                        _ => {}
                                                     }
                                                     let result = get_containers()
                                                         .await
-                                                        .map_err(|e| e.to_string());
+                                                        .map_err(|e| e.to_string())
+                                                        .map(|mut list| {
+                                                            knownStats
+                                                                .with_value(|stats| merge_stats(&mut list, stats));
+                                                            list
+                                                        });
                                                     setContainers.set(Some(result));
                                                     setPendingAction.set(None);
                                                 });
@@ -210,11 +414,28 @@ pub fn ContainersPage() -> impl IntoView {
                                 let onStart = makeAction("start");
                                 let onStop = makeAction("stop");
                                 let onRestart = makeAction("restart");
+                                let onPause = makeAction("pause");
+                                let onUnpause = makeAction("unpause");
+                                let onKill = makeAction("kill");
+                                let doRemove = makeAction("remove");
+                                let onConfirmRemove = move |ev: leptos::ev::MouseEvent| {
+                                    setConfirmRemove.set(None);
+                                    doRemove(ev);
+                                };
+                                let idForConfirm = containerId.clone();
+                                let onRemove = move |_| {
+                                    setForceRemove.set(false);
+                                    setConfirmRemove.set(Some(idForConfirm.clone()));
+                                };
 
                                 let hasDetails = !ports.is_empty()
                                     || !runtime.is_empty()
                                     || !restartPolicy.is_empty()
-                                    || !mounts.is_empty();
+                                    || !mounts.is_empty()
+                                    || rwSize.is_some()
+                                    || cpuLimit.is_some()
+                                    || memCap.is_some()
+                                    || !env.is_empty();
 
                                 // Clone containerId for each closure that checks pending
                                 let idPend1 = containerId.clone();
@@ -223,6 +444,13 @@ pub fn ContainersPage() -> impl IntoView {
                                 let idPend4 = containerId.clone();
                                 let idPend5 = containerId.clone();
                                 let idPend6 = containerId.clone();
+                                let idPend7 = containerId.clone();
+                                let idPend8 = containerId.clone();
+                                let idPend9 = containerId.clone();
+                                let idPend10 = containerId.clone();
+                                let idPend11 = containerId.clone();
+                                let idConfirm = containerId.clone();
+                                let nameForConfirm = containerName.clone();
 
                                 // Clone containerId for each closure that checks expanded
                                 let idExp1 = containerId.clone();
@@ -237,8 +465,31 @@ pub fn ContainersPage() -> impl IntoView {
                                                 )></span>
                                                 <span class="container-name">{containerName}</span>
                                                 <span class="container-status-text">{statusLbl}</span>
+                                                {health
+                                                    .map(|h| {
+                                                        let (cls, title) = health_indicator(h);
+                                                        view! {
+                                                            <span
+                                                                class=format!("health-dot {cls}")
+                                                                title=title
+                                                            ></span>
+                                                        }
+                                                    })}
                                             </div>
-                                            <span class="container-state-detail">{stateText}</span>
+                                            <span class="container-state-detail">
+                                                {exitCode
+                                                    .zip(exitLabel)
+                                                    .map(|(code, label)| {
+                                                        view! {
+                                                            <span class=if code == 0 {
+                                                                "container-exit-code"
+                                                            } else {
+                                                                "container-exit-code exit-nonzero"
+                                                            }>{label}</span>
+                                                        }
+                                                    })}
+                                                {stateText}
+                                            </span>
                                         </div>
                                         <div class="container-image">{containerImage}</div>
 
@@ -248,39 +499,52 @@ pub fn ContainersPage() -> impl IntoView {
                                                     <div class="stat-pair">
                                                         <span class="stat-label">"CPU"</span>
                                                         <span class="stat-value">
-                                                            {format!("{:.1}%", cpuPct)}
+                                                            {if hasStats {
+                                                                format!("{:.1}%", cpuPct)
+                                                            } else {
+                                                                STATS_PENDING.to_string()
+                                                            }}
                                                         </span>
                                                     </div>
                                                     <div class="stat-pair">
                                                         <span class="stat-label">"Memory"</span>
                                                         <span class="stat-value">
-                                                            {format!(
-                                                                "{} / {}",
-                                                                format_mem_bytes(memUsage),
-                                                                format_mem_bytes(memLimit),
-                                                            )}
+                                                            {if hasStats {
+                                                                format!(
+                                                                    "{} / {}",
+                                                                    format_mem_bytes(memUsage),
+                                                                    format_mem_bytes(memLimit),
+                                                                )
+                                                            } else {
+                                                                STATS_PENDING.to_string()
+                                                            }}
                                                         </span>
                                                     </div>
                                                     <div class="stat-pair">
                                                         <span class="stat-label">"Net I/O"</span>
                                                         <span class="stat-value">
-                                                            {format!(
-                                                                "{} / {}",
-                                                                format_net_bytes(netRx),
-                                                                format_net_bytes(netTx),
-                                                            )}
+                                                            {if hasStats {
+                                                                format!(
+                                                                    "{} / {}",
+                                                                    format_net_bytes(netRx),
+                                                                    format_net_bytes(netTx),
+                                                                )
+                                                            } else {
+                                                                STATS_PENDING.to_string()
+                                                            }}
                                                         </span>
                                                     </div>
                                                 </div>
                                             }
                                                 .into_any()
                                         } else {
-                                            view! {}.into_any()
+                                            ().into_any()
                                         }}
 
                                         <div class="container-actions">
                                             <button
                                                 class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("start")
                                                 disabled=move || {
                                                     isRunning
                                                         || pendingAction.get().as_ref() == Some(&idPend1)
@@ -297,6 +561,7 @@ pub fn ContainersPage() -> impl IntoView {
                                             </button>
                                             <button
                                                 class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("stop")
                                                 disabled=move || {
                                                     isStopped
                                                         || pendingAction.get().as_ref() == Some(&idPend3)
@@ -313,6 +578,7 @@ pub fn ContainersPage() -> impl IntoView {
                                             </button>
                                             <button
                                                 class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("restart")
                                                 disabled=move || {
                                                     !isRunning
                                                         || pendingAction.get().as_ref() == Some(&idPend5)
@@ -327,6 +593,55 @@ pub fn ContainersPage() -> impl IntoView {
                                                     }
                                                 }}
                                             </button>
+                                            <button
+                                                class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("pause")
+                                                disabled=move || {
+                                                    !isRunning
+                                                        || pendingAction.get().as_ref() == Some(&idPend7)
+                                                }
+                                                on:click=onPause
+                                            >
+                                                "Pause"
+                                            </button>
+                                            <button
+                                                class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("unpause")
+                                                disabled=move || {
+                                                    !isPaused
+                                                        || pendingAction.get().as_ref() == Some(&idPend8)
+                                                }
+                                                on:click=onUnpause
+                                            >
+                                                "Unpause"
+                                            </button>
+                                            <button
+                                                class="btn btn-sm btn-ghost"
+                                                hidden=move || actionHidden("kill")
+                                                disabled=move || {
+                                                    !needsForce
+                                                        || pendingAction.get().as_ref() == Some(&idPend9)
+                                                }
+                                                on:click=onKill
+                                            >
+                                                "Kill"
+                                            </button>
+                                            <button
+                                                class="btn btn-sm btn-danger"
+                                                hidden=move || actionHidden("remove")
+                                                disabled=move || {
+                                                    pendingAction.get().as_ref() == Some(&idPend10)
+                                                }
+                                                on:click=onRemove
+                                            >
+                                                "Remove"
+                                            </button>
+                                            <a
+                                                class="btn btn-sm btn-ghost"
+                                                href=format!("/containers/{containerId}/logs")
+                                            >
+                                                "Logs"
+                                            </a>
                                             {if hasDetails {
                                                 view! {
                                                     <button
@@ -344,16 +659,64 @@ pub fn ContainersPage() -> impl IntoView {
                                                 }
                                                     .into_any()
                                             } else {
-                                                view! {}.into_any()
+                                                ().into_any()
                                             }}
                                         </div>
 
+                                        <Show when=move || {
+                                            confirmRemove.get().as_ref() == Some(&idConfirm)
+                                        }>
+                                            <div class="container-confirm">
+                                                <p>
+                                                    "Remove " {nameForConfirm.clone()}
+                                                    "? Its writable layer will be deleted."
+                                                </p>
+                                                {needsForce
+                                                    .then(|| {
+                                                        view! {
+                                                            <label class="container-confirm-force">
+                                                                <input
+                                                                    type="checkbox"
+                                                                    prop:checked=move || forceRemove.get()
+                                                                    on:change=move |ev| {
+                                                                        setForceRemove.set(event_target_checked(&ev))
+                                                                    }
+                                                                />
+                                                                "Force: stop and remove the running container"
+                                                            </label>
+                                                        }
+                                                    })}
+                                                <div class="container-actions">
+                                                    <button
+                                                        class="btn btn-sm btn-danger"
+                                                        disabled={
+                                                            let idPend11 = idPend11.clone();
+                                                            move || {
+                                                                (needsForce && !forceRemove.get())
+                                                                    || pendingAction.get().as_ref() == Some(&idPend11)
+                                                            }
+                                                        }
+                                                        on:click=onConfirmRemove.clone()
+                                                    >
+                                                        "Confirm Remove"
+                                                    </button>
+                                                    <button
+                                                        class="btn btn-sm btn-ghost"
+                                                        on:click=move |_| setConfirmRemove.set(None)
+                                                    >
+                                                        "Cancel"
+                                                    </button>
+                                                </div>
+                                            </div>
+                                        </Show>
+
                                         {if hasDetails {
                                             let ports = ports.clone();
                                             let runtime = runtime.clone();
                                             let restartPolicy = restartPolicy.clone();
                                             let mounts = mounts.clone();
                                             let created = created.clone();
+                                            let env = env.clone();
                                             view! {
                                                 <div
                                                     class="container-details"
@@ -376,7 +739,7 @@ pub fn ContainersPage() -> impl IntoView {
                                                         }
                                                             .into_any()
                                                     } else {
-                                                        view! {}.into_any()
+                                                        ().into_any()
                                                     }}
                                                     {if !restartPolicy.is_empty() {
                                                         view! {
@@ -391,7 +754,7 @@ pub fn ContainersPage() -> impl IntoView {
                                                         }
                                                             .into_any()
                                                     } else {
-                                                        view! {}.into_any()
+                                                        ().into_any()
                                                     }}
                                                     {if !created.is_empty() {
                                                         view! {
@@ -404,7 +767,56 @@ pub fn ContainersPage() -> impl IntoView {
                                                         }
                                                             .into_any()
                                                     } else {
-                                                        view! {}.into_any()
+                                                        ().into_any()
+                                                    }}
+                                                    {(cpuLimit.is_some() || memCap.is_some())
+                                                        .then(|| {
+                                                            let cpus = cpuLimit
+                                                                .map(|n| format!("{n:.2} CPUs"))
+                                                                .unwrap_or_else(|| "no CPU limit".to_string());
+                                                            let memory = memCap
+                                                                .map(format_mem_bytes)
+                                                                .unwrap_or_else(|| "no memory limit".to_string());
+                                                            view! {
+                                                                <div class="detail-row">
+                                                                    <span class="detail-label">"Limits"</span>
+                                                                    <span class="detail-value">
+                                                                        {format!("{cpus} \u{00B7} {memory}")}
+                                                                    </span>
+                                                                </div>
+                                                            }
+                                                        })}
+                                                    {match (rwSize, rootFsSize) {
+                                                        (Some(rw), Some(rootFs)) => {
+                                                            let isLarge = rw >= LARGE_RW_LAYER_BYTES;
+                                                            view! {
+                                                                <div class="detail-row">
+                                                                    <span class="detail-label">
+                                                                        "Writable Layer"
+                                                                    </span>
+                                                                    <span class=if isLarge {
+                                                                        "detail-value detail-warning"
+                                                                    } else {
+                                                                        "detail-value"
+                                                                    }>
+                                                                        {format_mem_bytes(rw)}
+                                                                        {if isLarge {
+                                                                            " \u{26A0} large writable layer \u{2014} consider a volume"
+                                                                        } else {
+                                                                            ""
+                                                                        }}
+                                                                    </span>
+                                                                </div>
+                                                                <div class="detail-row">
+                                                                    <span class="detail-label">"Root FS"</span>
+                                                                    <span class="detail-value">
+                                                                        {format_mem_bytes(rootFs)}
+                                                                    </span>
+                                                                </div>
+                                                            }
+                                                                .into_any()
+                                                        }
+                                                        _ => ().into_any(),
                                                     }}
                                                     {if !ports.is_empty() {
                                                         let portList = ports
@@ -427,7 +839,7 @@ pub fn ContainersPage() -> impl IntoView {
                                                         }
                                                             .into_any()
                                                     } else {
-                                                        view! {}.into_any()
+                                                        ().into_any()
                                                     }}
                                                     {if !mounts.is_empty() {
                                                         let mountList = mounts
@@ -450,13 +862,33 @@ pub fn ContainersPage() -> impl IntoView {
                                                         }
                                                             .into_any()
                                                     } else {
-                                                        view! {}.into_any()
+                                                        ().into_any()
                                                     }}
+                                                    // Secret-looking values arrive already redacted
+                                                    {(!env.is_empty())
+                                                        .then(|| {
+                                                            let envList = env
+                                                                .iter()
+                                                                .map(|entry| {
+                                                                    view! {
+                                                                        <div class="detail-tag">
+                                                                            {entry.clone()}
+                                                                        </div>
+                                                                    }
+                                                                })
+                                                                .collect_view();
+                                                            view! {
+                                                                <div class="detail-row">
+                                                                    <span class="detail-label">"Environment"</span>
+                                                                    <div class="detail-tags">{envList}</div>
+                                                                </div>
+                                                            }
+                                                        })}
                                                 </div>
                                             }
                                                 .into_any()
                                         } else {
-                                            view! {}.into_any()
+                                            ().into_any()
                                         }}
                                     </div>
                                 }
@@ -1,16 +1,116 @@
 use leptos::prelude::*;
-use spark_types::{GpuProcess, SystemMetrics};
+use spark_types::{
+    Alert, AlertKind, Capabilities, ContainerActionResult, DiskMetrics, GpuMetrics, GpuProcess, SystemMetrics,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use crate::components::gauge::Gauge;
+use crate::components::gauge::{gauge_color, Gauge};
 use crate::components::metric_card::MetricCard;
+use crate::components::pager::{page_slice, Pager, DEFAULT_PAGE_SIZE};
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::refresh::RefreshContext;
+use crate::components::sparkline::Sparkline;
+use crate::components::temperature::use_temperature_unit;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::{ToastContext, ToastLevel};
 
+/// Samples kept for the sparklines; at the default 2s refresh this is the last two minutes.
+const HISTORY_SAMPLES: usize = 60;
+
+/// The values the dashboard plots over time, taken from each metrics update.
+#[derive(Clone)]
+struct MetricsSample {
+    gpus: Vec<GpuSample>,
+    cpu_load_1m: f32,
+    memory_pct: f32,
+}
+
+#[derive(Clone, Copy)]
+struct GpuSample {
+    index: u32,
+    utilization_pct: f32,
+    temperature_c: f32,
+    power_draw_w: f32,
+}
+
+#[cfg(feature = "hydrate")]
+impl MetricsSample {
+    fn from_metrics(metrics: &SystemMetrics) -> Self {
+        let memory = &metrics.memory;
+        Self {
+            gpus: metrics
+                .gpus
+                .iter()
+                .map(|g| GpuSample {
+                    index: g.index,
+                    utilization_pct: g.utilization_pct,
+                    temperature_c: g.temperature_c as f32,
+                    power_draw_w: g.power_draw_w,
+                })
+                .collect(),
+            cpu_load_1m: metrics.cpu.load_1m,
+            memory_pct: if memory.total_bytes > 0 {
+                (memory.used_bytes as f64 / memory.total_bytes as f64 * 100.0) as f32
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// One value per recorded sample, for a sparkline. Samples where `pick` has nothing
+/// (a GPU that wasn't reported) are skipped.
+fn history_series(
+    history: ReadSignal<VecDeque<MetricsSample>>,
+    pick: impl Fn(&MetricsSample) -> Option<f32> + Send + Sync + 'static,
+) -> Signal<Vec<f32>> {
+    Signal::derive(move || history.with(|h| h.iter().filter_map(&pick).collect()))
+}
+
+fn gpu_series(
+    history: ReadSignal<VecDeque<MetricsSample>>,
+    index: u32,
+    pick: impl Fn(&GpuSample) -> f32 + Send + Sync + 'static,
+) -> Signal<Vec<f32>> {
+    history_series(history, move |s| s.gpus.iter().find(|g| g.index == index).map(&pick))
+}
+
+#[server]
+pub async fn get_system_metrics() -> Result<SystemMetrics, ServerFnError> {
+    use spark_providers::{collect_system_metrics, ProviderOptions};
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(collect_system_metrics(&options).await)
+}
+
+#[server]
+async fn get_capabilities() -> Result<Capabilities, ServerFnError> {
+    Ok(use_context::<Capabilities>().unwrap_or_default())
+}
+
+/// SIGTERM, then SIGKILL after a grace period, if `gpu.allow_kill` permits it.
 #[server]
-async fn get_system_metrics() -> Result<SystemMetrics, ServerFnError> {
-    use spark_providers::collect_system_metrics;
-    Ok(collect_system_metrics().await)
+async fn kill_gpu_process(pid: u32) -> Result<ContainerActionResult, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::gpu::kill_process(&options.gpu, pid).await)
+}
+
+/// Alerts active as of the server's latest history sample.
+#[server]
+async fn get_alerts() -> Result<Vec<Alert>, ServerFnError> {
+    let Some(alerts) = use_context::<spark_api::alerts::SharedAlerts>() else {
+        return Ok(Vec::new());
+    };
+    Ok(alerts.active().await)
 }
 
-fn format_bytes(bytes: u64) -> String {
+/// Whether an alert of `kind` for `target` (a GPU index or mount point) is active.
+fn alert_active(alerts: ReadSignal<Vec<Alert>>, kind: AlertKind, target: String) -> Signal<bool> {
+    Signal::derive(move || alerts.with(|a| a.iter().any(|a| a.kind == kind && a.target == target)))
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
     const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
     const TIB: f64 = GIB * 1024.0;
     let bytesF64 = bytes as f64;
@@ -29,6 +129,61 @@ fn format_mib(mib: u64) -> String {
     }
 }
 
+/// Network throughput in decimal units, the way link speeds are quoted.
+fn format_rate(bytesPerSec: f64) -> String {
+    if bytesPerSec >= 1_000_000_000.0 {
+        format!("{:.1} GB/s", bytesPerSec / 1_000_000_000.0)
+    } else if bytesPerSec >= 1_000_000.0 {
+        format!("{:.1} MB/s", bytesPerSec / 1_000_000.0)
+    } else if bytesPerSec >= 1_000.0 {
+        format!("{:.1} KB/s", bytesPerSec / 1_000.0)
+    } else {
+        format!("{bytesPerSec:.0} B/s")
+    }
+}
+
+/// `used` as a percentage of `total`, or a dash when the total isn't known.
+fn format_share(usedMib: u64, totalMib: u64) -> String {
+    if totalMib == 0 {
+        return "\u{2014}".to_string();
+    }
+    format!("{:.1}%", usedMib as f64 / totalMib as f64 * 100.0)
+}
+
+fn format_mhz(mhz: Option<u32>) -> String {
+    match mhz {
+        Some(v) => format!("{v} MHz"),
+        None => "N/A".to_string(),
+    }
+}
+
+fn format_pct(pct: Option<f32>) -> String {
+    match pct {
+        Some(v) => format!("{v:.0}%"),
+        None => "N/A".to_string(),
+    }
+}
+
+/// GPU name prefixed with its vendor, unless the name already says it.
+fn gpu_display_name(gpu: &GpuMetrics) -> String {
+    let vendor = match gpu.vendor.as_str() {
+        "nvidia" => "NVIDIA",
+        "amd" => "AMD",
+        _ => return gpu.name.clone(),
+    };
+    if gpu.name.to_uppercase().contains(vendor) {
+        gpu.name.clone()
+    } else {
+        format!("{vendor} {}", gpu.name)
+    }
+}
+
+/// "2024-05-01 08:12 UTC" from the RFC 3339 boot time the server reports.
+fn format_boot_time(rfc3339: &str) -> String {
+    let (date, time) = rfc3339.split_once('T').unwrap_or((rfc3339, ""));
+    format!("{date} {} UTC", time.get(..5).unwrap_or(time))
+}
+
 fn format_uptime(seconds: u64) -> String {
     let days = seconds / 86400;
     let hours = (seconds % 86400) / 3600;
@@ -36,50 +191,254 @@ fn format_uptime(seconds: u64) -> String {
     format!("{days}d {hours}h {minutes}m")
 }
 
-fn gauge_color(value: f32) -> &'static str {
-    if value >= 90.0 {
+pub(crate) fn temp_gauge_color(tempC: u32) -> &'static str {
+    if tempC >= 80 {
         "#ef4444"
-    } else if value >= 70.0 {
+    } else if tempC >= 65 {
         "#f59e0b"
     } else {
         "#76b900"
     }
 }
 
-fn temp_gauge_color(tempC: u32) -> &'static str {
-    if tempC >= 80 {
+/// Color for a load average divided by core count: anything at or above one
+/// runnable task per core is saturated.
+fn load_color(perCore: f32) -> &'static str {
+    if perCore >= 1.0 {
         "#ef4444"
-    } else if tempC >= 65 {
+    } else if perCore >= 0.7 {
+        "#f59e0b"
+    } else {
+        "#76b900"
+    }
+}
+
+/// Color for a PSI "some" percentage: sustained stalls above a few percent are noticeable.
+fn pressure_color(pct: f32) -> &'static str {
+    if pct > 20.0 {
+        "#ef4444"
+    } else if pct > 5.0 {
         "#f59e0b"
     } else {
         "#76b900"
     }
 }
 
+/// Sort order for the GPU process table. `Default` keeps nvidia-smi's order.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ProcessSort {
+    Default,
+    GpuUtil,
+    Memory,
+}
+
+/// An open metrics socket and the JS callbacks it holds, which must live as long as it does.
+#[cfg(feature = "hydrate")]
+struct MetricsStream {
+    socket: web_sys::WebSocket,
+    _callbacks: Vec<wasm_bindgen::closure::Closure<dyn FnMut(wasm_bindgen::JsValue)>>,
+}
+
+#[cfg(feature = "hydrate")]
+const STREAM_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Open `/api/v1/system/stream` and feed its frames into `setMetrics`, dropping them while
+/// `paused`. `live` is true while the socket is open; after a drop it reconnects every few
+/// seconds until the page unmounts.
+#[cfg(feature = "hydrate")]
+fn connect_metrics_stream(
+    setMetrics: WriteSignal<Option<Result<SystemMetrics, String>>>,
+    live: RwSignal<bool>,
+    paused: Signal<bool>,
+    stream: StoredValue<Option<MetricsStream>, LocalStorage>,
+) {
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    let reconnect = move || {
+        set_timeout(
+            move || {
+                // The StoredValue is disposed once the dashboard unmounts
+                if stream.try_update_value(|s| close_metrics_stream(s.take())).is_some() {
+                    connect_metrics_stream(setMetrics, live, paused, stream);
+                }
+            },
+            STREAM_RECONNECT_DELAY,
+        );
+    };
+
+    let location = window().location();
+    let scheme = if location.protocol().as_deref() == Ok("https:") { "wss" } else { "ws" };
+    let socket = location
+        .host()
+        .ok()
+        .and_then(|host| web_sys::WebSocket::new(&format!("{scheme}://{host}/api/v1/system/stream")).ok());
+    let Some(socket) = socket else {
+        reconnect();
+        return;
+    };
+
+    let onOpen = Closure::<dyn FnMut(JsValue)>::new(move |_| live.set(true));
+    let onMessage = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        if paused.get_untracked() {
+            return;
+        }
+        let Some(text) = event
+            .dyn_into::<web_sys::MessageEvent>()
+            .ok()
+            .and_then(|e| e.data().as_string())
+        else {
+            return;
+        };
+        if let Ok(metrics) = serde_json::from_str::<SystemMetrics>(&text) {
+            setMetrics.set(Some(Ok(metrics)));
+        }
+    });
+    let onClose = Closure::<dyn FnMut(JsValue)>::new(move |_| {
+        live.set(false);
+        reconnect();
+    });
+    socket.set_onopen(Some(onOpen.as_ref().unchecked_ref()));
+    socket.set_onmessage(Some(onMessage.as_ref().unchecked_ref()));
+    socket.set_onclose(Some(onClose.as_ref().unchecked_ref()));
+
+    stream.set_value(Some(MetricsStream {
+        socket,
+        _callbacks: vec![onOpen, onMessage, onClose],
+    }));
+}
+
+#[cfg(feature = "hydrate")]
+fn close_metrics_stream(stream: Option<MetricsStream>) {
+    if let Some(stream) = stream {
+        stream.socket.set_onopen(None);
+        stream.socket.set_onmessage(None);
+        stream.socket.set_onclose(None);
+        let _ = stream.socket.close();
+    }
+}
+
 #[component]
 pub fn DashboardPage() -> impl IntoView {
     // Hold latest metrics in a signal — never re-enters loading after first data arrives.
     #[allow(unused_variables)]
     let (metrics, setMetrics) = signal(Option::<Result<SystemMetrics, String>>::None);
+    // Lives here rather than in the table so it survives each poll's re-render
+    let processSort = RwSignal::new(ProcessSort::Default);
+    let processPage = RwSignal::new(0usize);
+    // GPU process kill state, kept here for the same reason
+    #[allow(unused_variables)]
+    let (canKill, setCanKill) = signal(false);
+    let confirmKill = RwSignal::new(Option::<u32>::None);
+    let pendingKill = RwSignal::new(Option::<u32>::None);
+    // Browser time (ms) of the last metrics received, whether the latest poll failed, and
+    // whether the user closed the resulting banner; all reset by the next good sample
+    let lastSuccess = RwSignal::new(Option::<f64>::None);
+    let fetchFailing = RwSignal::new(false);
+    let bannerDismissed = RwSignal::new(false);
+    #[allow(unused_variables)]
+    let (nowMs, setNowMs) = signal(0.0);
+    // Recent samples for the sparklines, filled from the socket or the poll alike
+    #[allow(unused_variables)]
+    let (history, setHistory) = signal(VecDeque::<MetricsSample>::with_capacity(HISTORY_SAMPLES));
+    #[allow(unused_variables)]
+    let (alerts, setAlerts) = signal(Vec::<Alert>::new());
+    let refresh = use_context::<RefreshContext>();
+
+    // Shown once polls have been failing for longer than one refresh interval
+    let staleSecs = move || {
+        let last = lastSuccess.get()?;
+        let ago = ((nowMs.get() - last) / 1000.0).max(0.0) as u64;
+        let interval = refresh.map(|r| r.rate_secs.get()).unwrap_or(2).max(1);
+        (fetchFailing.get() && !bannerDismissed.get() && ago > interval).then_some(ago)
+    };
 
     #[cfg(feature = "hydrate")]
     {
         use wasm_bindgen_futures::spawn_local;
 
+        // Prefer the WebSocket; polling only runs while it's down
+        let streamLive = RwSignal::new(false);
+        let socket = StoredValue::new_local(None);
+        let paused = Signal::derive(move || refresh.is_some_and(|r| r.paused.get()));
+        connect_metrics_stream(setMetrics, streamLive, paused, socket);
+        on_cleanup(move || socket.update_value(|s| close_metrics_stream(s.take())));
+
+        // Any good sample, from the socket or a poll, means the server is reachable
+        Effect::new(move |_| {
+            metrics.with(|m| {
+                if let Some(Ok(m)) = m {
+                    lastSuccess.set(Some(js_sys::Date::now()));
+                    fetchFailing.set(false);
+                    bannerDismissed.set(false);
+                    let sample = MetricsSample::from_metrics(m);
+                    setHistory.update(|h| {
+                        if h.len() == HISTORY_SAMPLES {
+                            h.pop_front();
+                        }
+                        h.push_back(sample);
+                    });
+                }
+            });
+        });
+
+        let clock = set_interval_with_handle(
+            move || setNowMs.set(js_sys::Date::now()),
+            std::time::Duration::from_secs(1),
+        )
+        .expect("failed to set interval");
+        on_cleanup(move || clock.clear());
+
         let fetch = move || {
             spawn_local(async move {
-                let result = get_system_metrics().await.map_err(|e| e.to_string());
-                setMetrics.set(Some(result));
+                match get_system_metrics().await {
+                    Ok(m) => setMetrics.set(Some(Ok(m))),
+                    // Keep showing the last good metrics; the banner says they're stale
+                    Err(_) if matches!(metrics.get_untracked(), Some(Ok(_))) => fetchFailing.set(true),
+                    Err(e) => setMetrics.set(Some(Err(e.to_string()))),
+                }
             });
         };
 
-        // Initial fetch on mount
+        // Initial fetch on mount, so the page fills in before the socket's first frame
         fetch();
 
-        // Poll every 2 seconds — updates the signal in place, no flicker
-        let handle = set_interval_with_handle(fetch, std::time::Duration::from_secs(2))
-            .expect("failed to set interval");
-        on_cleanup(move || handle.clear());
+        // Kill buttons are off by default, so they stay hidden until the server allows them
+        spawn_local(async move {
+            if let Ok(caps) = get_capabilities().await {
+                setCanKill.set(caps.allows_gpu_kill());
+            }
+        });
+
+        // Poll at the chosen refresh rate — updates the signal in place, no flicker
+        use_poll(1, move || {
+            if !streamLive.get_untracked() {
+                fetch();
+            }
+        });
+
+        // Alerts only change when the server samples, every few seconds. Each one is
+        // toasted when it first appears; cleared alerts drop out of `toasted` so they
+        // toast again if they come back.
+        let toasts = use_context::<ToastContext>();
+        let toasted = StoredValue::new(HashSet::<String>::new());
+        let fetchAlerts = move || {
+            spawn_local(async move {
+                let Ok(active) = get_alerts().await else {
+                    return;
+                };
+                toasted.update_value(|seen| {
+                    for alert in active.iter().filter(|a| !seen.contains(&a.key())) {
+                        if let Some(toasts) = toasts {
+                            toasts.push(alert.message.clone(), ToastLevel::Warning);
+                        }
+                    }
+                    *seen = active.iter().map(Alert::key).collect();
+                });
+                setAlerts.set(active);
+            });
+        };
+        fetchAlerts();
+        use_poll(5, fetchAlerts);
     }
 
     view! {
@@ -87,6 +446,23 @@ pub fn DashboardPage() -> impl IntoView {
             <h1>"System Dashboard"</h1>
             <p class="subtitle">"DGX Spark real-time metrics"</p>
         </div>
+        {move || {
+            staleSecs()
+                .map(|ago| {
+                    view! {
+                        <div class="connection-banner" role="status">
+                            <span>{format!("Disconnected \u{2014} showing data from {ago}s ago")}</span>
+                            <button
+                                class="connection-banner-close"
+                                aria-label="Dismiss"
+                                on:click=move |_| bannerDismissed.set(true)
+                            >
+                                "\u{00D7}"
+                            </button>
+                        </div>
+                    }
+                })
+        }}
         {move || {
             match metrics.get() {
                 None => {
@@ -99,7 +475,17 @@ pub fn DashboardPage() -> impl IntoView {
                         .into_any()
                 }
                 Some(Ok(m)) => {
-                    view! { <DashboardContent metrics=m /> }.into_any()
+                    view! { <DashboardContent
+                            metrics=m
+                            process_sort=processSort
+                            process_page=processPage
+                            can_kill=canKill.get()
+                            confirm_kill=confirmKill
+                            pending_kill=pendingKill
+                            history=history
+                            alerts=alerts
+                        /> }
+                        .into_any()
                 }
                 Some(Err(e)) => {
                     view! {
@@ -115,23 +501,43 @@ pub fn DashboardPage() -> impl IntoView {
 }
 
 #[component]
-fn DashboardContent(metrics: SystemMetrics) -> impl IntoView {
-    let gpuUtilization = metrics.gpu.utilization_pct;
-    let gpuTemp = metrics.gpu.temperature_c;
-    let gpuMemUsed = metrics.gpu.memory_used_mib;
-    let gpuMemTotal = metrics.gpu.memory_total_mib;
-    let gpuMemPct = if gpuMemTotal > 0 {
-        (gpuMemUsed as f32 / gpuMemTotal as f32) * 100.0
+fn DashboardContent(
+    metrics: SystemMetrics,
+    process_sort: RwSignal<ProcessSort>,
+    process_page: RwSignal<usize>,
+    can_kill: bool,
+    confirm_kill: RwSignal<Option<u32>>,
+    pending_kill: RwSignal<Option<u32>>,
+    history: ReadSignal<VecDeque<MetricsSample>>,
+    alerts: ReadSignal<Vec<Alert>>,
+) -> impl IntoView {
+    let multiGpu = metrics.gpus.len() > 1;
+    // Flatten processes across devices, remembering which GPU each runs on
+    let gpuProcesses: Vec<(u32, GpuProcess)> = metrics
+        .gpus
+        .iter()
+        .flat_map(|g| g.processes.iter().cloned().map(move |p| (g.index, p)))
+        .collect();
+    let gpuMemoryTotals: HashMap<u32, u64> =
+        metrics.gpus.iter().map(|g| (g.index, g.memory_total_mib)).collect();
+
+    let gpuCards = if metrics.gpus.is_empty() {
+        view! {
+            <MetricCard title="GPU".to_string()>
+                <div class="gauge-container">
+                    <div class="uptime-display">"No GPU detected"</div>
+                </div>
+            </MetricCard>
+        }
+            .into_any()
     } else {
-        0.0
+        metrics
+            .gpus
+            .into_iter()
+            .map(|gpu| view! { <GpuCards gpu=gpu show_index=multiGpu history=history alerts=alerts /> })
+            .collect_view()
+            .into_any()
     };
-    let gpuPower = metrics.gpu.power_draw_w;
-    let gpuName = metrics.gpu.name.clone();
-    let gpuProcesses = metrics.gpu.processes.clone();
-    let gpuUnifiedMemory = metrics.gpu.unified_memory;
-
-    // Temperature: normalize to 0-100 scale where 30°C = 0% and 90°C = 100%
-    let tempNormalized = ((gpuTemp as f32 - 30.0) / 60.0 * 100.0).clamp(0.0, 100.0);
 
     let memUsed = metrics.memory.used_bytes;
     let memTotal = metrics.memory.total_bytes;
@@ -141,31 +547,396 @@ fn DashboardContent(metrics: SystemMetrics) -> impl IntoView {
         0.0
     };
 
-    let diskUsed = metrics.disk.used_bytes;
-    let diskTotal = metrics.disk.total_bytes;
+    // Buffers and cache are reclaimable, so they come out of "available"; whatever is
+    // left over is genuinely free
+    let memCache = metrics.memory.buffers_bytes + metrics.memory.cached_bytes;
+    let memAppUsed = metrics.memory.app_used_bytes;
+    let memFree = metrics.memory.available_bytes.saturating_sub(memCache);
+    let shareOf = move |bytes: u64| {
+        if memTotal > 0 {
+            bytes as f64 / memTotal as f64 * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    // Hosts without swap (common on GPU servers) get no card at all
+    let swapTotal = metrics.memory.swap_total_bytes;
+    let swapUsed = metrics.memory.swap_used_bytes;
+    let swapCard = (swapTotal > 0).then(|| {
+        let swapPct = (swapUsed as f64 / swapTotal as f64 * 100.0) as f32;
+        view! {
+            <MetricCard title="Swap".to_string()>
+                <Gauge
+                    value=swapPct
+                    label=format!("{} / {}", format_bytes(swapUsed), format_bytes(swapTotal))
+                    unit="%".to_string()
+                    color=gauge_color(swapPct).to_string()
+                />
+            </MetricCard>
+        }
+    });
+
+    let disks = if metrics.disks.is_empty() {
+        vec![metrics.disk.clone()]
+    } else {
+        metrics.disks.clone()
+    };
+    let multiDisk = disks.len() > 1;
+    let diskCards = disks
+        .into_iter()
+        .map(|disk| view! { <DiskCard disk=disk show_mount=multiDisk alerts=alerts /> })
+        .collect_view();
+
+    // Hidden when /proc/net/dev couldn't be read or every interface is excluded
+    let netCard = (!metrics.net.is_empty()).then(|| {
+        let rows = metrics
+            .net
+            .iter()
+            .map(|iface| {
+                view! {
+                    <div class="metric-row">
+                        <span class="metric-label">{iface.name.clone()}</span>
+                        <span class="metric-value">
+                            {format!(
+                                "\u{2193} {} \u{00B7} \u{2191} {}",
+                                format_rate(iface.rx_bytes_per_sec),
+                                format_rate(iface.tx_bytes_per_sec),
+                            )}
+                        </span>
+                    </div>
+                }
+            })
+            .collect_view();
+        view! { <MetricCard title="Network".to_string()>{rows}</MetricCard> }
+    });
+
+    let uptimeFormatted = format_uptime(metrics.uptime.seconds);
+    let bootTime = metrics.uptime.boot_time.as_deref().map(format_boot_time);
+    // Idle time is summed over cores, so divide by both to get an average share per core
+    let coreSeconds = metrics.cpu.logical_cores as f64 * metrics.uptime.seconds as f64;
+    let avgIdlePct = (coreSeconds > 0.0 && metrics.uptime.idle_seconds > 0)
+        .then(|| (metrics.uptime.idle_seconds as f64 / coreSeconds * 100.0).min(100.0));
+    let tempUnit = use_temperature_unit();
+
+    // Empty when /proc/cpuinfo couldn't be read
+    let cpu = &metrics.cpu;
+    let mut cpuSummary = String::new();
+    if cpu.logical_cores > 0 {
+        cpuSummary = format!(
+            "{} \u{00B7} {} cores / {} threads",
+            cpu.model, cpu.physical_cores, cpu.logical_cores
+        );
+        if cpu.current_mhz > 0.0 {
+            cpuSummary.push_str(&format!(" \u{00B7} {:.0} MHz", cpu.current_mhz));
+        }
+    }
+
+    view! {
+        <div class="dashboard-grid">
+            {gpuCards}
+
+            <MetricCard
+                title="System Memory".to_string()
+                alert=alert_active(alerts, AlertKind::Memory, String::new())
+            >
+                <Gauge
+                    value=memPct
+                    label=format!("{} / {}", format_bytes(memUsed), format_bytes(memTotal))
+                    unit="%".to_string()
+                    color=gauge_color(memPct).to_string()
+                />
+                <Sparkline values=history_series(history, |s| Some(s.memory_pct)) max=100.0 />
+                <div class="memory-breakdown">
+                    <span
+                        class="memory-segment memory-used"
+                        style=format!("width: {:.1}%", shareOf(memAppUsed))
+                    ></span>
+                    <span
+                        class="memory-segment memory-cached"
+                        style=format!("width: {:.1}%", shareOf(memCache))
+                    ></span>
+                </div>
+                <div class="memory-legend">
+                    <span class="memory-legend-used">{format!("Used {}", format_bytes(memAppUsed))}</span>
+                    <span class="memory-legend-cached">{format!("Cache {}", format_bytes(memCache))}</span>
+                    <span>{format!("Free {}", format_bytes(memFree))}</span>
+                </div>
+                <div
+                    class="metric-row"
+                    title=if metrics.memory.used_includes_cache {
+                        "Total minus MemFree: page cache counts as used"
+                    } else {
+                        "Total minus MemAvailable: reclaimable cache isn't counted"
+                    }
+                >
+                    <span class="metric-label">
+                        {if metrics.memory.used_includes_cache { "Used (incl. cache)" } else { "Used" }}
+                    </span>
+                    <span class="metric-value">{format_bytes(memUsed)}</span>
+                </div>
+                <div class="metric-row" title="MemAvailable: what new allocations can get without swapping">
+                    <span class="metric-label">"Available"</span>
+                    <span class="metric-value">{format_bytes(metrics.memory.available_bytes)}</span>
+                </div>
+                {metrics
+                    .memory
+                    .pressure_some_avg10
+                    .map(|avg10| {
+                        let avg60 = metrics.memory.pressure_some_avg60.unwrap_or(0.0);
+                        view! {
+                            <div class="metric-row">
+                                <span class="metric-label">"Pressure (10s / 60s)"</span>
+                                <span
+                                    class="metric-value"
+                                    style=format!("color: {}", pressure_color(avg10))
+                                >
+                                    {format!("{avg10:.1}% / {avg60:.1}%")}
+                                </span>
+                            </div>
+                        }
+                    })}
+            </MetricCard>
+
+            {swapCard}
+
+            <MetricCard title="CPU Load".to_string()>
+                <div class="gauge-label">{cpuSummary}</div>
+                <Sparkline values=history_series(history, |s| Some(s.cpu_load_1m)) />
+                <div class="metric-row">
+                    <span class="metric-label">"1 min"</span>
+                    <span
+                        class="metric-value"
+                        style=format!("color: {}", load_color(metrics.cpu.load_per_core_1m))
+                    >
+                        {format!("{:.2}", metrics.cpu.load_1m)}
+                    </span>
+                </div>
+                <div class="metric-row">
+                    <span class="metric-label">"5 min"</span>
+                    <span
+                        class="metric-value"
+                        style=format!("color: {}", load_color(metrics.cpu.load_per_core_5m))
+                    >
+                        {format!("{:.2}", metrics.cpu.load_5m)}
+                    </span>
+                </div>
+                <div class="metric-row">
+                    <span class="metric-label">"15 min"</span>
+                    <span
+                        class="metric-value"
+                        style=format!("color: {}", load_color(metrics.cpu.load_per_core_15m))
+                    >
+                        {format!("{:.2}", metrics.cpu.load_15m)}
+                    </span>
+                </div>
+                {metrics
+                    .cpu
+                    .pressure_some_avg10
+                    .map(|avg10| {
+                        view! {
+                            <div class="metric-row">
+                                <span class="metric-label">"Pressure (10s)"</span>
+                                <span
+                                    class="metric-value"
+                                    style=format!("color: {}", pressure_color(avg10))
+                                >
+                                    {format!("{avg10:.1}%")}
+                                </span>
+                            </div>
+                        }
+                    })}
+                {(metrics.cpu.procs_total > 0)
+                    .then(|| {
+                        view! {
+                            <div class="metric-row" title="Runnable and total tasks, counting threads">
+                                <span class="metric-label">"Tasks"</span>
+                                <span class="metric-value">
+                                    {format!(
+                                        "{} running / {} total",
+                                        metrics.cpu.procs_running,
+                                        metrics.cpu.procs_total,
+                                    )}
+                                </span>
+                            </div>
+                        }
+                    })}
+                {avgIdlePct
+                    .map(|pct| {
+                        view! {
+                            <div class="metric-row" title="Average idle time per core since boot">
+                                <span class="metric-label">"Idle since boot"</span>
+                                <span class="metric-value">{format!("{pct:.0}%")}</span>
+                            </div>
+                        }
+                    })}
+                {metrics
+                    .cpu
+                    .temperature_c
+                    .map(|temp| {
+                        view! {
+                            <div class="metric-row">
+                                <span class="metric-label">"Temperature"</span>
+                                <span class="metric-value">
+                                    {move || tempUnit.get().format(temp as f32)}
+                                </span>
+                            </div>
+                        }
+                    })}
+            </MetricCard>
+
+            {diskCards}
+
+            {netCard}
+
+            <MetricCard title="Uptime".to_string()>
+                <div class="gauge-container">
+                    <div class="uptime-display">{uptimeFormatted}</div>
+                    <div class="gauge-label">"System Uptime"</div>
+                    {bootTime
+                        .map(|boot| view! { <div class="gauge-label">{format!("Booted: {boot}")}</div> })}
+                </div>
+            </MetricCard>
+        </div>
+
+        <GpuProcessTable
+            processes=gpuProcesses
+            show_gpu=multiGpu
+            memory_totals=gpuMemoryTotals
+            sort=process_sort
+            page=process_page
+            can_kill=can_kill
+            confirm_kill=confirm_kill
+            pending_kill=pending_kill
+        />
+    }
+}
+
+/// Usage gauge for one mount point.
+#[component]
+fn DiskCard(disk: DiskMetrics, show_mount: bool, alerts: ReadSignal<Vec<Alert>>) -> impl IntoView {
+    let title = if show_mount {
+        format!("Disk {}", disk.mount_point)
+    } else {
+        "Disk Usage".to_string()
+    };
+    let diskUsed = disk.used_bytes;
+    let diskTotal = disk.total_bytes;
     let diskPct = if diskTotal > 0 {
         (diskUsed as f64 / diskTotal as f64 * 100.0) as f32
     } else {
         0.0
     };
+    // Inodes can run out long before bytes do, so this gets its own warning color
+    let inodePct = (disk.inodes_total > 0)
+        .then(|| (disk.inodes_used as f64 / disk.inodes_total as f64 * 100.0) as f32);
+    let alert = alert_active(alerts, AlertKind::Disk, disk.mount_point.clone());
 
-    let uptimeFormatted = format_uptime(metrics.uptime.seconds);
+    view! {
+        <MetricCard title=title alert=alert>
+            <Gauge
+                value=diskPct
+                label=format!(
+                    "{} / {}",
+                    format_bytes(diskUsed),
+                    format_bytes(diskTotal),
+                )
+                unit="%".to_string()
+                color=gauge_color(diskPct).to_string()
+            />
+            {inodePct
+                .map(|pct| {
+                    view! {
+                        <div class="metric-row">
+                            <span class="metric-label">"Inodes"</span>
+                            <span
+                                class="metric-value"
+                                style=(pct >= 90.0).then_some("color: #ef4444")
+                            >
+                                {format!("{pct:.0}%")}
+                            </span>
+                        </div>
+                    }
+                })}
+            {disk
+                .read_bytes_per_sec
+                .zip(disk.write_bytes_per_sec)
+                .map(|(read, write)| {
+                    view! {
+                        <div class="metric-row">
+                            <span class="metric-label">{format!("I/O {}", disk.device)}</span>
+                            <span class="metric-value">
+                                {format!(
+                                    "R {}/s \u{00B7} W {}/s",
+                                    format_bytes(read as u64),
+                                    format_bytes(write as u64),
+                                )}
+                            </span>
+                        </div>
+                    }
+                })}
+        </MetricCard>
+    }
+}
+
+/// Utilization, temperature, memory and power cards for one GPU.
+/// Card titles carry the device index when `show_index` is set.
+#[component]
+fn GpuCards(
+    gpu: GpuMetrics,
+    show_index: bool,
+    history: ReadSignal<VecDeque<MetricsSample>>,
+    alerts: ReadSignal<Vec<Alert>>,
+) -> impl IntoView {
+    let prefix = if show_index {
+        format!("GPU {}", gpu.index)
+    } else {
+        "GPU".to_string()
+    };
+
+    let gpuUtilization = gpu.utilization_pct;
+    let gpuTemp = gpu.temperature_c;
+    let gpuMemUsed = gpu.memory_used_mib;
+    let gpuMemTotal = gpu.memory_total_mib;
+    let gpuMemPct = if gpuMemTotal > 0 {
+        (gpuMemUsed as f32 / gpuMemTotal as f32) * 100.0
+    } else {
+        0.0
+    };
+    let gpuPower = gpu.power_draw_w;
+    let gpuPowerLimit = gpu.power_limit_w;
+    let fanSpeed = gpu.fan_speed_pct;
+    let smClock = gpu.sm_clock_mhz;
+    let memClock = gpu.mem_clock_mhz;
+    let encoderUtil = gpu.encoder_util_pct;
+    let decoderUtil = gpu.decoder_util_pct;
+    let gpuName = gpu_display_name(&gpu);
+    let gpuUnifiedMemory = gpu.unified_memory;
+    let gpuIndex = gpu.index;
+    let tempUnit = use_temperature_unit();
 
-    // GPU Memory card: branch on unified memory
+    // Temperature: normalize to 0-100 scale where 30°C = 0% and 90°C = 100%
+    let tempNormalized = ((gpuTemp as f32 - 30.0) / 60.0 * 100.0).clamp(0.0, 100.0);
+
+    // GPU Memory card: unified memory counts what GPU processes hold against system RAM
     let gpuMemoryCard = if gpuUnifiedMemory {
         view! {
-            <MetricCard title="GPU Memory".to_string()>
-                <div class="gauge-container">
-                    <div class="uptime-display">"Unified Memory"</div>
-                    <div class="gauge-label">{format_mib(gpuMemTotal)} " total"</div>
-                    <div class="gauge-label">"Per-GPU VRAM tracking not available"</div>
+            <MetricCard title=format!("{prefix} Memory")>
+                <Gauge
+                    value=gpuMemPct
+                    label=format!("{} / {}", format_mib(gpuMemUsed), format_mib(gpuMemTotal))
+                    unit="%".to_string()
+                    color=gauge_color(gpuMemPct).to_string()
+                />
+                <div class="gauge-label" title="Memory held by GPU processes, out of system memory">
+                    "Shared with system"
                 </div>
             </MetricCard>
         }
             .into_any()
     } else {
         view! {
-            <MetricCard title="GPU Memory".to_string()>
+            <MetricCard title=format!("{prefix} Memory")>
                 <Gauge
                     value=gpuMemPct
                     label=format!("{} / {} MiB", gpuMemUsed, gpuMemTotal)
@@ -177,87 +948,256 @@ fn DashboardContent(metrics: SystemMetrics) -> impl IntoView {
             .into_any()
     };
 
-    view! {
-        <div class="dashboard-grid">
-            <MetricCard title="GPU Utilization".to_string()>
+    // Power card: a gauge against the enforced limit, or bare wattage when there is none
+    let gpuPowerCard = if gpuPowerLimit > 0.0 {
+        let powerPct = gpuPower / gpuPowerLimit * 100.0;
+        view! {
+            <MetricCard title=format!("{prefix} Power")>
                 <Gauge
-                    value=gpuUtilization
-                    label=gpuName.clone()
+                    value=powerPct
+                    label=format!("{:.0} / {:.0} W", gpuPower, gpuPowerLimit)
                     unit="%".to_string()
-                    color=gauge_color(gpuUtilization).to_string()
+                    color=gauge_color(powerPct).to_string()
                 />
-            </MetricCard>
-
-            <MetricCard title="GPU Temperature".to_string()>
-                <Gauge
-                    value=tempNormalized
-                    label="Temperature".to_string()
-                    unit="\u{00B0}C".to_string()
-                    color=temp_gauge_color(gpuTemp).to_string()
-                    display_value=format!("{gpuTemp}")
+                <Sparkline
+                    values=gpu_series(history, gpuIndex, |g| g.power_draw_w)
+                    max=gpuPowerLimit
                 />
             </MetricCard>
-
-            {gpuMemoryCard}
-
-            <MetricCard title="GPU Power".to_string()>
+        }
+            .into_any()
+    } else {
+        view! {
+            <MetricCard title=format!("{prefix} Power")>
                 <div class="gauge-container">
                     <div class="uptime-display">{format!("{:.0} W", gpuPower)}</div>
                     <div class="gauge-label">"Power Draw"</div>
                 </div>
+                <Sparkline values=gpu_series(history, gpuIndex, |g| g.power_draw_w) />
             </MetricCard>
+        }
+            .into_any()
+    };
 
-            <MetricCard title="System Memory".to_string()>
-                <Gauge
-                    value=memPct
-                    label=format!("{} / {}", format_bytes(memUsed), format_bytes(memTotal))
-                    unit="%".to_string()
-                    color=gauge_color(memPct).to_string()
-                />
-            </MetricCard>
-
-            <MetricCard title="CPU Load".to_string()>
+    // Older drivers report [N/A] for both; there's nothing to show then
+    let videoCard = (encoderUtil.is_some() || decoderUtil.is_some()).then(|| {
+        view! {
+            <MetricCard title=format!("{prefix} Video Engines")>
                 <div class="metric-row">
-                    <span class="metric-label">"1 min"</span>
-                    <span class="metric-value">{format!("{:.2}", metrics.cpu.load_1m)}</span>
+                    <span class="metric-label">"Encoder"</span>
+                    <span class="metric-value">{format_pct(encoderUtil)}</span>
                 </div>
                 <div class="metric-row">
-                    <span class="metric-label">"5 min"</span>
-                    <span class="metric-value">{format!("{:.2}", metrics.cpu.load_5m)}</span>
-                </div>
-                <div class="metric-row">
-                    <span class="metric-label">"15 min"</span>
-                    <span class="metric-value">{format!("{:.2}", metrics.cpu.load_15m)}</span>
+                    <span class="metric-label">"Decoder"</span>
+                    <span class="metric-value">{format_pct(decoderUtil)}</span>
                 </div>
             </MetricCard>
+        }
+    });
 
-            <MetricCard title="Disk Usage".to_string()>
-                <Gauge
-                    value=diskPct
-                    label=format!(
-                        "{} / {}",
-                        format_bytes(diskUsed),
-                        format_bytes(diskTotal),
-                    )
-                    unit="%".to_string()
-                    color=gauge_color(diskPct).to_string()
-                />
-            </MetricCard>
+    view! {
+        <MetricCard title=format!("{prefix} Utilization")>
+            <Gauge
+                value=gpuUtilization
+                label=gpuName.clone()
+                unit="%".to_string()
+                color=gauge_color(gpuUtilization).to_string()
+            />
+            <Sparkline values=gpu_series(history, gpuIndex, |g| g.utilization_pct) max=100.0 />
+        </MetricCard>
 
-            <MetricCard title="Uptime".to_string()>
-                <div class="gauge-container">
-                    <div class="uptime-display">{uptimeFormatted}</div>
-                    <div class="gauge-label">"System Uptime"</div>
-                </div>
-            </MetricCard>
-        </div>
+        <MetricCard
+            title=format!("{prefix} Temperature")
+            alert=alert_active(alerts, AlertKind::GpuTemperature, gpuIndex.to_string())
+        >
+            {move || {
+                let unit = tempUnit.get();
+                view! {
+                    <Gauge
+                        value=tempNormalized
+                        label="Temperature".to_string()
+                        unit=unit.symbol().to_string()
+                        color=temp_gauge_color(gpuTemp).to_string()
+                        display_value=unit.format_value(gpuTemp as f32)
+                    />
+                }
+            }}
+            <Sparkline values=gpu_series(history, gpuIndex, |g| g.temperature_c) max=100.0 />
+        </MetricCard>
+
+        {gpuMemoryCard}
+
+        {gpuPowerCard}
+
+        <MetricCard title=format!("{prefix} Clocks")>
+            <div class="metric-row">
+                <span class="metric-label">"SM"</span>
+                <span class="metric-value">{format_mhz(smClock)}</span>
+            </div>
+            <div class="metric-row">
+                <span class="metric-label">"Memory"</span>
+                <span class="metric-value">{format_mhz(memClock)}</span>
+            </div>
+            {fanSpeed
+                .map(|pct| {
+                    view! {
+                        <div class="metric-row">
+                            <span class="metric-label">"Fan"</span>
+                            <span class="metric-value">{format!("{pct}%")}</span>
+                        </div>
+                    }
+                })}
+        </MetricCard>
 
-        <GpuProcessTable processes=gpuProcesses />
+        {videoCard}
     }
 }
 
 #[component]
-fn GpuProcessTable(processes: Vec<GpuProcess>) -> impl IntoView {
+fn GpuProcessTable(
+    processes: Vec<(u32, GpuProcess)>,
+    show_gpu: bool,
+    /// Memory size of each GPU by index, for the share-of-memory column.
+    memory_totals: HashMap<u32, u64>,
+    sort: RwSignal<ProcessSort>,
+    page: RwSignal<usize>,
+    /// Show a kill button on each process (`gpu.allow_kill`).
+    can_kill: bool,
+    /// PID whose kill button was clicked once and now asks for confirmation.
+    confirm_kill: RwSignal<Option<u32>>,
+    /// PID being killed; every kill button is disabled until it finishes.
+    pending_kill: RwSignal<Option<u32>>,
+) -> impl IntoView {
+    let columns = (6 + usize::from(show_gpu) + usize::from(can_kill)).to_string();
+    let total = processes.len();
+    let shareOf = {
+        let memory_totals = memory_totals.clone();
+        move |gpuIndex: u32, mib: u64| {
+            format_share(mib, memory_totals.get(&gpuIndex).copied().unwrap_or(0))
+        }
+    };
+
+    // Totals over every listed process, not just the current page
+    let footer = (!processes.is_empty()).then(|| {
+        let usedMib: u64 = processes.iter().map(|(_, p)| p.memory_mib).sum();
+        let gpus: HashSet<u32> = processes.iter().map(|(index, _)| *index).collect();
+        let totalMib: u64 = gpus.iter().filter_map(|index| memory_totals.get(index)).sum();
+        view! {
+            <tfoot>
+                <tr>
+                    <td colspan=if show_gpu { "5" } else { "4" }>
+                        {format!("Total ({total} process{})", if total == 1 { "" } else { "es" })}
+                    </td>
+                    <td>{format!("{usedMib} MiB")}</td>
+                    <td>{format_share(usedMib, totalMib)}</td>
+                    {can_kill.then(|| view! { <td></td> })}
+                </tr>
+            </tfoot>
+        }
+    });
+
+    // Clicking a sortable header sorts by it (descending); clicking again restores the default
+    let toggleSort = move |key: ProcessSort| {
+        sort.update(|s| *s = if *s == key { ProcessSort::Default } else { key });
+        page.set(0);
+    };
+    let indicator = move |key: ProcessSort| if sort.get() == key { " \u{25BE}" } else { "" };
+
+    // The first click asks for confirmation, the second sends the signal
+    #[cfg(feature = "hydrate")]
+    let toasts = use_context::<ToastContext>();
+    let onKill = move |pid: u32| {
+        if confirm_kill.get_untracked() != Some(pid) {
+            confirm_kill.set(Some(pid));
+            return;
+        }
+        confirm_kill.set(None);
+        pending_kill.set(Some(pid));
+        #[cfg(feature = "hydrate")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = kill_gpu_process(pid).await;
+            if let Some(toasts) = toasts {
+                match result {
+                    Ok(res) if res.success => toasts.push(res.message, ToastLevel::Success),
+                    Ok(res) => toasts.push(res.message, ToastLevel::Error),
+                    Err(e) => toasts.push(e.to_string(), ToastLevel::Error),
+                }
+            }
+            pending_kill.set(None);
+        });
+    };
+    let killLabel = move |pid: u32| {
+        if pending_kill.get() == Some(pid) {
+            "Killing..."
+        } else if confirm_kill.get() == Some(pid) {
+            "Confirm Kill"
+        } else {
+            "Kill"
+        }
+    };
+
+    let rows = move || {
+        if processes.is_empty() {
+            return view! {
+                <tr>
+                    <td colspan=columns.clone()>"No GPU processes running"</td>
+                </tr>
+            }
+                .into_any();
+        }
+
+        let mut sorted = processes.clone();
+        match sort.get() {
+            ProcessSort::Default => {}
+            ProcessSort::GpuUtil => sorted.sort_by(|a, b| {
+                b.1.sm_util_pct
+                    .unwrap_or(-1.0)
+                    .total_cmp(&a.1.sm_util_pct.unwrap_or(-1.0))
+            }),
+            ProcessSort::Memory => sorted.sort_by_key(|p| std::cmp::Reverse(p.1.memory_mib)),
+        }
+
+        page_slice(&sorted, page.get(), DEFAULT_PAGE_SIZE)
+            .into_iter()
+            .map(|(gpuIndex, process)| {
+                view! {
+                    <tr>
+                        {show_gpu.then(|| view! { <td>{gpuIndex}</td> })}
+                        <td>{process.pid}</td>
+                        <td>{process.user.clone()}</td>
+                        <td class="process-command" title=process.cmdline.clone()>
+                            {process.cmdline.clone()}
+                        </td>
+                        <td>
+                            {process
+                                .sm_util_pct
+                                .map(|pct| format!("{pct:.0}%"))
+                                .unwrap_or_else(|| "\u{2014}".to_string())}
+                        </td>
+                        <td>{format!("{} MiB", process.memory_mib)}</td>
+                        <td>{shareOf(gpuIndex, process.memory_mib)}</td>
+                        {can_kill
+                            .then(|| {
+                                let pid = process.pid;
+                                view! {
+                                    <td>
+                                        <button
+                                            class="btn btn-sm btn-danger"
+                                            disabled=move || pending_kill.get().is_some()
+                                            on:click=move |_| onKill(pid)
+                                        >
+                                            {move || killLabel(pid)}
+                                        </button>
+                                    </td>
+                                }
+                            })}
+                    </tr>
+                }
+            })
+            .collect_view()
+            .into_any()
+    };
+
     view! {
         <div class="process-section">
             <div class="card">
@@ -265,36 +1205,26 @@ fn GpuProcessTable(processes: Vec<GpuProcess>) -> impl IntoView {
                 <table>
                     <thead>
                         <tr>
+                            {show_gpu.then(|| view! { <th>"GPU"</th> })}
                             <th>"PID"</th>
-                            <th>"Process"</th>
-                            <th>"GPU Memory"</th>
+                            <th>"User"</th>
+                            <th>"Command"</th>
+                            <th class="sortable" on:click=move |_| toggleSort(ProcessSort::GpuUtil)>
+                                "GPU %"
+                                {move || indicator(ProcessSort::GpuUtil)}
+                            </th>
+                            <th class="sortable" on:click=move |_| toggleSort(ProcessSort::Memory)>
+                                "GPU Memory"
+                                {move || indicator(ProcessSort::Memory)}
+                            </th>
+                            <th>"% of Memory"</th>
+                            {can_kill.then(|| view! { <th></th> })}
                         </tr>
                     </thead>
-                    <tbody>
-                        {if processes.is_empty() {
-                            view! {
-                                <tr>
-                                    <td colspan="3">"No GPU processes running"</td>
-                                </tr>
-                            }
-                                .into_any()
-                        } else {
-                            processes
-                                .into_iter()
-                                .map(|process| {
-                                    view! {
-                                        <tr>
-                                            <td>{process.pid}</td>
-                                            <td>{process.name.clone()}</td>
-                                            <td>{format!("{} MiB", process.memory_mib)}</td>
-                                        </tr>
-                                    }
-                                })
-                                .collect_view()
-                                .into_any()
-                        }}
-                    </tbody>
+                    <tbody>{rows}</tbody>
+                    {footer}
                 </table>
+                <Pager page=page total=total />
             </div>
         </div>
     }
@@ -0,0 +1,166 @@
+use leptos::prelude::*;
+use leptos_router::hooks::{use_params_map, use_query_map};
+use spark_types::SystemMetrics;
+
+use crate::components::gauge::{gauge_color, Gauge};
+use crate::components::temperature::{use_temperature_unit, TemperatureUnit};
+use crate::pages::dashboard::{format_bytes, temp_gauge_color};
+
+const DEFAULT_REFRESH_SECS: u64 = 2;
+const MIN_REFRESH_SECS: u64 = 1;
+
+/// Build the single gauge for `metric`, or `None` if the metric name is unknown.
+/// GPU metrics read the device with index `gpuIndex`; temperatures are shown in `tempUnit`.
+fn metric_gauge(
+    metric: &str,
+    m: &SystemMetrics,
+    gpuIndex: u32,
+    tempUnit: TemperatureUnit,
+) -> Option<AnyView> {
+    let gpu = m
+        .gpus
+        .iter()
+        .find(|g| g.index == gpuIndex)
+        .cloned()
+        .unwrap_or_default();
+    let view = match metric {
+        "gpu_util" => view! {
+            <Gauge
+                value=gpu.utilization_pct
+                label=format!("GPU {} Utilization", gpu.index)
+                unit="%".to_string()
+                color=gauge_color(gpu.utilization_pct).to_string()
+            />
+        }
+        .into_any(),
+        "gpu_temp" => {
+            let tempC = gpu.temperature_c;
+            let tempNormalized = ((tempC as f32 - 30.0) / 60.0 * 100.0).clamp(0.0, 100.0);
+            view! {
+                <Gauge
+                    value=tempNormalized
+                    label=format!("GPU {} Temperature", gpu.index)
+                    unit=tempUnit.symbol().to_string()
+                    color=temp_gauge_color(tempC).to_string()
+                    display_value=tempUnit.format_value(tempC as f32)
+                />
+            }
+            .into_any()
+        }
+        "memory" => {
+            let pct = if m.memory.total_bytes > 0 {
+                (m.memory.used_bytes as f64 / m.memory.total_bytes as f64 * 100.0) as f32
+            } else {
+                0.0
+            };
+            view! {
+                <Gauge
+                    value=pct
+                    label=format!(
+                        "Memory {} / {}",
+                        format_bytes(m.memory.used_bytes),
+                        format_bytes(m.memory.total_bytes),
+                    )
+                    unit="%".to_string()
+                    color=gauge_color(pct).to_string()
+                />
+            }
+            .into_any()
+        }
+        "disk" => {
+            let pct = if m.disk.total_bytes > 0 {
+                (m.disk.used_bytes as f64 / m.disk.total_bytes as f64 * 100.0) as f32
+            } else {
+                0.0
+            };
+            view! {
+                <Gauge
+                    value=pct
+                    label=format!(
+                        "Disk {} / {}",
+                        format_bytes(m.disk.used_bytes),
+                        format_bytes(m.disk.total_bytes),
+                    )
+                    unit="%".to_string()
+                    color=gauge_color(pct).to_string()
+                />
+            }
+            .into_any()
+        }
+        _ => return None,
+    };
+    Some(view)
+}
+
+/// Chrome-less single-metric view for wall displays and iframes.
+///
+/// `/embed/:metric?refresh=<seconds>&gpu=<index>` where metric is one of
+/// `gpu_util`, `gpu_temp`, `memory`, `disk`.
+#[component]
+pub fn EmbedPage() -> impl IntoView {
+    let params = use_params_map();
+    let query = use_query_map();
+
+    let metric = move || params.with(|p| p.get("metric").unwrap_or_default());
+    #[allow(unused_variables)]
+    let refreshSecs = query.with_untracked(|q| {
+        q.get("refresh")
+            .and_then(|r| r.parse::<u64>().ok())
+            .unwrap_or(DEFAULT_REFRESH_SECS)
+            .max(MIN_REFRESH_SECS)
+    });
+
+    let gpuIndex = query.with_untracked(|q| {
+        q.get("gpu").and_then(|g| g.parse::<u32>().ok()).unwrap_or(0)
+    });
+
+    #[allow(unused_variables)]
+    let (metrics, setMetrics) = signal(Option::<Result<SystemMetrics, String>>::None);
+    let tempUnit = use_temperature_unit();
+
+    #[cfg(feature = "hydrate")]
+    {
+        use crate::pages::dashboard::get_system_metrics;
+        use wasm_bindgen_futures::spawn_local;
+
+        let fetch = move || {
+            spawn_local(async move {
+                let result = get_system_metrics().await.map_err(|e| e.to_string());
+                setMetrics.set(Some(result));
+            });
+        };
+
+        fetch();
+
+        let handle =
+            set_interval_with_handle(fetch, std::time::Duration::from_secs(refreshSecs))
+                .expect("failed to set interval");
+        on_cleanup(move || handle.clear());
+    }
+
+    view! {
+        <div class="embed-layout">
+            {move || {
+                match metrics.get() {
+                    None => view! { <div class="spinner"></div> }.into_any(),
+                    Some(Ok(m)) => {
+                        let name = metric();
+                        metric_gauge(&name, &m, gpuIndex, tempUnit.get())
+                            .unwrap_or_else(|| {
+                                view! {
+                                    <p class="embed-error">
+                                        "Unknown metric: " {name}
+                                    </p>
+                                }
+                                    .into_any()
+                            })
+                    }
+                    Some(Err(e)) => {
+                        view! { <p class="embed-error">"Failed to load metrics: " {e}</p> }
+                            .into_any()
+                    }
+                }
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,48 @@
+use leptos::form::ActionForm;
+use leptos::prelude::*;
+
+use crate::components::branding::site_name;
+
+/// Check the token against the configured one and set the session cookie.
+#[server(endpoint = "login")]
+pub async fn login(token: String) -> Result<(), ServerFnError> {
+    use http::{header::SET_COOKIE, HeaderValue};
+    use leptos_axum::ResponseOptions;
+    use spark_api::middleware::auth::{CookieOptions, SharedAuthToken};
+
+    // No token configured means auth is off; there's nothing to log in to
+    if let Some(expected) = use_context::<SharedAuthToken>().and_then(|shared| shared.get()) {
+        let cookies = use_context::<CookieOptions>().unwrap_or_default();
+        let cookie = cookies
+            .session_cookie(&expected, &token)
+            .and_then(|cookie| HeaderValue::from_str(&cookie).ok())
+            .ok_or_else(|| ServerFnError::new("Invalid token"))?;
+        expect_context::<ResponseOptions>().insert_header(SET_COOKIE, cookie);
+    }
+    leptos_axum::redirect("/");
+    Ok(())
+}
+
+#[component]
+pub fn LoginPage() -> impl IntoView {
+    let loginAction = ServerAction::<Login>::new();
+    let error = move || match loginAction.value().get() {
+        Some(Err(e)) => Some(e.to_string().replace("error running server function: ", "")),
+        _ => None,
+    };
+
+    view! {
+        <div class="login-layout">
+            <ActionForm action=loginAction attr:class="login-card">
+                <h1>{site_name}</h1>
+                <label for="login-token">"Access token"</label>
+                <input id="login-token" type="password" name="token" autocomplete="current-password" required />
+                {move || error().map(|e| view! { <p class="login-error">{e}</p> })}
+                <button type="submit" class="btn btn-primary" disabled=move || loginAction.pending().get()>
+                    "Log in"
+                </button>
+            </ActionForm>
+            <p class="login-footer">{concat!("Spark Console v", env!("CARGO_PKG_VERSION"))}</p>
+        </div>
+    }
+}
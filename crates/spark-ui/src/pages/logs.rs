@@ -0,0 +1,166 @@
+use leptos::html;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+/// Lines fetched when the page opens or follow is switched off.
+#[cfg(feature = "hydrate")]
+const LOG_TAIL: usize = 200;
+/// Oldest lines are dropped past this so a chatty container can't grow the page forever.
+#[cfg(feature = "hydrate")]
+const MAX_LOG_LINES: usize = 5000;
+
+#[server]
+async fn get_container_logs(container_id: String, tail: usize) -> Result<String, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::logs::tail(&options.docker, &container_id, tail)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[cfg(feature = "hydrate")]
+struct LogStream {
+    source: web_sys::EventSource,
+    _callbacks: Vec<wasm_bindgen::closure::Closure<dyn FnMut(wasm_bindgen::JsValue)>>,
+}
+
+/// Open the container's `logs/stream` SSE endpoint. The first events replace `lines` with
+/// the last [`LOG_TAIL`] lines; later ones are appended. `follow` is cleared when the
+/// container exits or the connection drops, since `EventSource` would otherwise reconnect
+/// and replay the tail.
+#[cfg(feature = "hydrate")]
+fn open_log_stream(
+    containerId: &str,
+    setLines: WriteSignal<Vec<String>>,
+    follow: RwSignal<bool>,
+    setLogError: WriteSignal<Option<String>>,
+) -> Option<LogStream> {
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    let source = web_sys::EventSource::new(&format!(
+        "/api/v1/containers/{containerId}/logs/stream?tail={LOG_TAIL}"
+    ))
+    .ok()?;
+
+    let onOpen = Closure::<dyn FnMut(JsValue)>::new(move |_| setLines.set(Vec::new()));
+    let onLog = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let Some(line) = event
+            .dyn_into::<web_sys::MessageEvent>()
+            .ok()
+            .and_then(|e| e.data().as_string())
+        else {
+            return;
+        };
+        setLines.update(|lines| {
+            lines.push(line);
+            if lines.len() > MAX_LOG_LINES {
+                lines.drain(..lines.len() - MAX_LOG_LINES);
+            }
+        });
+    });
+    let onEnd = Closure::<dyn FnMut(JsValue)>::new(move |_| follow.set(false));
+    let onError = Closure::<dyn FnMut(JsValue)>::new(move |_| {
+        setLogError.set(Some("Log stream disconnected".to_string()));
+        follow.set(false);
+    });
+    source.set_onopen(Some(onOpen.as_ref().unchecked_ref()));
+    source.set_onerror(Some(onError.as_ref().unchecked_ref()));
+    let _ = source.add_event_listener_with_callback("log", onLog.as_ref().unchecked_ref());
+    let _ = source.add_event_listener_with_callback("end", onEnd.as_ref().unchecked_ref());
+
+    Some(LogStream {
+        source,
+        _callbacks: vec![onOpen, onLog, onEnd, onError],
+    })
+}
+
+#[cfg(feature = "hydrate")]
+fn close_log_stream(stream: Option<LogStream>) {
+    if let Some(stream) = stream {
+        stream.source.set_onopen(None);
+        stream.source.set_onerror(None);
+        stream.source.close();
+    }
+}
+
+#[component]
+pub fn ContainerLogsPage() -> impl IntoView {
+    let params = use_params_map();
+    let containerId = move || params.read().get("id").unwrap_or_default();
+
+    #[allow(unused_variables)]
+    let (lines, setLines) = signal(Vec::<String>::new());
+    #[allow(unused_variables)]
+    let (logError, setLogError) = signal(Option::<String>::None);
+    let follow = RwSignal::new(true);
+    let logRef = NodeRef::<html::Pre>::new();
+
+    #[cfg(feature = "hydrate")]
+    {
+        use wasm_bindgen_futures::spawn_local;
+
+        let stream = StoredValue::new_local(None);
+        on_cleanup(move || stream.update_value(|s| close_log_stream(s.take())));
+
+        Effect::new(move |_| {
+            let id = containerId();
+            stream.update_value(|s| close_log_stream(s.take()));
+            if follow.get() {
+                setLogError.set(None);
+                stream.set_value(open_log_stream(&id, setLines, follow, setLogError));
+            } else {
+                spawn_local(async move {
+                    match get_container_logs(id, LOG_TAIL).await {
+                        Ok(text) => setLines.set(text.lines().map(str::to_string).collect()),
+                        Err(e) => setLogError.set(Some(e.to_string())),
+                    }
+                });
+            }
+        });
+
+        // Keep the newest line in view while following
+        Effect::new(move |_| {
+            lines.track();
+            if let Some(el) = logRef.get() {
+                if follow.get_untracked() {
+                    el.set_scroll_top(el.scroll_height());
+                }
+            }
+        });
+    }
+
+    view! {
+        <div class="dashboard-header">
+            <h1>"Logs"</h1>
+            <p class="subtitle">
+                <a href="/containers">"Containers"</a>
+                " / "
+                {containerId}
+            </p>
+        </div>
+        <div class="log-toolbar">
+            <label class="log-follow">
+                <input
+                    type="checkbox"
+                    prop:checked=move || follow.get()
+                    on:change=move |ev| follow.set(event_target_checked(&ev))
+                />
+                "Follow"
+            </label>
+        </div>
+        {move || {
+            logError
+                .get()
+                .map(|msg| {
+                    view! {
+                        <div class="container-action-error">
+                            <p>{msg}</p>
+                        </div>
+                    }
+                })
+        }}
+        <pre class="log-viewer card" node_ref=logRef>
+            {move || lines.get().join("\n")}
+        </pre>
+    }
+}
@@ -1,3 +1,9 @@
 pub mod containers;
 pub mod dashboard;
+pub mod embed;
+pub mod login;
+pub mod logs;
 pub mod models;
+pub mod services;
+pub mod storage;
+pub mod updates;
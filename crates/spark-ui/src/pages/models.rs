@@ -1,50 +1,231 @@
 use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
 use spark_types::ModelEntry;
 
+use crate::components::pager::{page_slice, Pager, DEFAULT_PAGE_SIZE};
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::toast::ToastContext;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::ToastLevel;
+
+/// A model scan and its fingerprint. `models` is `None` when the fingerprint matches the
+/// one the page already shows, so an unchanged list isn't sent or re-rendered.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ModelListing {
+    pub fingerprint: String,
+    pub models: Option<Vec<ModelEntry>>,
+}
+
+#[server]
+async fn get_models(known: Option<String>) -> Result<ModelListing, ServerFnError> {
+    use spark_providers::{models, ProviderOptions};
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    let entries = models::collect(&options.models).await;
+    let fingerprint = models::fingerprint(&entries);
+    let unchanged = known.as_deref() == Some(fingerprint.as_str());
+    Ok(ModelListing {
+        fingerprint,
+        models: (!unchanged).then_some(entries),
+    })
+}
+
+#[server]
+async fn get_model_dirs() -> Result<Vec<String>, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(options.models.dirs)
+}
+
 #[server]
-async fn get_models() -> Result<Vec<ModelEntry>, ServerFnError> {
-    let models = spark_providers::models::collect().await;
-    Ok(models)
+async fn delete_model(path: String) -> Result<(), ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::models::delete(&options.models, &path)
+        .await
+        .map_err(ServerFnError::new)
 }
 
 fn format_size(bytes: u64) -> String {
+    const TIB: f64 = 1024.0 * 1024.0 * 1024.0 * 1024.0;
     const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
     const MIB: f64 = 1024.0 * 1024.0;
     let b = bytes as f64;
-    if b >= GIB {
+    if b >= TIB {
+        format!("{:.1} TiB", b / TIB)
+    } else if b >= GIB {
         format!("{:.1} GiB", b / GIB)
     } else {
         format!("{:.1} MiB", b / MIB)
     }
 }
 
-const SCANNED_DIRS: &[&str] = &[
-    "/opt/models",
-    "/home/auxidus-spark/.cache/huggingface/hub",
-    "/home/auxidus-spark/.ollama/models",
-];
+/// Parameter count in the units model names use, e.g. "7.2B" or "350M".
+fn format_params(count: u64) -> String {
+    let n = count as f64;
+    if n >= 1e9 {
+        format!("{:.1}B", n / 1e9)
+    } else if n >= 1e6 {
+        format!("{:.0}M", n / 1e6)
+    } else {
+        format!("{:.0}K", n / 1e3)
+    }
+}
+
+/// Current time in Unix seconds, from the browser's clock once hydrated.
+fn now_secs() -> u64 {
+    #[cfg(feature = "hydrate")]
+    {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+    #[cfg(not(feature = "hydrate"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    }
+}
+
+/// "3 days ago" style age of a Unix timestamp.
+fn format_relative(timestamp: u64, now: u64) -> String {
+    let ago = now.saturating_sub(timestamp);
+    let (count, unit) = match ago {
+        0..=59 => return "just now".to_string(),
+        60..=3_599 => (ago / 60, "minute"),
+        3_600..=86_399 => (ago / 3_600, "hour"),
+        86_400..=2_591_999 => (ago / 86_400, "day"),
+        2_592_000..=31_535_999 => (ago / 2_592_000, "month"),
+        _ => (ago / 31_536_000, "year"),
+    };
+    format!("{count} {unit}{} ago", if count == 1 { "" } else { "s" })
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ModelSort {
+    Name,
+    Format,
+    Size,
+    Modified,
+}
+
+/// The column the models table is sorted by, and in which direction.
+#[derive(Clone, Copy, PartialEq)]
+struct ModelOrder {
+    key: ModelSort,
+    descending: bool,
+}
+
+impl ModelOrder {
+    /// Clicking the current column flips the direction. A new column starts with the
+    /// biggest or newest first for size and modified, and A to Z otherwise.
+    fn toggled(self, key: ModelSort) -> Self {
+        if self.key == key {
+            Self {
+                key,
+                descending: !self.descending,
+            }
+        } else {
+            Self {
+                key,
+                descending: matches!(key, ModelSort::Size | ModelSort::Modified),
+            }
+        }
+    }
+
+    /// Ties fall back to name then path, always ascending, so equal keys keep a fixed order.
+    fn sort(self, list: &mut [ModelEntry]) {
+        list.sort_by(|a, b| {
+            let ordering = match self.key {
+                ModelSort::Name => a.name.cmp(&b.name),
+                ModelSort::Format => a.format.cmp(&b.format),
+                ModelSort::Size => a.size_bytes.cmp(&b.size_bytes),
+                ModelSort::Modified => a.modified.cmp(&b.modified),
+            };
+            let ordering = if self.descending { ordering.reverse() } else { ordering };
+            ordering
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.path.cmp(&b.path))
+        });
+    }
+}
 
 #[component]
 pub fn ModelsPage() -> impl IntoView {
     #[allow(unused_variables)]
     let (models, setModels) = signal(Option::<Result<Vec<ModelEntry>, String>>::None);
+    // Starts on the server's name order
+    let sort = RwSignal::new(ModelOrder {
+        key: ModelSort::Name,
+        descending: false,
+    });
+    let indicator = move |key: ModelSort| {
+        let order = sort.get();
+        match (order.key == key, order.descending) {
+            (false, _) => "",
+            (true, false) => " \u{25B4}",
+            (true, true) => " \u{25BE}",
+        }
+    };
+    let page = RwSignal::new(0usize);
+    let header = move |key: ModelSort, label: &'static str| {
+        view! {
+            <th
+                class="sortable"
+                on:click=move |_| {
+                    sort.update(|s| *s = s.toggled(key));
+                    page.set(0);
+                }
+            >
+                {label}
+                {move || indicator(key)}
+            </th>
+        }
+    };
+    #[allow(unused_variables)]
+    let (scannedDirs, setScannedDirs) = signal(Vec::<String>::new());
+    // Path whose delete button has been clicked once and now asks for confirmation
+    #[allow(unused_variables)]
+    let (confirmDelete, setConfirmDelete) = signal(Option::<String>::None);
+    #[allow(unused_variables)]
+    let (busy, setBusy) = signal(false);
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
 
-    #[cfg(feature = "hydrate")]
-    {
-        use wasm_bindgen_futures::spawn_local;
+    // Fingerprint of the list on screen, cleared on errors so the next success redraws
+    #[allow(unused_variables)]
+    let shown = StoredValue::new(Option::<String>::None);
 
-        let fetch = move || {
-            spawn_local(async move {
-                let result = get_models().await.map_err(|e| e.to_string());
-                setModels.set(Some(result));
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            wasm_bindgen_futures::spawn_local(async move {
+                match get_models(shown.get_value()).await {
+                    Ok(ModelListing { fingerprint, models: Some(list) }) => {
+                        shown.set_value(Some(fingerprint));
+                        setModels.set(Some(Ok(list)));
+                    }
+                    Ok(ModelListing { models: None, .. }) => {}
+                    Err(e) => {
+                        shown.set_value(None);
+                        setModels.set(Some(Err(e.to_string())));
+                    }
+                }
             });
-        };
+        }
+    };
 
+    #[cfg(feature = "hydrate")]
+    {
         fetch();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(dirs) = get_model_dirs().await {
+                setScannedDirs.set(dirs);
+            }
+        });
 
-        let handle = set_interval_with_handle(fetch, std::time::Duration::from_secs(30))
-            .expect("failed to set interval");
-        on_cleanup(move || handle.clear());
+        use_poll(30, fetch);
     }
 
     view! {
@@ -80,56 +261,142 @@ pub fn ModelsPage() -> impl IntoView {
                                     "No model files were found in the scanned directories:"
                                 </p>
                                 <div style="display: flex; flex-direction: column; gap: 0.25rem;">
-                                    {SCANNED_DIRS
-                                        .iter()
-                                        .map(|dir| {
-                                            view! {
-                                                <code style="font-size: 0.8125rem; color: var(--text-secondary);">
-                                                    {*dir}
-                                                </code>
-                                            }
-                                        })
-                                        .collect_view()}
+                                    {move || {
+                                        scannedDirs
+                                            .get()
+                                            .into_iter()
+                                            .map(|dir| {
+                                                view! {
+                                                    <code style="font-size: 0.8125rem; color: var(--text-secondary);">
+                                                        {dir}
+                                                    </code>
+                                                }
+                                            })
+                                            .collect_view()
+                                    }}
                                 </div>
                             </div>
                         }
                             .into_any()
                     } else {
                         let count = list.len();
+                        let totalBytes: u64 = list.iter().map(|m| m.size_bytes).sum();
+                        let mut list = list;
+                        sort.get().sort(&mut list);
+                        let list = page_slice(&list, page.get(), DEFAULT_PAGE_SIZE);
+                        let now = now_secs();
                         view! {
                             <div class="card">
                                 <div class="card-title">
-                                    {format!("{count} Model{}", if count == 1 { "" } else { "s" })}
+                                    {format!(
+                                        "{count} model{}, {} total",
+                                        if count == 1 { "" } else { "s" },
+                                        format_size(totalBytes),
+                                    )}
                                 </div>
                                 <table>
                                     <thead>
                                         <tr>
-                                            <th>"Name"</th>
-                                            <th>"Format"</th>
-                                            <th>"Size"</th>
+                                            {header(ModelSort::Name, "Name")}
+                                            {header(ModelSort::Format, "Format")}
+                                            <th>"Architecture"</th>
+                                            <th>"Quant / Dtype"</th>
+                                            <th>"Params"</th>
+                                            {header(ModelSort::Size, "Size")}
+                                            {header(ModelSort::Modified, "Modified")}
                                             <th>"Path"</th>
+                                            <th></th>
                                         </tr>
                                     </thead>
                                     <tbody>
                                         {list
                                             .into_iter()
                                             .map(|entry| {
+                                                let path = entry.path.clone();
+                                                let pathForLabel = entry.path.clone();
+                                                let onDelete = move |_| {
+                                                    let path = path.clone();
+                                                    if confirmDelete.get_untracked().as_ref() != Some(&path) {
+                                                        setConfirmDelete.set(Some(path));
+                                                        return;
+                                                    }
+                                                    setConfirmDelete.set(None);
+                                                    setBusy.set(true);
+                                                    #[cfg(feature = "hydrate")]
+                                                    wasm_bindgen_futures::spawn_local(async move {
+                                                        let result = delete_model(path.clone()).await;
+                                                        if let Some(toasts) = toasts {
+                                                            match result {
+                                                                Ok(()) => {
+                                                                    toasts.push(format!("Deleted {path}"), ToastLevel::Success)
+                                                                }
+                                                                Err(e) => toasts.push(e.to_string(), ToastLevel::Error),
+                                                            }
+                                                        }
+                                                        setBusy.set(false);
+                                                        fetch();
+                                                    });
+                                                };
                                                 view! {
                                                     <tr>
-                                                        <td>{entry.name.clone()}</td>
+                                                        <td>
+                                                            {entry.name.clone()}
+                                                            {entry
+                                                                .model_name
+                                                                .clone()
+                                                                .map(|name| {
+                                                                    view! { <div class="model-subtitle">{name}</div> }
+                                                                })}
+                                                        </td>
                                                         <td>{entry.format.clone()}</td>
+                                                        <td>{entry.architecture.clone().unwrap_or_default()}</td>
+                                                        // safetensors files have a dtype rather than a quant type
+                                                        <td>
+                                                            {entry
+                                                                .quantization
+                                                                .clone()
+                                                                .or_else(|| entry.dtype.clone())
+                                                                .unwrap_or_default()}
+                                                        </td>
+                                                        <td title=entry
+                                                            .tensor_count
+                                                            .map(|n| format!("{n} tensors"))>
+                                                            {entry.param_count.map(format_params).unwrap_or_default()}
+                                                        </td>
                                                         <td>{format_size(entry.size_bytes)}</td>
+                                                        <td>
+                                                            {entry
+                                                                .modified
+                                                                .map(|t| format_relative(t, now))
+                                                                .unwrap_or_else(|| "\u{2014}".to_string())}
+                                                        </td>
                                                         <td
                                                             style="word-break: break-all; font-size: 0.75rem; color: var(--text-secondary);"
                                                         >
                                                             {entry.path.clone()}
                                                         </td>
+                                                        <td>
+                                                            <button
+                                                                class="btn btn-sm btn-danger"
+                                                                disabled=move || busy.get()
+                                                                on:click=onDelete
+                                                            >
+                                                                {move || {
+                                                                    if confirmDelete.get().as_ref() == Some(&pathForLabel) {
+                                                                        "Confirm Delete"
+                                                                    } else {
+                                                                        "Delete"
+                                                                    }
+                                                                }}
+                                                            </button>
+                                                        </td>
                                                     </tr>
                                                 }
                                             })
                                             .collect_view()}
                                     </tbody>
                                 </table>
+                                <Pager page=page total=count />
                             </div>
                         }
                             .into_any()
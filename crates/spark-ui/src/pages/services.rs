@@ -0,0 +1,219 @@
+use leptos::prelude::*;
+use spark_types::{Capabilities, ContainerActionResult, ServiceSummary};
+
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::toast::ToastContext;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::ToastLevel;
+
+#[server]
+async fn get_services() -> Result<Vec<ServiceSummary>, ServerFnError> {
+    spark_providers::services::list()
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn get_capabilities() -> Result<Capabilities, ServerFnError> {
+    Ok(use_context::<Capabilities>().unwrap_or_default())
+}
+
+#[server]
+async fn service_action(unit: String, action: String) -> Result<ContainerActionResult, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::services::run_action(&options.services, &unit, &action).await)
+}
+
+/// Status dot class for a unit's `ACTIVE` state.
+fn active_class(active: &str) -> &'static str {
+    match active {
+        "active" => "status-running",
+        "failed" => "status-stopped",
+        "inactive" => "status-inactive",
+        _ => "status-other",
+    }
+}
+
+#[component]
+pub fn ServicesPage() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (services, setServices) = signal(Option::<Result<Vec<ServiceSummary>, String>>::None);
+    #[allow(unused_variables)]
+    let (capabilities, setCapabilities) = signal(Option::<Capabilities>::None);
+    #[allow(unused_variables)]
+    let (pendingUnit, setPendingUnit) = signal(Option::<String>::None);
+    let (filter, setFilter) = signal(String::new());
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
+
+    // Unlike container actions these are off by default, so hide them until confirmed
+    let actionsAllowed = move || capabilities.get().is_some_and(|caps| caps.allows_service_actions());
+
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_services().await.map_err(|e| e.to_string());
+                setServices.set(Some(result));
+            });
+        }
+    };
+
+    #[cfg(feature = "hydrate")]
+    {
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(caps) = get_capabilities().await {
+                setCapabilities.set(Some(caps));
+            }
+        });
+        fetch();
+        use_poll(10, fetch);
+    }
+
+    #[allow(unused_variables)]
+    let runAction = move |unit: String, action: &'static str| {
+        setPendingUnit.set(Some(unit.clone()));
+        #[cfg(feature = "hydrate")]
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = service_action(unit, action.to_string()).await;
+            if let Some(toasts) = toasts {
+                match result {
+                    Ok(res) if res.success => toasts.push(res.message, ToastLevel::Success),
+                    Ok(res) => toasts.push(res.message, ToastLevel::Error),
+                    Err(e) => toasts.push(e.to_string(), ToastLevel::Error),
+                }
+            }
+            setPendingUnit.set(None);
+            fetch();
+        });
+    };
+
+    view! {
+        <div class="dashboard-header">
+            <h1>"Services"</h1>
+            <p class="subtitle">"systemd units"</p>
+        </div>
+        {move || match services.get() {
+            None => {
+                view! {
+                    <div class="loading">
+                        <div class="spinner"></div>
+                        "Loading services..."
+                    </div>
+                }
+                    .into_any()
+            }
+            Some(Err(e)) => {
+                view! {
+                    <div class="card">
+                        <p style="color: var(--danger)">"Failed to list services: " {e}</p>
+                    </div>
+                }
+                    .into_any()
+            }
+            Some(Ok(list)) => {
+                let needle = filter.get().to_lowercase();
+                let total = list.len();
+                let shown: Vec<ServiceSummary> = list
+                    .into_iter()
+                    .filter(|s| {
+                        needle.is_empty()
+                            || s.name.to_lowercase().contains(&needle)
+                            || s.description.to_lowercase().contains(&needle)
+                    })
+                    .collect();
+                view! {
+                    <div class="card">
+                        <div class="card-title-row">
+                            <div class="card-title">
+                                {format!("{} of {total} services", shown.len())}
+                            </div>
+                            <input
+                                class="table-filter"
+                                type="search"
+                                placeholder="Filter by name or description"
+                                prop:value=move || filter.get()
+                                on:input=move |ev| setFilter.set(event_target_value(&ev))
+                            />
+                        </div>
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>"Unit"</th>
+                                    <th>"Description"</th>
+                                    <th>"Load"</th>
+                                    <th>"State"</th>
+                                    <th></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {shown
+                                    .into_iter()
+                                    .map(|service| {
+                                        let isActive = service.active == "active";
+                                        let unit = service.name.clone();
+                                        let unitForPending = service.name.clone();
+                                        let busy = move || {
+                                            pendingUnit.get().as_ref() == Some(&unitForPending)
+                                        };
+                                        let button = move |action: &'static str, label: &'static str| {
+                                            let unit = unit.clone();
+                                            let busy = busy.clone();
+                                            view! {
+                                                <button
+                                                    class="btn btn-sm btn-ghost"
+                                                    disabled=busy
+                                                    on:click=move |_| runAction(unit.clone(), action)
+                                                >
+                                                    {label}
+                                                </button>
+                                            }
+                                        };
+                                        let actions = move || {
+                                            actionsAllowed()
+                                                .then(|| {
+                                                    if isActive {
+                                                        view! {
+                                                            {button("restart", "Restart")}
+                                                            {button("stop", "Stop")}
+                                                        }
+                                                            .into_any()
+                                                    } else {
+                                                        button("start", "Start").into_any()
+                                                    }
+                                                })
+                                                .map(|buttons| view! { <div class="service-actions">{buttons}</div> })
+                                        };
+                                        view! {
+                                            <tr>
+                                                <td style="word-break: break-all;">{service.name.clone()}</td>
+                                                <td style="color: var(--text-secondary);">
+                                                    {service.description.clone()}
+                                                </td>
+                                                <td>{service.load.clone()}</td>
+                                                <td>
+                                                    <span class="service-state">
+                                                        <span class=format!(
+                                                            "status-badge {}",
+                                                            active_class(&service.active),
+                                                        )></span>
+                                                        {format!("{} ({})", service.active, service.sub)}
+                                                    </span>
+                                                </td>
+                                                <td>{actions}</td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    </div>
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
@@ -0,0 +1,631 @@
+use leptos::prelude::*;
+use spark_types::{ContainerActionResult, DirUsage, DiskMetrics, ImageSummary, VolumeSummary};
+
+use crate::components::gauge::gauge_color;
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+use crate::components::toast::ToastContext;
+#[cfg(feature = "hydrate")]
+use crate::components::toast::ToastLevel;
+use crate::pages::dashboard::format_bytes;
+
+#[server]
+async fn get_filesystems(include_pseudo: bool) -> Result<Vec<DiskMetrics>, ServerFnError> {
+    spark_providers::disk::list_filesystems(include_pseudo)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn get_usage_roots() -> Result<Vec<String>, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(options.disk.usage_roots())
+}
+
+#[server]
+async fn get_dir_usage(path: String) -> Result<Vec<DirUsage>, ServerFnError> {
+    use spark_providers::{disk, ProviderOptions};
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    let root = disk::usage_root(&options.disk, &path)
+        .await
+        .map_err(ServerFnError::new)?;
+    disk::dir_usage(&options.disk, &root, disk::DEFAULT_USAGE_TOP)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn get_images() -> Result<Vec<ImageSummary>, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::docker::list_images(&options.docker)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn remove_image(image_id: String) -> Result<ContainerActionResult, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::docker::remove_image(&options.docker, &image_id).await)
+}
+
+#[server]
+async fn get_volumes() -> Result<Vec<VolumeSummary>, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    spark_providers::docker::list_volumes(&options.docker)
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[server]
+async fn prune_volumes() -> Result<ContainerActionResult, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::docker::prune_volumes(&options.docker).await)
+}
+
+#[server]
+async fn prune_images() -> Result<ContainerActionResult, ServerFnError> {
+    use spark_providers::ProviderOptions;
+    let options = use_context::<ProviderOptions>().unwrap_or_default();
+    Ok(spark_providers::docker::prune_images(&options.docker).await)
+}
+
+/// Report an action's outcome as a toast.
+#[cfg(feature = "hydrate")]
+fn toast_result(toasts: Option<ToastContext>, result: Result<ContainerActionResult, ServerFnError>) {
+    let Some(toasts) = toasts else {
+        return;
+    };
+    match result {
+        Ok(res) if res.success => toasts.push(res.message, ToastLevel::Success),
+        Ok(res) => toasts.push(res.message, ToastLevel::Error),
+        Err(e) => toasts.push(e.to_string(), ToastLevel::Error),
+    }
+}
+
+#[component]
+pub fn StoragePage() -> impl IntoView {
+    view! {
+        <div class="dashboard-header">
+            <h1>"Storage"</h1>
+            <p class="subtitle">"Filesystems, container images and volumes"</p>
+        </div>
+        <FilesystemsSection />
+        <DiskUsageSection />
+        <ImagesSection />
+        <VolumesSection />
+    }
+}
+
+fn usage_pct(used: u64, total: u64) -> f32 {
+    if total > 0 {
+        (used as f64 / total as f64 * 100.0) as f32
+    } else {
+        0.0
+    }
+}
+
+#[component]
+fn FilesystemsSection() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (filesystems, setFilesystems) = signal(Option::<Result<Vec<DiskMetrics>, String>>::None);
+    let showPseudo = RwSignal::new(false);
+
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            let includePseudo = showPseudo.get_untracked();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_filesystems(includePseudo).await.map_err(|e| e.to_string());
+                setFilesystems.set(Some(result));
+            });
+        }
+    };
+
+    #[cfg(feature = "hydrate")]
+    {
+        // Runs once on mount and again whenever the checkbox changes
+        Effect::new(move |_| {
+            showPseudo.track();
+            fetch();
+        });
+        use_poll(30, fetch);
+    }
+
+    view! {
+        <div class="card">
+            <div class="card-title-row">
+                <div class="card-title">"Filesystems"</div>
+                <label class="card-toggle">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || showPseudo.get()
+                        on:change=move |ev| showPseudo.set(event_target_checked(&ev))
+                    />
+                    "Show pseudo filesystems"
+                </label>
+            </div>
+            {move || match filesystems.get() {
+                None => {
+                    view! {
+                        <div class="loading">
+                            <div class="spinner"></div>
+                            "Loading filesystems..."
+                        </div>
+                    }
+                        .into_any()
+                }
+                Some(Err(e)) => {
+                    view! { <p style="color: var(--danger)">"Failed to load filesystems: " {e}</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) if list.is_empty() => {
+                    view! { <p style="color: var(--text-secondary)">"No filesystems found"</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) => {
+                    view! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>"Mount"</th>
+                                    <th>"Type"</th>
+                                    <th>"Device"</th>
+                                    <th>"Size"</th>
+                                    <th>"Used"</th>
+                                    <th>"Available"</th>
+                                    <th>"Usage"</th>
+                                    <th>"Inodes"</th>
+                                    <th>"I/O"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {list
+                                    .into_iter()
+                                    .map(|fs| {
+                                        let pct = usage_pct(fs.used_bytes, fs.total_bytes);
+                                        let inodes = (fs.inodes_total > 0)
+                                            .then(|| usage_pct(fs.inodes_used, fs.inodes_total));
+                                        let io = fs
+                                            .read_bytes_per_sec
+                                            .zip(fs.write_bytes_per_sec)
+                                            .map(|(read, write)| {
+                                                format!(
+                                                    "R {}/s \u{00B7} W {}/s",
+                                                    format_bytes(read as u64),
+                                                    format_bytes(write as u64),
+                                                )
+                                            });
+                                        view! {
+                                            <tr>
+                                                <td style="word-break: break-all;">{fs.mount_point.clone()}</td>
+                                                <td>{fs.fs_type.clone()}</td>
+                                                <td style="word-break: break-all; font-size: 0.75rem; color: var(--text-secondary);">
+                                                    {fs.device.clone()}
+                                                </td>
+                                                <td>{format_bytes(fs.total_bytes)}</td>
+                                                <td>{format_bytes(fs.used_bytes)}</td>
+                                                <td>{format_bytes(fs.available_bytes)}</td>
+                                                <td>
+                                                    <div class="usage-bar" title=format!("{pct:.1}%")>
+                                                        <div
+                                                            class="usage-bar-fill"
+                                                            style=format!(
+                                                                "width: {:.1}%; background-color: {};",
+                                                                pct.min(100.0),
+                                                                gauge_color(pct),
+                                                            )
+                                                        ></div>
+                                                    </div>
+                                                    <span class="usage-bar-label">{format!("{pct:.0}%")}</span>
+                                                </td>
+                                                <td style=inodes
+                                                    .filter(|pct| *pct >= 90.0)
+                                                    .map(|_| "color: #ef4444")>
+                                                    {inodes
+                                                        .map(|pct| format!("{pct:.0}%"))
+                                                        .unwrap_or_else(|| "\u{2014}".to_string())}
+                                                </td>
+                                                <td style="white-space: nowrap;">
+                                                    {io.unwrap_or_else(|| "\u{2014}".to_string())}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+/// The largest directories under a chosen mount. `du` can take a while on a big disk, so
+/// it only runs when asked and never polls.
+#[component]
+fn DiskUsageSection() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (roots, setRoots) = signal(Vec::<String>::new());
+    let selected = RwSignal::new(String::new());
+    #[allow(unused_variables)]
+    let (usage, setUsage) = signal(Option::<Result<Vec<DirUsage>, String>>::None);
+    #[allow(unused_variables)]
+    let (busy, setBusy) = signal(false);
+
+    #[cfg(feature = "hydrate")]
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Ok(list) = get_usage_roots().await {
+            if let Some(first) = list.first() {
+                selected.set(first.clone());
+            }
+            setRoots.set(list);
+        }
+    });
+
+    let onScan = move |_| {
+        setBusy.set(true);
+        #[cfg(feature = "hydrate")]
+        {
+            let path = selected.get_untracked();
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_dir_usage(path).await.map_err(|e| e.to_string());
+                setUsage.set(Some(result));
+                setBusy.set(false);
+            });
+        }
+    };
+
+    view! {
+        <div class="card storage-section">
+            <div class="card-title-row">
+                <div class="card-title">"Disk Usage"</div>
+                <div class="dir-usage-controls">
+                    <select on:change=move |ev| selected.set(event_target_value(&ev))>
+                        {move || {
+                            roots
+                                .get()
+                                .into_iter()
+                                .map(|root| {
+                                    let isSelected = root == selected.get_untracked();
+                                    view! {
+                                        <option value=root.clone() selected=isSelected>
+                                            {root.clone()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </select>
+                    <button
+                        class="btn btn-sm"
+                        title="List the largest directories; this walks the disk and can take a while"
+                        disabled=move || busy.get() || selected.get().is_empty()
+                        on:click=onScan
+                    >
+                        {move || if busy.get() { "Scanning..." } else { "Scan" }}
+                    </button>
+                </div>
+            </div>
+            {move || match usage.get() {
+                None => {
+                    view! {
+                        <p style="color: var(--text-secondary)">
+                            "Pick a mount and scan it to see which directories take the most space."
+                        </p>
+                    }
+                        .into_any()
+                }
+                Some(Err(e)) => {
+                    view! { <p style="color: var(--danger)">"Failed to scan: " {e}</p> }.into_any()
+                }
+                Some(Ok(list)) if list.is_empty() => {
+                    view! { <p style="color: var(--text-secondary)">"No directories found"</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) => {
+                    // Entries come largest first, so bars are scaled to the first one
+                    let largest = list.first().map(|d| d.size_bytes).unwrap_or(0);
+                    view! {
+                        <ul class="dir-usage-list">
+                            {list
+                                .into_iter()
+                                .map(|dir| {
+                                    let pct = usage_pct(dir.size_bytes, largest);
+                                    view! {
+                                        <li class="dir-usage-row">
+                                            <span class="dir-usage-path" title=dir.path.clone()>
+                                                {dir.path.clone()}
+                                            </span>
+                                            <div class="dir-usage-bar">
+                                                <div
+                                                    class="usage-bar-fill"
+                                                    style=format!(
+                                                        "width: {pct:.1}%; background-color: var(--accent);",
+                                                    )
+                                                ></div>
+                                            </div>
+                                            <span class="dir-usage-size">{format_bytes(dir.size_bytes)}</span>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn ImagesSection() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (images, setImages) = signal(Option::<Result<Vec<ImageSummary>, String>>::None);
+    // Image whose delete button has been clicked once and now asks for confirmation
+    #[allow(unused_variables)]
+    let (confirmDelete, setConfirmDelete) = signal(Option::<String>::None);
+    #[allow(unused_variables)]
+    let (busy, setBusy) = signal(false);
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
+
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_images().await.map_err(|e| e.to_string());
+                setImages.set(Some(result));
+            });
+        }
+    };
+
+    #[cfg(feature = "hydrate")]
+    {
+        fetch();
+        use_poll(30, fetch);
+    }
+
+    let onPrune = move |_| {
+        setBusy.set(true);
+        #[cfg(feature = "hydrate")]
+        wasm_bindgen_futures::spawn_local(async move {
+            toast_result(toasts, prune_images().await);
+            setBusy.set(false);
+            fetch();
+        });
+    };
+
+    view! {
+        <div class="card storage-section">
+            <div class="card-title-row">
+                <div class="card-title">"Images"</div>
+                <button
+                    class="btn btn-sm btn-ghost"
+                    title="Remove untagged images no container uses"
+                    disabled=move || busy.get()
+                    on:click=onPrune
+                >
+                    "Prune Dangling"
+                </button>
+            </div>
+            {move || match images.get() {
+                None => {
+                    view! {
+                        <div class="loading">
+                            <div class="spinner"></div>
+                            "Loading images..."
+                        </div>
+                    }
+                        .into_any()
+                }
+                Some(Err(e)) => {
+                    view! { <p style="color: var(--danger)">"Failed to load images: " {e}</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) if list.is_empty() => {
+                    view! { <p style="color: var(--text-secondary)">"No images found"</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) => {
+                    view! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>"Image"</th>
+                                    <th>"ID"</th>
+                                    <th>"Size"</th>
+                                    <th>"Created"</th>
+                                    <th></th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {list
+                                    .into_iter()
+                                    .map(|image| {
+                                        let imageId = image.id.clone();
+                                        let idForLabel = image.id.clone();
+                                        let onDelete = move |_| {
+                                            let id = imageId.clone();
+                                            if confirmDelete.get_untracked().as_ref() != Some(&id) {
+                                                setConfirmDelete.set(Some(id));
+                                                return;
+                                            }
+                                            setConfirmDelete.set(None);
+                                            setBusy.set(true);
+                                            #[cfg(feature = "hydrate")]
+                                            wasm_bindgen_futures::spawn_local(async move {
+                                                toast_result(toasts, remove_image(id).await);
+                                                setBusy.set(false);
+                                                fetch();
+                                            });
+                                        };
+                                        view! {
+                                            <tr>
+                                                <td>{format!("{}:{}", image.repo, image.tag)}</td>
+                                                <td style="font-size: 0.75rem; color: var(--text-secondary);">
+                                                    {image.id.clone()}
+                                                </td>
+                                                <td>{format_bytes(image.size_bytes)}</td>
+                                                <td>{image.created.clone()}</td>
+                                                <td>
+                                                    <button
+                                                        class="btn btn-sm btn-danger"
+                                                        disabled=move || busy.get()
+                                                        on:click=onDelete
+                                                    >
+                                                        {move || {
+                                                            if confirmDelete.get().as_ref() == Some(&idForLabel) {
+                                                                "Confirm Delete"
+                                                            } else {
+                                                                "Delete"
+                                                            }
+                                                        }}
+                                                    </button>
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
+
+#[component]
+fn VolumesSection() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (volumes, setVolumes) = signal(Option::<Result<Vec<VolumeSummary>, String>>::None);
+    // Prune deletes data, so the first click only arms it
+    #[allow(unused_variables)]
+    let (confirmPrune, setConfirmPrune) = signal(false);
+    #[allow(unused_variables)]
+    let (busy, setBusy) = signal(false);
+    #[allow(unused_variables)]
+    let toasts = use_context::<ToastContext>();
+
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_volumes().await.map_err(|e| e.to_string());
+                setVolumes.set(Some(result));
+            });
+        }
+    };
+
+    #[cfg(feature = "hydrate")]
+    {
+        fetch();
+        use_poll(30, fetch);
+    }
+
+    let onPrune = move |_| {
+        if !confirmPrune.get_untracked() {
+            setConfirmPrune.set(true);
+            return;
+        }
+        setConfirmPrune.set(false);
+        setBusy.set(true);
+        #[cfg(feature = "hydrate")]
+        wasm_bindgen_futures::spawn_local(async move {
+            toast_result(toasts, prune_volumes().await);
+            setBusy.set(false);
+            fetch();
+        });
+    };
+
+    view! {
+        <div class="card storage-section">
+            <div class="card-title-row">
+                <div class="card-title">"Volumes"</div>
+                <button
+                    class="btn btn-sm btn-danger"
+                    title="Remove unused anonymous volumes"
+                    disabled=move || busy.get()
+                    on:click=onPrune
+                >
+                    {move || if confirmPrune.get() { "Confirm Prune" } else { "Prune Unused" }}
+                </button>
+            </div>
+            {move || match volumes.get() {
+                None => {
+                    view! {
+                        <div class="loading">
+                            <div class="spinner"></div>
+                            "Loading volumes..."
+                        </div>
+                    }
+                        .into_any()
+                }
+                Some(Err(e)) => {
+                    view! { <p style="color: var(--danger)">"Failed to load volumes: " {e}</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) if list.is_empty() => {
+                    view! { <p style="color: var(--text-secondary)">"No volumes found"</p> }
+                        .into_any()
+                }
+                Some(Ok(list)) => {
+                    view! {
+                        <table>
+                            <thead>
+                                <tr>
+                                    <th>"Name"</th>
+                                    <th>"Driver"</th>
+                                    <th>"Size"</th>
+                                    <th>"Status"</th>
+                                    <th>"Mountpoint"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                {list
+                                    .into_iter()
+                                    .map(|volume| {
+                                        let (statusText, statusStyle) = match volume.in_use {
+                                            Some(true) => ("In use", "color: var(--text-secondary)"),
+                                            Some(false) => ("Unused", "color: var(--warning)"),
+                                            None => ("\u{2014}", "color: var(--text-secondary)"),
+                                        };
+                                        view! {
+                                            <tr>
+                                                <td style="word-break: break-all;">{volume.name.clone()}</td>
+                                                <td>{volume.driver.clone()}</td>
+                                                <td>
+                                                    {volume
+                                                        .size_bytes
+                                                        .map(format_bytes)
+                                                        .unwrap_or_else(|| "\u{2014}".to_string())}
+                                                </td>
+                                                <td style=statusStyle>{statusText}</td>
+                                                <td style="word-break: break-all; font-size: 0.75rem; color: var(--text-secondary);">
+                                                    {volume.mountpoint.clone()}
+                                                </td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </tbody>
+                        </table>
+                    }
+                        .into_any()
+                }
+            }}
+        </div>
+    }
+}
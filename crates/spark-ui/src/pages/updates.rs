@@ -0,0 +1,87 @@
+use leptos::prelude::*;
+use spark_types::UpdateStatus;
+
+#[cfg(feature = "hydrate")]
+use crate::components::refresh::use_poll;
+
+#[server]
+async fn get_updates() -> Result<UpdateStatus, ServerFnError> {
+    spark_providers::updates::collect()
+        .await
+        .map_err(ServerFnError::new)
+}
+
+#[component]
+pub fn UpdatesPage() -> impl IntoView {
+    #[allow(unused_variables)]
+    let (status, setStatus) = signal(Option::<Result<UpdateStatus, String>>::None);
+
+    #[allow(unused_variables)]
+    let fetch = move || {
+        #[cfg(feature = "hydrate")]
+        {
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = get_updates().await.map_err(|e| e.to_string());
+                setStatus.set(Some(result));
+            });
+        }
+    };
+
+    #[cfg(feature = "hydrate")]
+    {
+        fetch();
+        // Updates arrive a few times a day at most, and counting them reads the apt lists
+        use_poll(300, fetch);
+    }
+
+    view! {
+        <div class="dashboard-header">
+            <h1>"Updates"</h1>
+            <p class="subtitle">"Pending OS package updates. Install them on the host with apt; the console never does."</p>
+        </div>
+        {move || match status.get() {
+            None => {
+                view! {
+                    <div class="loading">
+                        <div class="spinner"></div>
+                        "Checking for updates..."
+                    </div>
+                }
+                    .into_any()
+            }
+            Some(Err(e)) => {
+                view! {
+                    <div class="card">
+                        <p style="color: var(--danger)">"Failed to check for updates: " {e}</p>
+                    </div>
+                }
+                    .into_any()
+            }
+            Some(Ok(status)) => {
+                let securityStyle = (status.security > 0).then_some("color: var(--warning)");
+                let (rebootText, rebootStyle) = if status.reboot_required {
+                    ("Required", Some("color: var(--warning)"))
+                } else {
+                    ("Not needed", None)
+                };
+                view! {
+                    <div class="dashboard-grid">
+                        <div class="card">
+                            <div class="card-title">"Available Updates"</div>
+                            <div class="update-count">{status.total}</div>
+                        </div>
+                        <div class="card">
+                            <div class="card-title">"Security Updates"</div>
+                            <div class="update-count" style=securityStyle>{status.security}</div>
+                        </div>
+                        <div class="card">
+                            <div class="card-title">"Reboot"</div>
+                            <div class="update-count" style=rebootStyle>{rebootText}</div>
+                        </div>
+                    </div>
+                }
+                    .into_any()
+            }
+        }}
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// Used for the page title, nav and login page until `ui.site_name` is set.
+pub const DEFAULT_SITE_NAME: &str = "Spark Console";
+/// Refresh rate until `ui.refresh_secs` is set or the user picks one in the nav.
+pub const DEFAULT_REFRESH_SECS: u64 = 2;
+
+/// The `[ui]` section of the console config.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UiOptions {
+    /// Refresh rate used until the user picks one in the nav.
+    pub refresh_secs: u64,
+    /// Name for the page title, nav and login page, to tell several consoles apart.
+    pub site_name: String,
+    /// `#rgb` or `#rrggbb` colour replacing the default accent green.
+    pub accent_color: Option<String>,
+}
+
+impl Default for UiOptions {
+    fn default() -> Self {
+        Self {
+            refresh_secs: DEFAULT_REFRESH_SECS,
+            site_name: DEFAULT_SITE_NAME.to_string(),
+            accent_color: None,
+        }
+    }
+}